@@ -0,0 +1,139 @@
+//! Compare the time and peak heap memory needed to deserialize a large metadata document:
+//!
+//! * by reading the whole file into a `String` and then calling `Edmx::from_str`
+//! * by calling `Edmx::from_reader` on a buffered file reader
+//! * by calling `Edmx::from_reader_skipping_annotations` on a buffered file reader
+//!
+//! The metadata document is `test_data/mock_service.xml` padded with generated `<Annotations>` elements.  Its size in
+//! megabytes can be passed as the first argument:
+//!
+//! `cargo bench --bench metadata_parsing -- 8`
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    env,
+    fs::{self, File},
+    io::BufReader,
+    path::Path,
+    str::FromStr,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use parse_sap_odata::edmx::Edmx;
+
+static PATH_TO_MOCK_SERVICE: &str = "./test_data/mock_service.xml";
+static DEFAULT_SIZE_MB: usize = 4;
+static ITERATIONS: u32 = 3;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Wrap the system allocator to record the peak number of bytes allocated
+struct PeakAlloc;
+
+static ALLOCATED: AtomicUsize = AtomicUsize::new(0);
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for PeakAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+
+        if !ptr.is_null() {
+            let allocated = ALLOCATED.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+            PEAK.fetch_max(allocated, Ordering::Relaxed);
+        }
+
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        ALLOCATED.fetch_sub(layout.size(), Ordering::Relaxed);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: PeakAlloc = PeakAlloc;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn gen_annotations(idx: usize) -> String {
+    format!(
+        r#"  <Annotations Target="GWSAMPLE_BASIC.Product/Property{idx}" xmlns="http://docs.oasis-open.org/odata/ns/edm">
+    <Annotation Term="com.sap.vocabularies.Common.v1.Label" String="Generated label number {idx}"/>
+    <Annotation Term="com.sap.vocabularies.UI.v1.DataPoint">
+      <Record><PropertyValue Property="Value" PropertyPath="Property{idx}"/></Record>
+    </Annotation>
+  </Annotations>
+"#
+    )
+}
+
+// Write a copy of the mock service whose schema is padded with annotations until the file has the given size
+fn write_large_metadata(path: &Path, size_mb: usize) {
+    let xml = fs::read_to_string(PATH_TO_MOCK_SERVICE).expect("Unable to read the mock service metadata");
+    let (head, tail) = xml.split_at(xml.find("</Schema>").expect("Mock service metadata has no schema"));
+    let mut padded = String::with_capacity(size_mb * 1024 * 1024 + xml.len());
+
+    padded.push_str(head);
+
+    for idx in 0.. {
+        if padded.len() >= size_mb * 1024 * 1024 {
+            break;
+        }
+
+        padded.push_str(&gen_annotations(idx));
+    }
+
+    padded.push_str(tail);
+    fs::write(path, padded).expect("Unable to write the padded metadata");
+}
+
+fn measure<F: Fn() -> Edmx>(label: &str, parse: F) {
+    let mut elapsed = Duration::ZERO;
+    let mut peak_bytes = 0;
+
+    for _ in 0..ITERATIONS {
+        let baseline = ALLOCATED.load(Ordering::Relaxed);
+        PEAK.store(baseline, Ordering::Relaxed);
+
+        let start = Instant::now();
+        let edmx = parse();
+        elapsed += start.elapsed();
+        peak_bytes = peak_bytes.max(PEAK.load(Ordering::Relaxed) - baseline);
+
+        drop(edmx);
+    }
+
+    println!(
+        "{:<40} {:>10.1} ms {:>10.1} MB",
+        label,
+        elapsed.as_secs_f64() * 1000.0 / ITERATIONS as f64,
+        peak_bytes as f64 / (1024.0 * 1024.0)
+    );
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn main() {
+    // `cargo bench` passes `--bench` to benchmarks that do not use the default harness
+    let size_mb = env::args()
+        .skip(1)
+        .find_map(|arg| arg.parse::<usize>().ok())
+        .unwrap_or(DEFAULT_SIZE_MB);
+    let path = env::temp_dir().join(format!("parse_sap_odata_bench_{size_mb}mb.xml"));
+
+    write_large_metadata(&path, size_mb);
+
+    println!("Deserializing {} MB of metadata ({ITERATIONS} iterations)", size_mb);
+    println!("{:<40} {:>13} {:>13}", "Method", "Mean time", "Peak heap");
+
+    measure("read_to_string + Edmx::from_str", || {
+        let xml = fs::read_to_string(&path).unwrap();
+        Edmx::from_str(&xml).unwrap()
+    });
+    measure("Edmx::from_reader", || {
+        Edmx::from_reader(BufReader::new(File::open(&path).unwrap())).unwrap()
+    });
+    measure("Edmx::from_reader_skipping_annotations", || {
+        Edmx::from_reader_skipping_annotations(BufReader::new(File::open(&path).unwrap())).unwrap()
+    });
+
+    let _ = fs::remove_file(&path);
+}
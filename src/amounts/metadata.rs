@@ -0,0 +1,44 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::{
+    amounts::{UnitKind, UnitPairing},
+    parser::generate::{gen_ident, gen_owned_string},
+};
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+impl UnitKind {
+    pub fn member_name(&self) -> &'static str {
+        match self {
+            UnitKind::Currency => "Currency",
+            UnitKind::UnitOfMeasure => "UnitOfMeasure",
+            UnitKind::Unspecified => "Unspecified",
+        }
+    }
+}
+
+impl ToTokens for UnitKind {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let member = gen_ident(self.member_name());
+        tokens.extend(quote!(UnitKind::#member))
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Metadata Module Generation
+/// Generate the source code that declares an instance of this UnitPairing
+impl ToTokens for UnitPairing {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let amount_property = gen_owned_string(&self.amount_property);
+        let unit_property = gen_owned_string(&self.unit_property);
+        let kind = &self.kind;
+
+        tokens.extend(quote! {
+            UnitPairing {
+                amount_property: #amount_property,
+                unit_property: #unit_property,
+                kind: #kind,
+            }
+        })
+    }
+}
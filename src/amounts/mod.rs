@@ -0,0 +1,112 @@
+#[cfg(feature = "parser")]
+pub mod metadata;
+
+use std::fmt::Formatter;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{property::Property, sap_semantics::property::SAPSemanticsProperty};
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// An amount together with the currency code in which it is expressed
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Money<T> {
+    pub amount: T,
+    pub currency: String,
+}
+
+impl<T> Money<T> {
+    pub fn new(amount: T, currency: &str) -> Money<T> {
+        Money {
+            amount,
+            currency: currency.to_owned(),
+        }
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Money<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.amount, self.currency)
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// A quantity together with its unit of measure
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Quantity<T> {
+    pub quantity: T,
+    pub unit: String,
+}
+
+impl<T> Quantity<T> {
+    pub fn new(quantity: T, unit: &str) -> Quantity<T> {
+        Quantity {
+            quantity,
+            unit: unit.to_owned(),
+        }
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Quantity<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.quantity, self.unit)
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// The kind of unit referenced by an `sap:unit` annotation
+///
+/// This is derived from the `sap:semantics` annotation of the referenced property
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum UnitKind {
+    /// The unit property is annotated with `sap:semantics="currency-code"`
+    Currency,
+    /// The unit property is annotated with `sap:semantics="unit-of-measure"`
+    UnitOfMeasure,
+    /// The unit property carries no semantic annotation
+    Unspecified,
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Pairs an amount or quantity property with the property holding its currency code or unit of measure
+///
+/// Both property names are the OData names as they appear in the metadata document
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UnitPairing {
+    pub amount_property: String,
+    pub unit_property: String,
+    pub kind: UnitKind,
+}
+
+impl UnitPairing {
+    /// Find all the properties annotated with `sap:unit` whose unit property exists in the same list of properties
+    ///
+    /// Pairings are returned in the alphabetical order of the amount property names
+    pub fn find_in(props: &[Property]) -> Vec<UnitPairing> {
+        let mut amounts: Vec<_> = props.iter().filter(|prop| prop.sap_annotations.unit.is_some()).collect();
+        amounts.sort();
+
+        amounts
+            .into_iter()
+            .filter_map(|amount| {
+                let unit_name = amount.sap_annotations.unit.as_ref()?;
+                let unit = props.iter().find(|prop| prop.odata_name.eq(unit_name))?;
+
+                Some(UnitPairing {
+                    amount_property: amount.odata_name.clone(),
+                    unit_property: unit.odata_name.clone(),
+                    kind: match unit.sap_annotations.semantics {
+                        Some(SAPSemanticsProperty::CurrencyCode) => UnitKind::Currency,
+                        Some(SAPSemanticsProperty::UnitOfMeasure) => UnitKind::UnitOfMeasure,
+                        _ => UnitKind::Unspecified,
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(feature = "parser")]
+#[cfg(test)]
+pub mod unit_tests;
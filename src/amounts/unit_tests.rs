@@ -0,0 +1,77 @@
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+    str::FromStr,
+};
+
+use super::{Money, Quantity, UnitKind, UnitPairing};
+use crate::{edmx::data_services::schema::entity_type::EntityType, test_utils::*};
+use quote::quote;
+
+static PATH_TO_ENTITY_TYPE_PRODUCT: &str = "./test_data/entity_type_product.xml";
+
+fn fetch_entity_type_product() -> Result<EntityType, String> {
+    let mut xml_buffer: Vec<u8> = Vec::new();
+    let test_data = File::open(Path::new(PATH_TO_ENTITY_TYPE_PRODUCT)).unwrap();
+    let _file_size = BufReader::new(test_data).read_to_end(&mut xml_buffer);
+
+    match String::from_utf8(xml_buffer) {
+        Ok(xml) => EntityType::from_str(&xml).map_err(|err| err.to_string()),
+        Err(err) => Err(format!("XML test data was not in UTF8 format: {err}")),
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_find_unit_pairings() -> Result<(), String> {
+    let product = fetch_entity_type_product()?;
+    let pairings = UnitPairing::find_in(&product.properties);
+
+    handle_test_comparison(&pairings.len(), &5)?;
+
+    // Pairings are sorted by amount property name
+    handle_test_comparison(&pairings[0].amount_property, &"Depth".to_string())?;
+    handle_test_comparison(&pairings[0].unit_property, &"DimUnit".to_string())?;
+    handle_test_bool(pairings[0].kind == UnitKind::UnitOfMeasure)?;
+    handle_test_comparison(&pairings[2].amount_property, &"Price".to_string())?;
+    handle_test_comparison(&pairings[2].unit_property, &"CurrencyCode".to_string())?;
+    handle_test_bool(pairings[2].kind == UnitKind::Currency)?;
+    handle_test_comparison(&pairings[3].amount_property, &"WeightMeasure".to_string())?;
+    handle_test_comparison(&pairings[3].unit_property, &"WeightUnit".to_string())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_ignore_unit_property_not_in_same_type() -> Result<(), String> {
+    let mut product = fetch_entity_type_product()?;
+    product.properties.retain(|prop| !prop.odata_name.eq("CurrencyCode"));
+
+    let pairings = UnitPairing::find_in(&product.properties);
+
+    handle_test_comparison(&pairings.len(), &4)?;
+    handle_test_bool(pairings.iter().all(|up| !up.amount_property.eq("Price")))
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_display_money_and_quantity() -> Result<(), String> {
+    handle_test_comparison(&Money::new(12.5, "EUR").to_string(), &"12.5 EUR".to_string())?;
+    handle_test_comparison(&Quantity::new(3, "KG").to_string(), &"3 KG".to_string())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_generate_unit_pairing_instance() -> Result<(), String> {
+    let pairing = UnitPairing {
+        amount_property: "Price".to_string(),
+        unit_property: "CurrencyCode".to_string(),
+        kind: UnitKind::Currency,
+    };
+    let src_lines = tokens_to_rust_src(quote!(static PAIRING: UnitPairing = #pairing;));
+
+    handle_test_comparison(&src_lines[0], &"static PAIRING: UnitPairing = UnitPairing {".to_string())?;
+    handle_test_comparison(&src_lines[1], &"amount_property: \"Price\".to_owned(),".to_string())?;
+    handle_test_comparison(&src_lines[2], &"unit_property: \"CurrencyCode\".to_owned(),".to_string())?;
+    handle_test_comparison(&src_lines[3], &"kind: UnitKind::Currency,".to_string())
+}
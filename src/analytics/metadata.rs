@@ -0,0 +1,46 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::{
+    analytics::{AnalyticalKind, AnalyticalProperty},
+    parser::generate::{gen_ident, gen_opt_string, gen_owned_string},
+};
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+impl AnalyticalKind {
+    pub fn member_name(&self) -> &'static str {
+        match self {
+            AnalyticalKind::Dimension => "Dimension",
+            AnalyticalKind::Measure => "Measure",
+            AnalyticalKind::Attribute => "Attribute",
+        }
+    }
+}
+
+impl ToTokens for AnalyticalKind {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let member = gen_ident(self.member_name());
+        tokens.extend(quote!(AnalyticalKind::#member))
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Metadata Module Generation
+/// Generate the source code that declares an instance of this AnalyticalProperty
+impl ToTokens for AnalyticalProperty {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let property_name = gen_owned_string(&self.property_name);
+        let kind = &self.kind;
+        let attribute_for = gen_opt_string(&self.attribute_for);
+        let super_ordinate = gen_opt_string(&self.super_ordinate);
+
+        tokens.extend(quote! {
+            AnalyticalProperty {
+                property_name: #property_name,
+                kind: #kind,
+                attribute_for: #attribute_for,
+                super_ordinate: #super_ordinate,
+            }
+        })
+    }
+}
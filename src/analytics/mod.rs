@@ -0,0 +1,211 @@
+use crate::{property::Property, sap_annotations::aggregation_role::SAPAggregationRoleProperty};
+
+#[cfg(feature = "parser")]
+pub mod metadata;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// The part played by a property of an entity type annotated with `sap:semantics="aggregate"`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AnalyticalKind {
+    /// `sap:aggregation-role="dimension"`: values are grouped by this property
+    Dimension,
+    /// `sap:aggregation-role="measure"`: values are aggregated over the selected dimensions
+    Measure,
+    /// `sap:attribute-for="<dimension>"`: this property further describes a dimension
+    Attribute,
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Describes the analytical role of a property in an aggregate entity type
+///
+/// All property names are the OData names as they appear in the metadata document
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AnalyticalProperty {
+    pub property_name: String,
+    pub kind: AnalyticalKind,
+    /// The dimension described by this attribute
+    pub attribute_for: Option<String>,
+    /// The dimension that must also be selected when this dimension is selected
+    pub super_ordinate: Option<String>,
+}
+
+impl AnalyticalProperty {
+    /// Find the dimensions, measures and attributes in a list of properties
+    ///
+    /// Properties are returned in the alphabetical order of their names
+    pub fn find_in(props: &[Property]) -> Vec<AnalyticalProperty> {
+        let mut analytical_props: Vec<AnalyticalProperty> = props
+            .iter()
+            .filter_map(|prop| {
+                let sap_annos = &prop.sap_annotations;
+                let kind = match sap_annos.aggregation_role {
+                    Some(SAPAggregationRoleProperty::Dimension) => AnalyticalKind::Dimension,
+                    Some(SAPAggregationRoleProperty::Measure) => AnalyticalKind::Measure,
+                    _ if sap_annos.attribute_for.is_some() => AnalyticalKind::Attribute,
+                    _ => return None,
+                };
+
+                Some(AnalyticalProperty {
+                    property_name: prop.odata_name.clone(),
+                    kind,
+                    attribute_for: sap_annos.attribute_for.clone(),
+                    super_ordinate: sap_annos.super_ordinate.clone(),
+                })
+            })
+            .collect();
+
+        analytical_props.sort_by(|a, b| a.property_name.cmp(&b.property_name));
+        analytical_props
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Implemented by the generated `<EntityType>Dimension` enums.
+/// Dimension enums also contain the attributes of those dimensions
+pub trait AggregateDimension: Copy + PartialEq {
+    fn odata_name(&self) -> &'static str;
+    /// If this is an attribute, the dimension it describes
+    fn attribute_for(&self) -> Option<Self>;
+    /// The dimension that must also be selected when this dimension is selected
+    fn super_ordinate(&self) -> Option<Self>;
+}
+
+/// Implemented by the generated `<EntityType>Measure` enums
+pub trait AggregateMeasure: Copy + PartialEq {
+    fn odata_name(&self) -> &'static str;
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// The reasons why a combination of dimensions and measures cannot be requested from an aggregate entity set
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AggregateQueryError {
+    /// Neither a dimension nor a measure has been selected
+    NothingSelected,
+    /// An attribute has been selected without the dimension it describes
+    MissingDimension { attribute: String, dimension: String },
+    /// A dimension has been selected without its super-ordinate dimension
+    MissingSuperOrdinate { dimension: String, super_ordinate: String },
+}
+
+impl std::fmt::Display for AggregateQueryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AggregateQueryError::NothingSelected => write!(f, "No dimensions or measures have been selected"),
+            AggregateQueryError::MissingDimension { attribute, dimension } => {
+                write!(f, "Attribute {attribute} cannot be selected without dimension {dimension}")
+            },
+            AggregateQueryError::MissingSuperOrdinate { dimension, super_ordinate } => write!(
+                f,
+                "Dimension {dimension} cannot be selected without its super-ordinate dimension {super_ordinate}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for AggregateQueryError {}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Builds the `$select` query option for an aggregate entity set
+///
+/// The server aggregates the selected measures over the selected dimensions, so only certain combinations of properties
+/// are valid:
+/// * At least one dimension or measure must be selected
+/// * An attribute can only be selected together with the dimension it describes
+/// * A dimension with a super-ordinate dimension can only be selected together with that super-ordinate dimension
+///
+/// ```rust,ignore
+/// let select = AggregateQuery::new()
+///     .dimension(SalesOrderQueryResultDimension::CompanyCode)
+///     .measure(SalesOrderQueryResultMeasure::NetAmount)
+///     .to_select()?;
+///
+/// assert_eq!(select, "$select=CompanyCode,NetAmount");
+/// ```
+#[derive(Clone, Debug)]
+pub struct AggregateQuery<D: AggregateDimension, M: AggregateMeasure> {
+    dimensions: Vec<D>,
+    measures: Vec<M>,
+}
+
+impl<D: AggregateDimension, M: AggregateMeasure> Default for AggregateQuery<D, M> {
+    fn default() -> Self {
+        AggregateQuery {
+            dimensions: vec![],
+            measures: vec![],
+        }
+    }
+}
+
+impl<D: AggregateDimension, M: AggregateMeasure> AggregateQuery<D, M> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Group the results by this dimension (or dimension attribute)
+    pub fn dimension(mut self, dimension: D) -> Self {
+        if !self.dimensions.contains(&dimension) {
+            self.dimensions.push(dimension);
+        }
+        self
+    }
+
+    /// Aggregate this measure
+    pub fn measure(mut self, measure: M) -> Self {
+        if !self.measures.contains(&measure) {
+            self.measures.push(measure);
+        }
+        self
+    }
+
+    /// Check that the selected combination of dimensions and measures can be aggregated
+    pub fn validate(&self) -> Result<(), AggregateQueryError> {
+        if self.dimensions.is_empty() && self.measures.is_empty() {
+            return Err(AggregateQueryError::NothingSelected);
+        }
+
+        for dim in &self.dimensions {
+            if let Some(owner) = dim.attribute_for() {
+                if !self.dimensions.contains(&owner) {
+                    return Err(AggregateQueryError::MissingDimension {
+                        attribute: dim.odata_name().to_owned(),
+                        dimension: owner.odata_name().to_owned(),
+                    });
+                }
+            }
+
+            if let Some(super_ordinate) = dim.super_ordinate() {
+                if !self.dimensions.contains(&super_ordinate) {
+                    return Err(AggregateQueryError::MissingSuperOrdinate {
+                        dimension: dim.odata_name().to_owned(),
+                        super_ordinate: super_ordinate.odata_name().to_owned(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The comma-separated list of selected property names: dimensions first, then measures
+    pub fn select_list(&self) -> Result<String, AggregateQueryError> {
+        self.validate()?;
+
+        Ok(self
+            .dimensions
+            .iter()
+            .map(|dim| dim.odata_name())
+            .chain(self.measures.iter().map(|measure| measure.odata_name()))
+            .collect::<Vec<&str>>()
+            .join(","))
+    }
+
+    /// The complete `$select` query option
+    pub fn to_select(&self) -> Result<String, AggregateQueryError> {
+        Ok(format!("$select={}", self.select_list()?))
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(feature = "parser")]
+#[cfg(test)]
+pub mod unit_tests;
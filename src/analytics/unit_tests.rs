@@ -0,0 +1,145 @@
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+    str::FromStr,
+};
+
+use super::*;
+use crate::{edmx::data_services::schema::entity_type::EntityType, test_utils::*};
+use quote::quote;
+
+static PATH_TO_ENTITY_TYPE_SALES_ORDER_QUERY: &str = "./test_data/entity_type_sales_order_query.xml";
+
+pub fn fetch_entity_type_sales_order_query() -> Result<EntityType, String> {
+    let mut xml_buffer: Vec<u8> = Vec::new();
+    let test_data = File::open(Path::new(PATH_TO_ENTITY_TYPE_SALES_ORDER_QUERY)).unwrap();
+    let _file_size = BufReader::new(test_data).read_to_end(&mut xml_buffer);
+
+    match String::from_utf8(xml_buffer) {
+        Ok(xml) => EntityType::from_str(&xml).map_err(|err| err.to_string()),
+        Err(err) => Err(format!("XML test data was not in UTF8 format: {err}")),
+    }
+}
+
+// Hand-written equivalents of the enums generated for the SalesOrderQueryResult entity type
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TestDimension {
+    CompanyCode,
+    Customer,
+    CustomerCountry,
+}
+
+impl AggregateDimension for TestDimension {
+    fn odata_name(&self) -> &'static str {
+        match *self {
+            Self::CompanyCode => "CompanyCode",
+            Self::Customer => "Customer",
+            Self::CustomerCountry => "CustomerCountry",
+        }
+    }
+    fn attribute_for(&self) -> Option<Self> {
+        match *self {
+            Self::CustomerCountry => Some(Self::Customer),
+            _ => None,
+        }
+    }
+    fn super_ordinate(&self) -> Option<Self> {
+        match *self {
+            Self::Customer => Some(Self::CompanyCode),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TestMeasure {
+    NetAmount,
+}
+
+impl AggregateMeasure for TestMeasure {
+    fn odata_name(&self) -> &'static str {
+        "NetAmount"
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_find_analytical_properties() -> Result<(), String> {
+    let query_result = fetch_entity_type_sales_order_query()?;
+    handle_test_bool(query_result.is_aggregate())?;
+
+    let analytical_props = AnalyticalProperty::find_in(&query_result.properties);
+
+    // Properties are sorted by name
+    handle_test_comparison(&analytical_props.len(), &5)?;
+    handle_test_comparison(&analytical_props[0].property_name, &"CompanyCode".to_string())?;
+    handle_test_bool(analytical_props[0].kind == AnalyticalKind::Dimension)?;
+    handle_test_comparison(&analytical_props[1].property_name, &"Customer".to_string())?;
+    handle_test_comparison_opt(&analytical_props[1].super_ordinate, &Some("CompanyCode".to_string()))?;
+    handle_test_comparison(&analytical_props[2].property_name, &"CustomerCountry".to_string())?;
+    handle_test_bool(analytical_props[2].kind == AnalyticalKind::Attribute)?;
+    handle_test_comparison_opt(&analytical_props[2].attribute_for, &Some("Customer".to_string()))?;
+    handle_test_comparison(&analytical_props[3].property_name, &"NetAmount".to_string())?;
+    handle_test_bool(analytical_props[3].kind == AnalyticalKind::Measure)?;
+    handle_test_comparison(&analytical_props[4].property_name, &"OrderCount".to_string())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_build_valid_aggregate_select() -> Result<(), String> {
+    let select = AggregateQuery::new()
+        .measure(TestMeasure::NetAmount)
+        .dimension(TestDimension::CompanyCode)
+        .dimension(TestDimension::Customer)
+        .dimension(TestDimension::CompanyCode)
+        .to_select()
+        .map_err(|err| err.to_string())?;
+
+    handle_test_comparison(&select, &"$select=CompanyCode,Customer,NetAmount".to_string())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_reject_invalid_aggregate_selects() -> Result<(), String> {
+    let nothing: AggregateQuery<TestDimension, TestMeasure> = AggregateQuery::new();
+    handle_test_bool(nothing.validate() == Err(AggregateQueryError::NothingSelected))?;
+
+    let no_super_ordinate: AggregateQuery<TestDimension, TestMeasure> =
+        AggregateQuery::new().dimension(TestDimension::Customer);
+    handle_test_bool(
+        no_super_ordinate.validate()
+            == Err(AggregateQueryError::MissingSuperOrdinate {
+                dimension: "Customer".to_string(),
+                super_ordinate: "CompanyCode".to_string(),
+            }),
+    )?;
+
+    let no_dimension: AggregateQuery<TestDimension, TestMeasure> =
+        AggregateQuery::new().dimension(TestDimension::CustomerCountry);
+    handle_test_bool(
+        no_dimension.to_select()
+            == Err(AggregateQueryError::MissingDimension {
+                attribute: "CustomerCountry".to_string(),
+                dimension: "Customer".to_string(),
+            }),
+    )
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_generate_analytical_property_instance() -> Result<(), String> {
+    let analytical_prop = AnalyticalProperty {
+        property_name: "CustomerCountry".to_string(),
+        kind: AnalyticalKind::Attribute,
+        attribute_for: Some("Customer".to_string()),
+        super_ordinate: None,
+    };
+    let src_lines = tokens_to_rust_src(quote!(static PROP: AnalyticalProperty = #analytical_prop;));
+
+    handle_test_comparison(&src_lines[0], &"static PROP: AnalyticalProperty = AnalyticalProperty {".to_string())?;
+    handle_test_comparison(&src_lines[1], &"property_name: \"CustomerCountry\".to_owned(),".to_string())?;
+    handle_test_comparison(&src_lines[2], &"kind: AnalyticalKind::Attribute,".to_string())?;
+    handle_test_comparison(&src_lines[3], &"attribute_for: Some(\"Customer\".to_owned()),".to_string())?;
+    handle_test_comparison(&src_lines[4], &"super_ordinate: None,".to_string())
+}
@@ -0,0 +1,36 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::{capabilities::EntitySetCapabilities, parser::generate::gen_opt_number};
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Metadata Module Generation
+/// Generate the source code that declares an instance of this EntitySetCapabilities.
+/// The fully qualified type name is used because the instance is declared in the service document module
+impl ToTokens for EntitySetCapabilities {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let is_creatable = self.is_creatable;
+        let is_updatable = self.is_updatable;
+        let is_deletable = self.is_deletable;
+        let is_searchable = self.is_searchable;
+        let is_pageable = self.is_pageable;
+        let is_topable = self.is_topable;
+        let is_countable = self.is_countable;
+        let requires_filter = self.requires_filter;
+        let max_page_size = gen_opt_number(&self.max_page_size);
+
+        tokens.extend(quote! {
+            parse_sap_odata::capabilities::EntitySetCapabilities {
+                is_creatable: #is_creatable,
+                is_updatable: #is_updatable,
+                is_deletable: #is_deletable,
+                is_searchable: #is_searchable,
+                is_pageable: #is_pageable,
+                is_topable: #is_topable,
+                is_countable: #is_countable,
+                requires_filter: #requires_filter,
+                max_page_size: #max_page_size,
+            }
+        })
+    }
+}
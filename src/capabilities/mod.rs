@@ -0,0 +1,244 @@
+use std::marker::PhantomData;
+
+use crate::{query::percent_encode, sap_annotations::entity_set::SAPAnnotationsEntitySet};
+
+#[cfg(feature = "parser")]
+pub mod metadata;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// The operations and query options supported by an entity set, as declared by its SAP annotations
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EntitySetCapabilities {
+    pub is_creatable: bool,
+    pub is_updatable: bool,
+    pub is_deletable: bool,
+    pub is_searchable: bool,
+    pub is_pageable: bool,
+    pub is_topable: bool,
+    pub is_countable: bool,
+    pub requires_filter: bool,
+    pub max_page_size: Option<u32>,
+}
+
+impl From<&SAPAnnotationsEntitySet> for EntitySetCapabilities {
+    fn from(sap_annos: &SAPAnnotationsEntitySet) -> Self {
+        EntitySetCapabilities {
+            is_creatable: sap_annos.is_creatable,
+            is_updatable: sap_annos.is_updatable,
+            is_deletable: sap_annos.is_deletable,
+            is_searchable: sap_annos.is_searchable,
+            is_pageable: sap_annos.is_pageable,
+            is_topable: sap_annos.is_topable,
+            is_countable: sap_annos.is_countable,
+            requires_filter: sap_annos.requires_filter,
+            max_page_size: sap_annos.max_page_size,
+        }
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Implemented by the marker type generated for each entity set in the `entity_sets` module of the service document
+pub trait EntitySet {
+    const NAME: &'static str;
+    const CAPABILITIES: EntitySetCapabilities;
+}
+
+// Marker traits implemented only by the entity sets that support the corresponding operation or query option
+pub trait Creatable: EntitySet {}
+pub trait Updatable: EntitySet {}
+pub trait Deletable: EntitySet {}
+pub trait Searchable: EntitySet {}
+pub trait Pageable: EntitySet {}
+pub trait Topable: EntitySet {}
+pub trait Countable: EntitySet {}
+/// Implemented by entity sets that can be read without a `$filter` query option
+pub trait FilterOptional: EntitySet {}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HttpMethod {
+    Get,
+    Post,
+    Merge,
+    Delete,
+}
+
+impl std::fmt::Display for HttpMethod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HttpMethod::Get => write!(f, "GET"),
+            HttpMethod::Post => write!(f, "POST"),
+            HttpMethod::Merge => write!(f, "MERGE"),
+            HttpMethod::Delete => write!(f, "DELETE"),
+        }
+    }
+}
+
+/// The method and the path (relative to the service root) of a request
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ODataRequest {
+    pub method: HttpMethod,
+    pub path: String,
+}
+
+/// Request to create an entity in an entity set
+pub fn create_request<S: Creatable>() -> ODataRequest {
+    ODataRequest {
+        method: HttpMethod::Post,
+        path: S::NAME.to_owned(),
+    }
+}
+
+/// Request to update the entity identified by a key predicate such as `('HT-1000')`
+pub fn update_request<S: Updatable>(key_predicate: &str) -> ODataRequest {
+    ODataRequest {
+        method: HttpMethod::Merge,
+        path: format!("{}{key_predicate}", S::NAME),
+    }
+}
+
+/// Request to delete the entity identified by a key predicate such as `('HT-1000')`
+pub fn delete_request<S: Deletable>(key_predicate: &str) -> ODataRequest {
+    ODataRequest {
+        method: HttpMethod::Delete,
+        path: format!("{}{key_predicate}", S::NAME),
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Query builder state before a `$filter` has been added
+pub struct Unfiltered;
+/// Query builder state after a `$filter` has been added
+pub struct Filtered;
+
+/// Builds a request to read an entity set, allowing only the query options supported by that entity set.
+///
+/// The entity set's capabilities are checked at compile time:
+/// * `top()` is only available for entity sets that are topable
+/// * `skip()` is only available for entity sets that are pageable
+/// * `search()` is only available for entity sets that are searchable
+/// * `inline_count()` is only available for entity sets that are countable
+/// * `build()` is only available after calling `filter()` if the entity set requires a filter
+///
+/// ```rust,ignore
+/// let request = QueryBuilder::<entity_sets::ProductSet>::new()
+///     .filter("Category eq 'Notebooks'")
+///     .top(10)
+///     .build();
+///
+/// assert_eq!(request.path, "ProductSet?$filter=Category%20eq%20%27Notebooks%27&$top=10");
+/// ```
+pub struct QueryBuilder<S: EntitySet, F = Unfiltered> {
+    options: Vec<(&'static str, String)>,
+    entity_set: PhantomData<S>,
+    filter_state: PhantomData<F>,
+}
+
+impl<S: EntitySet> Default for QueryBuilder<S, Unfiltered> {
+    fn default() -> Self {
+        QueryBuilder {
+            options: vec![],
+            entity_set: PhantomData,
+            filter_state: PhantomData,
+        }
+    }
+}
+
+impl<S: EntitySet> QueryBuilder<S, Unfiltered> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a `$filter` query option. The filter expression will be percent-encoded
+    pub fn filter(mut self, expression: &str) -> QueryBuilder<S, Filtered> {
+        self.options.push(("$filter", percent_encode(expression)));
+
+        QueryBuilder {
+            options: self.options,
+            entity_set: PhantomData,
+            filter_state: PhantomData,
+        }
+    }
+
+    /// Build the request. Only available for entity sets that do not require a filter
+    pub fn build(self) -> ODataRequest
+    where
+        S: FilterOptional,
+    {
+        self.into_request()
+    }
+}
+
+impl<S: EntitySet> QueryBuilder<S, Filtered> {
+    pub fn build(self) -> ODataRequest {
+        self.into_request()
+    }
+}
+
+impl<S: EntitySet, F> QueryBuilder<S, F> {
+    fn with_option(mut self, name: &'static str, value: String) -> Self {
+        self.options.push((name, value));
+        self
+    }
+
+    /// Add a `$select` query option containing the comma-separated property names
+    pub fn select(self, property_names: &[&str]) -> Self {
+        self.with_option("$select", property_names.join(","))
+    }
+
+    /// Add an `$orderby` query option, E.G. `"Price desc"`
+    pub fn order_by(self, order: &str) -> Self {
+        self.with_option("$orderby", percent_encode(order))
+    }
+
+    pub fn top(self, count: u32) -> Self
+    where
+        S: Topable,
+    {
+        self.with_option("$top", count.to_string())
+    }
+
+    pub fn skip(self, count: u32) -> Self
+    where
+        S: Pageable,
+    {
+        self.with_option("$skip", count.to_string())
+    }
+
+    pub fn search(self, search_term: &str) -> Self
+    where
+        S: Searchable,
+    {
+        self.with_option("search", percent_encode(search_term))
+    }
+
+    pub fn inline_count(self) -> Self
+    where
+        S: Countable,
+    {
+        self.with_option("$inlinecount", "allpages".to_owned())
+    }
+
+    fn into_request(self) -> ODataRequest {
+        let query_string = self
+            .options
+            .iter()
+            .map(|(name, value)| format!("{name}={value}"))
+            .collect::<Vec<String>>()
+            .join("&");
+
+        ODataRequest {
+            method: HttpMethod::Get,
+            path: if query_string.is_empty() {
+                S::NAME.to_owned()
+            } else {
+                format!("{}?{query_string}", S::NAME)
+            },
+        }
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(feature = "parser")]
+#[cfg(test)]
+pub mod unit_tests;
@@ -0,0 +1,118 @@
+use super::*;
+use crate::test_utils::*;
+use quote::quote;
+
+// Hand-written equivalents of the marker types generated in the `entity_sets` module of a service document
+struct ProductSet;
+struct SalesOrderQuerySet;
+
+static ALL_CAPABILITIES: EntitySetCapabilities = EntitySetCapabilities {
+    is_creatable: true,
+    is_updatable: true,
+    is_deletable: true,
+    is_searchable: true,
+    is_pageable: true,
+    is_topable: true,
+    is_countable: true,
+    requires_filter: false,
+    max_page_size: None,
+};
+
+impl EntitySet for ProductSet {
+    const NAME: &'static str = "ProductSet";
+    const CAPABILITIES: EntitySetCapabilities = ALL_CAPABILITIES;
+}
+impl Creatable for ProductSet {}
+impl Updatable for ProductSet {}
+impl Deletable for ProductSet {}
+impl Searchable for ProductSet {}
+impl Pageable for ProductSet {}
+impl Topable for ProductSet {}
+impl Countable for ProductSet {}
+impl FilterOptional for ProductSet {}
+
+// Read-only entity set that requires a filter and cannot be paged
+impl EntitySet for SalesOrderQuerySet {
+    const NAME: &'static str = "SalesOrderQuerySet";
+    const CAPABILITIES: EntitySetCapabilities = EntitySetCapabilities {
+        is_creatable: false,
+        is_updatable: false,
+        is_deletable: false,
+        is_searchable: false,
+        is_pageable: false,
+        is_topable: false,
+        is_countable: true,
+        requires_filter: true,
+        max_page_size: Some(500),
+    };
+}
+impl Countable for SalesOrderQuerySet {}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_build_query_with_options() -> Result<(), String> {
+    let request = QueryBuilder::<ProductSet>::new()
+        .select(&["ProductID", "Name"])
+        .top(10)
+        .skip(20)
+        .order_by("Price desc")
+        .build();
+
+    handle_test_comparison(&request.method.to_string(), &"GET".to_string())?;
+    handle_test_comparison(
+        &request.path,
+        &"ProductSet?$select=ProductID,Name&$top=10&$skip=20&$orderby=Price%20desc".to_string(),
+    )?;
+
+    let request = QueryBuilder::<ProductSet>::new().build();
+    handle_test_comparison(&request.path, &"ProductSet".to_string())
+}
+
+#[test]
+pub fn should_build_query_for_set_requiring_filter() -> Result<(), String> {
+    let request = QueryBuilder::<SalesOrderQuerySet>::new()
+        .inline_count()
+        .filter("CustomerID eq '100000000'")
+        .build();
+
+    handle_test_comparison(
+        &request.path,
+        &"SalesOrderQuerySet?$inlinecount=allpages&$filter=CustomerID%20eq%20%27100000000%27".to_string(),
+    )?;
+    handle_test_bool(SalesOrderQuerySet::CAPABILITIES.requires_filter)?;
+    handle_test_comparison(
+        &SalesOrderQuerySet::CAPABILITIES.max_page_size.unwrap_or_default().to_string(),
+        &"500".to_string(),
+    )
+}
+
+#[test]
+pub fn should_build_modifying_requests() -> Result<(), String> {
+    let create = create_request::<ProductSet>();
+    let update = update_request::<ProductSet>("('HT-1000')");
+    let delete = delete_request::<ProductSet>("('HT-1000')");
+
+    handle_test_comparison(&create.to_string_pair(), &"POST ProductSet".to_string())?;
+    handle_test_comparison(&update.to_string_pair(), &"MERGE ProductSet('HT-1000')".to_string())?;
+    handle_test_comparison(&delete.to_string_pair(), &"DELETE ProductSet('HT-1000')".to_string())
+}
+
+impl ODataRequest {
+    fn to_string_pair(&self) -> String {
+        format!("{} {}", self.method, self.path)
+    }
+}
+
+#[test]
+pub fn should_generate_capabilities_instance() -> Result<(), String> {
+    let caps = SalesOrderQuerySet::CAPABILITIES;
+    let src_lines = tokens_to_rust_src(quote!(static CAPS: EntitySetCapabilities = #caps;));
+
+    handle_test_comparison(
+        &src_lines[0],
+        &"static CAPS: EntitySetCapabilities = parse_sap_odata::capabilities::EntitySetCapabilities {".to_string(),
+    )?;
+    handle_test_bool(src_lines.contains(&"is_deletable: false,".to_string()))?;
+    handle_test_bool(src_lines.contains(&"requires_filter: true,".to_string()))?;
+    handle_test_bool(src_lines.contains(&"max_page_size: Some(500),".to_string()))
+}
@@ -0,0 +1,53 @@
+use parse_sap_atom_feed::deserializers::edm_decimal;
+use rust_decimal::Decimal;
+use serde::Deserializer;
+
+use super::to_nullable_with;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Wrap each of the scale-aware decimal deserializers in the parse-sap-atom-feed crate so that an element marked as
+// `m:null="true"` becomes `None`.  (The `_opt` variants in that crate also return `None` for values that fail to parse)
+macro_rules! nullable_decimal_deserializers {
+    ($($name:ident => $inner:ident),* $(,)?) => {
+        $(
+            pub fn $name<'de, D>(deserializer: D) -> Result<Option<Decimal>, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                to_nullable_with(deserializer, edm_decimal::$inner)
+            }
+        )*
+    };
+}
+
+nullable_decimal_deserializers! {
+    to_nullable_rust_decimal_0dp => to_rust_decimal_0dp,
+    to_nullable_rust_decimal_1dp => to_rust_decimal_1dp,
+    to_nullable_rust_decimal_2dp => to_rust_decimal_2dp,
+    to_nullable_rust_decimal_3dp => to_rust_decimal_3dp,
+    to_nullable_rust_decimal_4dp => to_rust_decimal_4dp,
+    to_nullable_rust_decimal_5dp => to_rust_decimal_5dp,
+    to_nullable_rust_decimal_6dp => to_rust_decimal_6dp,
+    to_nullable_rust_decimal_7dp => to_rust_decimal_7dp,
+    to_nullable_rust_decimal_8dp => to_rust_decimal_8dp,
+    to_nullable_rust_decimal_9dp => to_rust_decimal_9dp,
+    to_nullable_rust_decimal_10dp => to_rust_decimal_10dp,
+    to_nullable_rust_decimal_11dp => to_rust_decimal_11dp,
+    to_nullable_rust_decimal_12dp => to_rust_decimal_12dp,
+    to_nullable_rust_decimal_13dp => to_rust_decimal_13dp,
+    to_nullable_rust_decimal_14dp => to_rust_decimal_14dp,
+    to_nullable_rust_decimal_15dp => to_rust_decimal_15dp,
+    to_nullable_rust_decimal_16dp => to_rust_decimal_16dp,
+    to_nullable_rust_decimal_17dp => to_rust_decimal_17dp,
+    to_nullable_rust_decimal_18dp => to_rust_decimal_18dp,
+    to_nullable_rust_decimal_19dp => to_rust_decimal_19dp,
+    to_nullable_rust_decimal_20dp => to_rust_decimal_20dp,
+    to_nullable_rust_decimal_21dp => to_rust_decimal_21dp,
+    to_nullable_rust_decimal_22dp => to_rust_decimal_22dp,
+    to_nullable_rust_decimal_23dp => to_rust_decimal_23dp,
+    to_nullable_rust_decimal_24dp => to_rust_decimal_24dp,
+    to_nullable_rust_decimal_25dp => to_rust_decimal_25dp,
+    to_nullable_rust_decimal_26dp => to_rust_decimal_26dp,
+    to_nullable_rust_decimal_27dp => to_rust_decimal_27dp,
+    to_nullable_rust_decimal_28dp => to_rust_decimal_28dp,
+}
@@ -0,0 +1,89 @@
+pub mod edm_decimal;
+
+use std::{borrow::Cow, fmt::Display, str::FromStr};
+
+use serde::{
+    de::{self, value::StringDeserializer, IntoDeserializer},
+    Deserialize, Deserializer,
+};
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// The attribute and text content of a property element such as `<d:Age m:null="true"/>` or `<d:Age>42</d:Age>`
+#[derive(Deserialize)]
+struct NullableElement {
+    #[serde(rename = "@null", default)]
+    null: Option<String>,
+    #[serde(rename = "$text", default)]
+    text: Option<String>,
+}
+
+/// Deserialize the value of a nullable property from an Atom feed.
+///
+/// A property whose value is `null` is represented by an empty element with the attribute `m:null="true"`.
+/// Without this deserializer, such an element becomes `Some("")` for a `String` field and fails to deserialize for
+/// numeric or boolean fields.
+/// An empty element without `m:null="true"` is an empty string
+pub fn to_nullable<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+    T::Err: Display,
+{
+    let elem = NullableElement::deserialize(deserializer)?;
+
+    if elem.null.as_deref() == Some("true") {
+        Ok(None)
+    } else {
+        elem.text.unwrap_or_default().parse::<T>().map(Some).map_err(de::Error::custom)
+    }
+}
+
+/// As [`to_nullable`], except that the text of an element that is not `null` is handed to `deserialize_text`.
+///
+/// This allows deserializers that expect a plain string, such as the scale-aware decimal deserializers in the
+/// parse-sap-atom-feed crate, to be used for nullable properties
+pub fn to_nullable_with<'de, D, T, F>(deserializer: D, deserialize_text: F) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    F: FnOnce(StringDeserializer<D::Error>) -> Result<T, D::Error>,
+{
+    let elem = NullableElement::deserialize(deserializer)?;
+
+    if elem.null.as_deref() == Some("true") {
+        Ok(None)
+    } else {
+        deserialize_text(elem.text.unwrap_or_default().into_deserializer()).map(Some)
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// As `NullableElement`, but borrowing the text content from the input wherever possible
+#[derive(Deserialize)]
+struct NullableCowElement<'a> {
+    #[serde(rename = "@null", default)]
+    null: Option<String>,
+    #[serde(rename = "$text", default, borrow)]
+    text: Cow<'a, str>,
+}
+
+/// Deserialize the value of a nullable string property of a borrowed struct.
+///
+/// As [`to_nullable`], except that the value borrows from the input unless it contains escaped characters.  (Serde
+/// does not borrow when deserializing an `Option<Cow<str>>` directly)
+pub fn to_nullable_cow_str<'de: 'a, 'a, D>(deserializer: D) -> Result<Option<Cow<'a, str>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let elem = NullableCowElement::deserialize(deserializer)?;
+
+    if elem.null.as_deref() == Some("true") {
+        Ok(None)
+    } else {
+        Ok(Some(elem.text))
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(feature = "parser")]
+#[cfg(test)]
+pub mod unit_tests;
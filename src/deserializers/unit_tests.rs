@@ -0,0 +1,88 @@
+use serde::Deserialize;
+
+use super::*;
+use crate::test_utils::*;
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Contact {
+    #[serde(default, deserialize_with = "to_nullable")]
+    age: Option<i32>,
+    #[serde(default, deserialize_with = "to_nullable")]
+    middle_name: Option<String>,
+    #[serde(default, deserialize_with = "to_nullable")]
+    is_active: Option<bool>,
+}
+
+fn parse_contact(properties: &str) -> Result<Contact, String> {
+    quick_xml::de::from_str(&format!("<m:properties>{properties}</m:properties>")).map_err(|err| err.to_string())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_deserialize_null_values_as_none() -> Result<(), String> {
+    let contact = parse_contact(r#"<d:Age m:null="true"/><d:MiddleName m:null="true"/><d:IsActive m:null="true"/>"#)?;
+
+    handle_test_comparison_opt(&contact.age, &None)?;
+    handle_test_comparison_opt(&contact.middle_name, &None)?;
+    handle_test_comparison_opt(&contact.is_active, &None)
+}
+
+#[test]
+pub fn should_deserialize_nullable_values() -> Result<(), String> {
+    let contact = parse_contact("<d:Age>42</d:Age><d:MiddleName/><d:IsActive>true</d:IsActive>")?;
+
+    handle_test_comparison_opt(&contact.age, &Some(42))?;
+    handle_test_comparison_opt(&contact.middle_name, &Some(String::new()))?;
+    handle_test_comparison_opt(&contact.is_active, &Some(true))?;
+    handle_test_comparison_opt(&parse_contact("")?.age, &None)?;
+    handle_test_bool(parse_contact("<d:Age>forty two</d:Age>").is_err())
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct BorrowedContact<'a> {
+    #[serde(borrow)]
+    last_name: Cow<'a, str>,
+    #[serde(default, borrow, deserialize_with = "to_nullable_cow_str")]
+    middle_name: Option<Cow<'a, str>>,
+}
+
+#[test]
+pub fn should_borrow_unescaped_string_values() -> Result<(), String> {
+    let xml = "<m:properties><d:LastName>Smith</d:LastName><d:MiddleName>Q</d:MiddleName></m:properties>";
+    let contact: BorrowedContact = quick_xml::de::from_str(xml).map_err(|err| err.to_string())?;
+
+    handle_test_bool(matches!(contact.last_name, Cow::Borrowed("Smith")))?;
+    handle_test_bool(matches!(contact.middle_name, Some(Cow::Borrowed("Q"))))
+}
+
+#[test]
+pub fn should_own_escaped_and_null_string_values() -> Result<(), String> {
+    let xml = r#"<m:properties><d:LastName>Smith &amp; Sons</d:LastName><d:MiddleName m:null="true"/></m:properties>"#;
+    let contact: BorrowedContact = quick_xml::de::from_str(xml).map_err(|err| err.to_string())?;
+
+    handle_test_bool(matches!(&contact.last_name, Cow::Owned(name) if name.eq("Smith & Sons")))?;
+    handle_test_bool(contact.middle_name.is_none())
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Price {
+    #[serde(default, deserialize_with = "edm_decimal::to_nullable_rust_decimal_3dp")]
+    amount: Option<rust_decimal::Decimal>,
+}
+
+fn parse_price(properties: &str) -> Result<Price, String> {
+    quick_xml::de::from_str(&format!("<m:properties>{properties}</m:properties>")).map_err(|err| err.to_string())
+}
+
+#[test]
+pub fn should_deserialize_nullable_decimals_to_scale() -> Result<(), String> {
+    handle_test_comparison_opt(&parse_price(r#"<d:Amount m:null="true"/>"#)?.amount, &None)?;
+    handle_test_comparison_opt(&parse_price("")?.amount, &None)?;
+    handle_test_comparison_opt(
+        &parse_price("<d:Amount>12.5</d:Amount>")?.amount,
+        &Some(rust_decimal::Decimal::new(12500, 3)),
+    )
+}
@@ -28,6 +28,10 @@ impl DataServices {
         self.schemas.iter().find(|schema| schema.namespace == namespace)
     }
 
+    pub fn fetch_schema_mut(&mut self, namespace: &str) -> Option<&mut Schema> {
+        self.schemas.iter_mut().find(|schema| schema.namespace == namespace)
+    }
+
     pub fn default_schema(&self) -> Option<&Schema> {
         self.fetch_schema("Default")
     }
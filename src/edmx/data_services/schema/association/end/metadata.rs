@@ -1,40 +1,16 @@
 use super::End;
-use std::fmt::Formatter;
 
-use crate::parser::generate::syntax_fragments::{CLOSE_CURLY, COLON, COMMA, LINE_FEED, OPEN_CURLY};
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
 use crate::{
     parser::generate::{gen_opt_string, gen_owned_string},
     utils::to_upper_camel_case,
 };
 
-static MY_NAME: &[u8] = "End".as_bytes();
-
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-enum EndFieldNames {
-    Role,
-    EntitySet,
-    EndType,
-    Multiplicity,
-}
-
-impl EndFieldNames {
-    pub fn value(prop_name: EndFieldNames) -> &'static [u8] {
-        match prop_name {
-            EndFieldNames::Role => b"role",
-            EndFieldNames::EntitySet => b"entity_set",
-            EndFieldNames::EndType => b"end_type",
-            EndFieldNames::Multiplicity => b"multiplicity",
-        }
-    }
-}
-
-// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-fn line_from_end(prop_md: EndFieldNames, val: Vec<u8>) -> Vec<u8> {
-    [EndFieldNames::value(prop_md), COLON, &val, COMMA, LINE_FEED].concat()
-}
-
-impl std::fmt::Display for End {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+impl ToTokens for End {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
         let entity_set = if let Some(es) = &self.entity_set {
             Some(to_upper_camel_case(es)) // Convert to struct name
         } else {
@@ -54,17 +30,18 @@ impl std::fmt::Display for End {
             None
         };
 
-        let out_buffer: Vec<u8> = [
-            MY_NAME,
-            OPEN_CURLY,
-            &*line_from_end(EndFieldNames::Role, gen_owned_string(&self.role)),
-            &*line_from_end(EndFieldNames::EntitySet, gen_opt_string(&entity_set)),
-            &*line_from_end(EndFieldNames::EndType, gen_opt_string(&end_type)),
-            &*line_from_end(EndFieldNames::Multiplicity, gen_opt_string(&self.multiplicity)),
-            CLOSE_CURLY,
-        ]
-        .concat();
-
-        write!(f, "{}", String::from_utf8(out_buffer).unwrap())
+        let role = gen_owned_string(&self.role);
+        let entity_set = gen_opt_string(&entity_set);
+        let end_type = gen_opt_string(&end_type);
+        let multiplicity = gen_opt_string(&self.multiplicity);
+
+        tokens.extend(quote! {
+            End {
+                role: #role,
+                entity_set: #entity_set,
+                end_type: #end_type,
+                multiplicity: #multiplicity,
+            }
+        })
     }
 }
@@ -1,11 +1,13 @@
-use std::{collections::VecDeque, fmt::Formatter};
+use std::collections::VecDeque;
 
-use crate::parser::generate::syntax_fragments::{
-    CLOSE_PAREN, CLOSE_SQR, COLON, COMMA, END_BLOCK, LINE_FEED, NONE, OPEN_CURLY, OPEN_PAREN, OPEN_SQR, SOME,
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::{
+    edmx::data_services::schema::association::Association,
+    parser::generate::{gen_opt_value, gen_owned_string},
 };
-use crate::{edmx::data_services::schema::association::Association, parser::generate::gen_owned_string};
 
-static MY_NAME: &[u8] = "Association".as_bytes();
 static UNDERSCORE: &str = "_";
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
@@ -35,57 +37,20 @@ pub fn normalise_assoc_name(assoc_name: &str) -> String {
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-enum AssociationFieldNames {
-    Name,
-    SapContentVersion,
-    Ends,
-    ReferentialConstraint,
-}
-
-impl AssociationFieldNames {
-    pub fn value(prop_name: AssociationFieldNames) -> &'static [u8] {
-        match prop_name {
-            AssociationFieldNames::Name => b"name",
-            AssociationFieldNames::SapContentVersion => b"sap_content_version",
-            AssociationFieldNames::Ends => b"ends",
-            AssociationFieldNames::ReferentialConstraint => b"referential_constraint",
-        }
-    }
-}
-
-// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-fn line_into_association(out: &mut Vec<u8>, prop_md: AssociationFieldNames, val: Vec<u8>) {
-    out.extend_from_slice(AssociationFieldNames::value(prop_md));
-    out.extend_from_slice(COLON);
-    out.extend_from_slice(&val);
-    out.extend_from_slice(COMMA);
-    out.extend_from_slice(LINE_FEED);
-}
-
-impl std::fmt::Display for Association {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let ends = [
-            OPEN_SQR,
-            format!("{}", self.ends[0]).as_bytes(),
-            COMMA,
-            format!("{}", self.ends[1]).as_bytes(),
-            CLOSE_SQR,
-        ]
-        .concat();
-        let ref_con = if let Some(ref_con) = &self.referential_constraint {
-            [SOME, OPEN_PAREN, format!("{}", ref_con).as_bytes(), CLOSE_PAREN].concat()
-        } else {
-            NONE.to_vec()
-        };
-
-        let mut out_buffer: Vec<u8> = Vec::new();
-        out_buffer.extend_from_slice(MY_NAME);
-        out_buffer.extend_from_slice(OPEN_CURLY);
-        line_into_association(&mut out_buffer, AssociationFieldNames::Name, gen_owned_string(&self.name));
-        line_into_association(&mut out_buffer, AssociationFieldNames::SapContentVersion, gen_owned_string(&self.sap_content_version));
-        line_into_association(&mut out_buffer, AssociationFieldNames::Ends, ends);
-        line_into_association(&mut out_buffer, AssociationFieldNames::ReferentialConstraint, ref_con);
-        out_buffer.extend_from_slice(END_BLOCK);
-        write!(f, "{}", String::from_utf8(out_buffer).unwrap())
+impl ToTokens for Association {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = gen_owned_string(&self.name);
+        let sap_content_version = gen_owned_string(&self.sap_content_version);
+        let (end0, end1) = (&self.ends[0], &self.ends[1]);
+        let referential_constraint = gen_opt_value(&self.referential_constraint);
+
+        tokens.extend(quote! {
+            Association {
+                name: #name,
+                sap_content_version: #sap_content_version,
+                ends: [#end0, #end1],
+                referential_constraint: #referential_constraint,
+            }
+        })
     }
 }
@@ -1,56 +1,22 @@
-use std::fmt::Formatter;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
 
-use crate::parser::generate::syntax_fragments::{
-    CLOSE_CURLY, CLOSE_SQR, COLON, COMMA, LINE_FEED, OPEN_CURLY, VEC_BANG,
-};
 use crate::{
     edmx::data_services::schema::association::referential_constraint::dependent::Dependent,
     parser::generate::gen_owned_string,
 };
 
-static MY_NAME: &[u8] = "Dependent".as_bytes();
-
-// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-enum DependentFieldNames {
-    Role,
-    PropertyRefs,
-}
-
-impl DependentFieldNames {
-    fn value(prop_name: DependentFieldNames) -> &'static [u8] {
-        match prop_name {
-            DependentFieldNames::Role => b"role",
-            DependentFieldNames::PropertyRefs => b"property_refs",
-        }
-    }
-}
-
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-fn line_from_dependent(prop_md: DependentFieldNames, val: Vec<u8>) -> Vec<u8> {
-    [DependentFieldNames::value(prop_md), COLON, &val, COMMA, LINE_FEED].concat()
-}
-
-impl std::fmt::Display for Dependent {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let prop_refs_str = self
-            .property_refs
-            .iter()
-            .map(|pr| format!("{},", pr))
-            .collect::<String>()
-            .into_bytes();
-
-        let out_buffer: Vec<u8> = [
-            MY_NAME,
-            OPEN_CURLY,
-            &*line_from_dependent(DependentFieldNames::Role, gen_owned_string(&self.role)),
-            &*line_from_dependent(
-                DependentFieldNames::PropertyRefs,
-                [VEC_BANG, &*prop_refs_str, CLOSE_SQR].concat(),
-            ),
-            CLOSE_CURLY,
-        ]
-        .concat();
-
-        write!(f, "{}", String::from_utf8(out_buffer).unwrap())
+impl ToTokens for Dependent {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let role = gen_owned_string(&self.role);
+        let property_refs = &self.property_refs;
+
+        tokens.extend(quote! {
+            Dependent {
+                role: #role,
+                property_refs: vec![#(#property_refs),*],
+            }
+        })
     }
 }
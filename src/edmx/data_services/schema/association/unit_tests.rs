@@ -107,3 +107,12 @@ pub fn should_normalise_association_name_variations() -> Result<(), String> {
 
     Ok(())
 }
+
+#[test]
+pub fn should_reject_association_without_two_ends() -> Result<(), String> {
+    let xml = r#"<Association Name="Assoc_OneEnd" sap:content-version="1">
+        <End Type="GWSAMPLE_BASIC.Product" Multiplicity="1" Role="FromRole_Assoc_OneEnd"/>
+    </Association>"#;
+
+    handle_test_bool(Association::from_str(xml).is_err())
+}
@@ -1,57 +1,21 @@
-use std::fmt::Formatter;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
 
 use super::ComplexType;
 use crate::parser::generate::gen_owned_string;
-use crate::parser::generate::syntax_fragments::{
-    CLOSE_SQR, COLON, COMMA, COMPLEX_TYPE, END_BLOCK, LINE_FEED, OPEN_CURLY, VEC_BANG,
-};
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-pub enum ComplexTypeFieldNames {
-    Name,
-    Properties,
-}
-
-// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-impl ComplexTypeFieldNames {
-    pub fn value(prop_name: ComplexTypeFieldNames) -> &'static [u8] {
-        match prop_name {
-            ComplexTypeFieldNames::Name => b"name",
-            ComplexTypeFieldNames::Properties => b"properties",
-        }
-    }
-}
-
-// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-fn line_from(prop_md: ComplexTypeFieldNames, val: Vec<u8>) -> Vec<u8> {
-    [ComplexTypeFieldNames::value(prop_md), COLON, &val, COMMA, LINE_FEED].concat()
-}
-
 // Output a ComplexType instance as its own source code
-impl std::fmt::Display for ComplexType {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut out_buffer: Vec<u8> = [
-            // Start ComplexType declaration
-            COMPLEX_TYPE,
-            OPEN_CURLY,
-            &*line_from(ComplexTypeFieldNames::Name, gen_owned_string(&self.name)),
-            // Start vector of properties
-            ComplexTypeFieldNames::value(ComplexTypeFieldNames::Properties),
-            COLON,
-            VEC_BANG,
-            LINE_FEED,
-        ]
-        .concat();
-
-        for prop in &self.properties {
-            out_buffer.append(&mut prop.to_string().into_bytes());
-            out_buffer.extend_from_slice(COMMA);
-            out_buffer.extend_from_slice(LINE_FEED);
-        }
-
-        // End vector of properties and ComplexType declaration
-        out_buffer.append(&mut [CLOSE_SQR, END_BLOCK].concat());
-
-        write!(f, "{}", String::from_utf8(out_buffer).unwrap())
+impl ToTokens for ComplexType {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = gen_owned_string(&self.name);
+        let properties = &self.properties;
+
+        tokens.extend(quote! {
+            ComplexType {
+                name: #name,
+                properties: vec![#(#properties),*],
+            }
+        })
     }
 }
@@ -1,59 +1,25 @@
 use super::AssociationSet;
-use std::fmt::Formatter;
 
-use crate::parser::generate::gen_owned_string;
-use crate::parser::generate::syntax_fragments::{
-    CLOSE_CURLY, CLOSE_SQR, COLON, COMMA, LINE_FEED, OPEN_CURLY, OPEN_SQR,
-};
-
-static MY_NAME: &[u8] = "AssociationSet".as_bytes();
-
-// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-enum AssociationSetFieldNames {
-    Name,
-    Association,
-    SapAnnotations,
-    Ends,
-}
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
 
-impl AssociationSetFieldNames {
-    pub fn value(prop_name: AssociationSetFieldNames) -> &'static [u8] {
-        match prop_name {
-            AssociationSetFieldNames::Name => b"name",
-            AssociationSetFieldNames::Association => b"association",
-            AssociationSetFieldNames::SapAnnotations => b"sap_annotations",
-            AssociationSetFieldNames::Ends => b"ends",
-        }
-    }
-}
+use crate::parser::generate::gen_owned_string;
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-fn line_from_end(prop_md: AssociationSetFieldNames, val: Vec<u8>) -> Vec<u8> {
-    [AssociationSetFieldNames::value(prop_md), COLON, &val, COMMA, LINE_FEED].concat()
-}
-
-impl std::fmt::Display for AssociationSet {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let ends = [
-            OPEN_SQR,
-            format!("{}", self.ends[0]).as_bytes(),
-            COMMA,
-            format!("{}", self.ends[1]).as_bytes(),
-            CLOSE_SQR,
-        ]
-        .concat();
-
-        let out_buffer: Vec<u8> = [
-            MY_NAME,
-            OPEN_CURLY,
-            &*line_from_end(AssociationSetFieldNames::Name, gen_owned_string(&self.name)),
-            &*line_from_end(AssociationSetFieldNames::Association, gen_owned_string(&self.association)),
-            &*line_from_end(AssociationSetFieldNames::Ends, ends),
-            &*line_from_end(AssociationSetFieldNames::SapAnnotations, self.sap_annotations.to_string().into_bytes()),
-            CLOSE_CURLY,
-        ]
-        .concat();
-
-        write!(f, "{}", String::from_utf8(out_buffer).unwrap())
+impl ToTokens for AssociationSet {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = gen_owned_string(&self.name);
+        let association = gen_owned_string(&self.association);
+        let (end0, end1) = (&self.ends[0], &self.ends[1]);
+        let sap_annotations = &self.sap_annotations;
+
+        tokens.extend(quote! {
+            AssociationSet {
+                name: #name,
+                association: #association,
+                ends: [#end0, #end1],
+                sap_annotations: #sap_annotations,
+            }
+        })
     }
 }
@@ -1,110 +1,155 @@
-use crate::parser::generate::syntax_fragments::{derive_traits::*, *};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::parser::generate::syntax_fragments::derive_traits::*;
 use crate::{
-    edmx::data_services::schema::entity_container::EntityContainer, parser::generate::*, utils::to_upper_camel_case,
+    capabilities::EntitySetCapabilities,
+    edmx::data_services::schema::entity_container::EntityContainer,
+    parser::{
+        generate::{
+            type_attributes::{gen_type_attributes, resolve_derives},
+            *,
+        },
+        options::GeneratorOptions,
+    },
+    utils::to_upper_camel_case,
 };
 
+/// The traits always derived by the entity container enum
+static ENTITY_CONTAINER_ENUM_DERIVES: [DeriveTraits; 3] = [DeriveTraits::COPY, DeriveTraits::CLONE, DeriveTraits::DEBUG];
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// The "capabilities" function within the entity container enum implementation
+///   pub const fn capabilities(&self) -> parse_sap_odata::capabilities::EntitySetCapabilities {
+///       match *self {
+///           EnumName::Variant => parse_sap_odata::capabilities::EntitySetCapabilities { /* SNIP */ },
+fn gen_enum_fn_capabilities(enum_name: &str, ent_cont: &EntityContainer) -> TokenStream {
+    let enum_ident = gen_ident(enum_name);
+    let arms = ent_cont.entity_sets.iter().map(|ent_set| {
+        let variant_ident = gen_ident(&to_upper_camel_case(&ent_set.name));
+        let caps = EntitySetCapabilities::from(&ent_set.sap_annotations);
+        quote!(#enum_ident::#variant_ident => #caps,)
+    });
+
+    quote! {
+        pub const fn capabilities(&self) -> parse_sap_odata::capabilities::EntitySetCapabilities {
+            match *self {
+                #(#arms)*
+            }
+        }
+    }
+}
+
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 impl EntityContainer {
     /// Transforms an `EntityContainer` into an enumeration of entity set names.
-    /// Additionally, this enumeration is given three helper functions:
+    /// Additionally, this enumeration is given four helper functions:
     /// * `pub fn iterator() -> impl Iterator<Item = GwsampleBasicEntities> { /* SNIP */ }`
     /// * `pub const fn variant_name(&self) -> &'static str { /* SNIP */ }`
     /// * `pub fn variant_names() -> Vec<&'static str> { /* SNIP */ }`
-    pub fn to_enum_with_impl_into(&self, out: &mut Vec<u8>) {
+    /// * `pub const fn capabilities(&self) -> EntitySetCapabilities { /* SNIP */ }`
+    pub fn to_enum_with_impl(&self) -> TokenStream {
+        self.to_enum_with_options(&GeneratorOptions::default())
+    }
+
+    /// As [`to_enum_with_impl`](Self::to_enum_with_impl), but with the additional derives and attributes that the
+    /// generator options give the entity container enum
+    pub fn to_enum_with_options(&self, options: &GeneratorOptions) -> TokenStream {
         let cont_name_camel = to_upper_camel_case(&self.name);
+        let cont_ident = gen_ident(&cont_name_camel);
+        let rules = &options.type_attributes.entity_container_enum;
+        let derives = resolve_derives(&self.name, &ENTITY_CONTAINER_ENUM_DERIVES, rules, |derive| match derive {
+            DeriveTraits::DEFAULT => Err("the enum has no default variant".to_owned()),
+            _ => Ok(()),
+        });
+        let type_attrs = gen_type_attributes(&self.name, &derives, rules);
 
-        let mut enum_fn_iterator = gen_enum_fn_iter_start(&cont_name_camel);
-        let mut enum_fn_variant_name = gen_enum_impl_fn_variant_name();
+        let ent_set_names_camel = self
+            .entity_sets
+            .iter()
+            .map(|ent_set| to_upper_camel_case(&ent_set.name))
+            .collect::<Vec<String>>();
+        let variants = ent_set_names_camel.iter().map(|name| gen_ident(name));
 
-        let mut entities_enum = self.entity_sets.iter().fold(
-            [
-                &*gen_derive_str(&[DeriveTraits::COPY, DeriveTraits::CLONE, DeriveTraits::DEBUG]),
-                RUSTC_ALLOW_DEAD_CODE,
-                &*gen_enum_start(&cont_name_camel),
-            ]
-            .concat(),
-            |mut acc, ent_set| {
-                let ent_set_name_camel = to_upper_camel_case(&ent_set.name);
-                gen_enum_variant_into(&mut acc, &ent_set_name_camel);
-                gen_enum_match_arm_into(&mut enum_fn_variant_name, &cont_name_camel, &ent_set_name_camel, &ent_set.name);
-                gen_fq_enum_variant_into(&mut enum_fn_iterator, &cont_name_camel, &ent_set_name_camel);
-                acc
-            },
+        let fn_iterator = gen_enum_fn_iterator(&cont_name_camel, ent_set_names_camel.iter().map(String::as_str));
+        let fn_variant_name = gen_enum_fn_variant_name(
+            &cont_name_camel,
+            ent_set_names_camel
+                .iter()
+                .map(String::as_str)
+                .zip(self.entity_sets.iter().map(|ent_set| ent_set.name.as_str())),
         );
+        let fn_capabilities = gen_enum_fn_capabilities(&cont_name_camel, self);
+        let fn_variant_names = gen_enum_fn_variant_names(&cont_name_camel);
+
+        quote! {
+            #type_attrs
+            #[allow(dead_code)]
+            pub enum #cont_ident {
+                #(#variants),*
+            }
 
-        entities_enum.extend_from_slice(END_BLOCK);
-        enum_fn_iterator.append(&mut gen_end_iter_fn());
-        enum_fn_variant_name.extend_from_slice(CLOSE_CURLY);
-        enum_fn_variant_name.extend_from_slice(END_BLOCK);
-
-        out.append(&mut entities_enum);
-        out.extend_from_slice(RUSTC_ALLOW_DEAD_CODE);
-        out.append(&mut gen_impl_start_for(&cont_name_camel));
-        out.append(&mut enum_fn_iterator);
-        out.append(&mut enum_fn_variant_name);
-        out.append(&mut gen_enum_fn_variant_names(&cont_name_camel));
-        out.extend_from_slice(END_BLOCK);
+            #[allow(dead_code)]
+            impl #cont_ident {
+                #fn_iterator
+                #fn_variant_name
+                #fn_capabilities
+                #fn_variant_names
+            }
+        }
     }
 
-    pub fn to_enum_with_impl(&self) -> Vec<u8> {
-        let cont_name_camel = to_upper_camel_case(&self.name);
+    /// Generates the module `entity_sets` containing a marker type for each entity set.
+    /// Each marker type implements `parse_sap_odata::capabilities::EntitySet` together with the marker traits for the
+    /// operations and query options that entity set supports, so that `QueryBuilder` and the request functions in
+    /// `parse_sap_odata::capabilities` can refuse unsupported requests at compile time
+    pub fn to_entity_sets_module(&self) -> TokenStream {
+        let marker_types = self.to_entity_sets_items();
+
+        quote! {
+            pub mod entity_sets {
+                #marker_types
+            }
+        }
+    }
 
-        // Output the start of the "iterator" function within the enum implementation
-        //   pub fn iterator() -> impl Iterator<Item = GwsampleBasicEntities> {↩︎
-        //       match *self {↩︎
-        let mut enum_fn_iterator = gen_enum_fn_iter_start(&cont_name_camel);
-
-        // Output the start of the "variant_name" function within the enum implementation
-        let mut enum_fn_variant_name = gen_enum_impl_fn_variant_name();
-
-        // Create entity set enum
-        let mut entities_enum = self.entity_sets.iter().fold(
-            // Initial accumulator value is the start of an enum for this entity container
-            // #[derive(Copy, Clone, Debug)]↩︎
-            // #[allow(dead_code)]↩︎
-            // pub enum <entity_container_name> {↩︎
-            [
-                &*gen_derive_str(&[DeriveTraits::COPY, DeriveTraits::CLONE, DeriveTraits::DEBUG]),
-                RUSTC_ALLOW_DEAD_CODE,
-                &*gen_enum_start(&cont_name_camel),
+    /// Generates the marker types held in the module `entity_sets`
+    pub fn to_entity_sets_items(&self) -> TokenStream {
+        let cont_ident = gen_ident(&to_upper_camel_case(&self.name));
+
+        let marker_types = self.entity_sets.iter().map(|ent_set| {
+            let ent_set_ident = gen_ident(&to_upper_camel_case(&ent_set.name));
+            let ent_set_name = &ent_set.name;
+            let caps = EntitySetCapabilities::from(&ent_set.sap_annotations);
+
+            let marker_traits = [
+                (caps.is_creatable, "Creatable"),
+                (caps.is_updatable, "Updatable"),
+                (caps.is_deletable, "Deletable"),
+                (caps.is_searchable, "Searchable"),
+                (caps.is_pageable, "Pageable"),
+                (caps.is_topable, "Topable"),
+                (caps.is_countable, "Countable"),
+                (!caps.requires_filter, "FilterOptional"),
             ]
-            .concat(),
-            |mut acc, ent_set| {
-                let ent_set_name_camel = to_upper_camel_case(&ent_set.name);
-
-                // Add variant to enum, iterator, and variant_name functions
-                gen_enum_variant_into(&mut acc, &ent_set_name_camel);
-                gen_enum_match_arm_into(
-                    &mut enum_fn_variant_name,
-                    &cont_name_camel,
-                    &ent_set_name_camel,
-                    &ent_set.name,
-                );
-                gen_fq_enum_variant_into(&mut enum_fn_iterator, &cont_name_camel, &ent_set_name_camel);
-
-                acc
-            },
-        );
+            .into_iter()
+            .filter(|(supported, _)| *supported)
+            .map(|(_, marker_trait)| {
+                let trait_ident = gen_ident(marker_trait);
+                quote!(impl parse_sap_odata::capabilities::#trait_ident for #ent_set_ident {})
+            });
+
+            quote! {
+                pub struct #ent_set_ident;
+                impl parse_sap_odata::capabilities::EntitySet for #ent_set_ident {
+                    const NAME: &'static str = #ent_set_name;
+                    const CAPABILITIES: parse_sap_odata::capabilities::EntitySetCapabilities =
+                        super::#cont_ident::#ent_set_ident.capabilities();
+                }
+                #(#marker_traits)*
+            }
+        });
 
-        // End enum and function blocks
-        entities_enum.extend_from_slice(END_BLOCK);
-        enum_fn_iterator.append(&mut gen_end_iter_fn());
-        enum_fn_variant_name.extend_from_slice(CLOSE_CURLY);
-        enum_fn_variant_name.extend_from_slice(END_BLOCK);
-
-        [
-            // EntityContainer enum
-            &*entities_enum,
-            // Output the start of an enum implementation
-            // #[allow(dead_code)]↩︎
-            // impl <entity_container_name> {↩︎
-            RUSTC_ALLOW_DEAD_CODE,
-            &*gen_impl_start_for(&cont_name_camel),
-            &*enum_fn_iterator,
-            &*enum_fn_variant_name,
-            &*gen_enum_fn_variant_names(&cont_name_camel),
-            END_BLOCK,
-        ]
-        .concat()
+        quote!(#(#marker_types)*)
     }
 }
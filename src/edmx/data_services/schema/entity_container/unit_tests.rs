@@ -7,6 +7,7 @@ use std::{
 
 use super::{AssociationSet, EntityContainer};
 use crate::test_utils::*;
+use quote::quote;
 
 impl FromStr for EntityContainer {
     type Err = quick_xml::DeError;
@@ -146,3 +147,35 @@ pub fn should_parse_association_set() -> Result<(), String> {
         Err(err) => Err(format!("XML test data was not in UTF8 format: {}", err)),
     }
 }
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_generate_entity_set_capabilities() -> Result<(), String> {
+    let mut xml_buffer: Vec<u8> = Vec::new();
+    let test_data = File::open(Path::new("./test_data/entity_container.xml")).unwrap();
+    let _file_size = BufReader::new(test_data).read_to_end(&mut xml_buffer);
+
+    match String::from_utf8(xml_buffer) {
+        Ok(xml) => {
+            let ent_cont = EntityContainer::from_str(&xml).unwrap();
+            let enum_src = ent_cont.to_enum_with_impl();
+            let entity_sets_src = ent_cont.to_entity_sets_module();
+            let src = tokens_to_rust_src(quote!(#enum_src #entity_sets_src)).join("\n");
+
+            handle_test_bool(src.contains(
+                "pub const fn capabilities(\n&self,\n) -> parse_sap_odata::capabilities::EntitySetCapabilities {",
+            ))?;
+            handle_test_bool(src.contains(
+                "GwsampleBasicEntities::VhCategorySet => {\nparse_sap_odata::capabilities::EntitySetCapabilities {",
+            ))?;
+            handle_test_bool(src.contains("pub struct VhCategorySet;"))?;
+            handle_test_bool(src.contains("const NAME: &'static str = \"VH_CategorySet\";"))?;
+            handle_test_bool(src.contains("impl parse_sap_odata::capabilities::Deletable for BusinessPartnerSet {}"))?;
+            handle_test_bool(!src.contains("impl parse_sap_odata::capabilities::Deletable for VhCategorySet {}"))?;
+            handle_test_bool(!src.contains("impl parse_sap_odata::capabilities::Pageable for VhCategorySet {}"))?;
+            handle_test_bool(src.contains("impl parse_sap_odata::capabilities::Topable for VhCategorySet {}"))?;
+            handle_test_bool(src.contains("impl parse_sap_odata::capabilities::FilterOptional for VhCategorySet {}"))
+        },
+        Err(err) => Err(format!("XML test data was not in UTF8 format: {}", err)),
+    }
+}
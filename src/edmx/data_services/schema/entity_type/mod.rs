@@ -45,6 +45,18 @@ pub struct EntityType {
     pub navigations: Vec<NavigationProperty>,
 }
 
+impl EntityType {
+    /// Is this entity type annotated with `sap:semantics="aggregate"`?
+    pub fn is_aggregate(&self) -> bool {
+        matches!(self.sap_semantics, Some(EntityTypeSAPSemantics::Aggregate))
+    }
+
+    /// Is this entity type annotated with `sap:semantics="parameters"`?
+    pub fn is_parameters(&self) -> bool {
+        matches!(self.sap_semantics, Some(EntityTypeSAPSemantics::Paramaters))
+    }
+}
+
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 #[cfg(test)]
 pub mod unit_tests;
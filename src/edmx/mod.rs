@@ -1,10 +1,14 @@
 pub mod data_services;
 pub mod reference;
+pub mod skip_annotations;
+
+use std::io::BufRead;
 
 use serde::{Deserialize, Serialize};
 
 use data_services::DataServices;
 use reference::Reference;
+use skip_annotations::SkipAnnotations;
 
 use crate::xml::{default_xml_namespace_edmx, default_xml_namespace_m, default_xml_namespace_sap};
 
@@ -43,6 +47,21 @@ pub struct Edmx {
     pub data_services: DataServices,
 }
 
+impl Edmx {
+    /// Deserialize a metadata document from any buffered reader, without first reading the whole document into memory
+    pub fn from_reader<R: BufRead>(reader: R) -> Result<Self, quick_xml::DeError> {
+        quick_xml::de::from_reader(reader)
+    }
+
+    /// Deserialize a metadata document from any buffered reader, skipping all `<Annotations>` elements.
+    ///
+    /// In the returned `Edmx`, the `annotation_list` of every schema is `None`.  For metadata documents with large
+    /// annotation sections, this saves both time and memory
+    pub fn from_reader_skipping_annotations<R: BufRead>(reader: R) -> Result<Self, quick_xml::DeError> {
+        Self::from_reader(SkipAnnotations::new(reader))
+    }
+}
+
 impl std::str::FromStr for Edmx {
     type Err = quick_xml::DeError;
 
@@ -50,3 +69,7 @@ impl std::str::FromStr for Edmx {
         quick_xml::de::from_str(s)
     }
 }
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(test)]
+pub mod unit_tests;
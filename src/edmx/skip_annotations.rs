@@ -0,0 +1,94 @@
+use std::io::{self, BufRead, Read};
+
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader, Writer,
+};
+
+static ANNOTATIONS_ELEMENT: &[u8] = b"Annotations";
+
+// Once this much XML has been buffered, it is handed to the consumer
+const FILL_SIZE: usize = 8 * 1024;
+
+fn is_annotations(elem: &BytesStart) -> bool {
+    elem.local_name().as_ref().eq(ANNOTATIONS_ELEMENT)
+}
+
+fn to_io_error(err: quick_xml::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// A buffered reader that passes through the XML read from `reader`, except for `<Annotations>` elements and
+/// everything they contain.
+///
+/// The XML is filtered as it is read, so at most a few kilobytes of the document are held in memory at any time
+pub struct SkipAnnotations<R: BufRead> {
+    reader: Reader<R>,
+    event_buf: Vec<u8>,
+    out: Vec<u8>,
+    pos: usize,
+    // How deeply nested the current event is within a skipped `<Annotations>` element
+    depth: usize,
+    done: bool,
+}
+
+impl<R: BufRead> SkipAnnotations<R> {
+    pub fn new(reader: R) -> SkipAnnotations<R> {
+        SkipAnnotations {
+            reader: Reader::from_reader(reader),
+            event_buf: Vec::new(),
+            out: Vec::new(),
+            pos: 0,
+            depth: 0,
+            done: false,
+        }
+    }
+
+    fn fill_out(&mut self) -> io::Result<()> {
+        self.out.clear();
+        self.pos = 0;
+
+        while !self.done && self.out.len() < FILL_SIZE {
+            self.event_buf.clear();
+
+            match self.reader.read_event_into(&mut self.event_buf).map_err(to_io_error)? {
+                Event::Eof => self.done = true,
+                Event::Start(_) if self.depth > 0 => self.depth += 1,
+                Event::End(_) if self.depth > 0 => self.depth -= 1,
+                _ if self.depth > 0 => {},
+                Event::Start(elem) if is_annotations(&elem) => self.depth = 1,
+                Event::Empty(elem) if is_annotations(&elem) => {},
+                event => Writer::new(&mut self.out).write_event(event)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<R: BufRead> Read for SkipAnnotations<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let available = self.fill_buf()?;
+        let len = available.len().min(buf.len());
+
+        buf[..len].copy_from_slice(&available[..len]);
+        self.consume(len);
+
+        Ok(len)
+    }
+}
+
+impl<R: BufRead> BufRead for SkipAnnotations<R> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        if self.pos >= self.out.len() {
+            self.fill_out()?;
+        }
+
+        Ok(&self.out[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos = (self.pos + amt).min(self.out.len());
+    }
+}
@@ -0,0 +1,63 @@
+use std::{
+    fs::{read_to_string, File},
+    io::BufReader,
+    str::FromStr,
+};
+
+use super::*;
+use crate::test_utils::*;
+
+static PATH_TO_MOCK_SERVICE: &str = "./test_data/mock_service.xml";
+
+static XML_ANNOTATIONS: &str = r#"<Annotations Target="GWSAMPLE_BASIC.Product/Name" xmlns="http://docs.oasis-open.org/odata/ns/edm">
+    <Annotation Term="com.sap.vocabularies.Common.v1.Label" String="Product Name"/>
+    <Annotation Term="com.sap.vocabularies.UI.v1.DataPoint">
+      <Record><PropertyValue Property="Value" PropertyPath="Name"/></Record>
+    </Annotation>
+  </Annotations>
+  <Annotations Target="GWSAMPLE_BASIC.Product" xmlns="http://docs.oasis-open.org/odata/ns/edm"/>
+"#;
+
+fn mock_service_with_annotations() -> Result<String, String> {
+    let xml = read_to_string(PATH_TO_MOCK_SERVICE).map_err(|err| err.to_string())?;
+
+    Ok(xml.replacen("</Schema>", &format!("{XML_ANNOTATIONS}</Schema>"), 1))
+}
+
+fn entity_type_names(edmx: &Edmx) -> String {
+    edmx.data_services.schemas[0]
+        .entity_types
+        .iter()
+        .map(|et| et.name.as_str())
+        .collect::<Vec<&str>>()
+        .join(",")
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_read_edmx_from_reader() -> Result<(), String> {
+    let file = File::open(PATH_TO_MOCK_SERVICE).map_err(|err| err.to_string())?;
+    let from_reader = Edmx::from_reader(BufReader::new(file)).map_err(|err| err.to_string())?;
+    let xml = read_to_string(PATH_TO_MOCK_SERVICE).map_err(|err| err.to_string())?;
+    let from_str = Edmx::from_str(&xml).map_err(|err| err.to_string())?;
+
+    handle_test_comparison(&entity_type_names(&from_reader), &entity_type_names(&from_str))
+}
+
+#[test]
+pub fn should_skip_annotations() -> Result<(), String> {
+    let xml = mock_service_with_annotations()?;
+    let edmx = Edmx::from_reader(xml.as_bytes()).map_err(|err| err.to_string())?;
+    let skipped = Edmx::from_reader_skipping_annotations(xml.as_bytes()).map_err(|err| err.to_string())?;
+
+    handle_test_comparison(&edmx.data_services.schemas[0].annotation_list.as_ref().map_or(0, Vec::len), &2)?;
+    handle_test_bool(skipped.data_services.schemas[0].annotation_list.is_none())?;
+    handle_test_comparison(&entity_type_names(&skipped), &entity_type_names(&edmx))
+}
+
+#[test]
+pub fn should_report_malformed_xml_when_skipping_annotations() -> Result<(), String> {
+    let xml = mock_service_with_annotations()?.replacen("</EntityType>", "</ComplexType>", 1);
+
+    handle_test_bool(Edmx::from_reader_skipping_annotations(xml.as_bytes()).is_err())
+}
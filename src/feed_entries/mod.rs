@@ -0,0 +1,166 @@
+use std::{io::BufRead, marker::PhantomData};
+
+use parse_sap_atom_feed::atom::feed::entry::Entry;
+use quick_xml::{
+    events::{BytesStart, Event},
+    DeError, Reader, Writer,
+};
+use serde::{de::DeserializeOwned, Deserialize};
+
+static ENTRY_ELEMENT: &[u8] = b"entry";
+
+fn is_entry(elem: &BytesStart) -> bool {
+    elem.local_name().as_ref().eq(ENTRY_ELEMENT)
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Iterate over the `<entry>` elements of an Atom feed read from `reader`, deserializing them one at a time.
+///
+/// Unlike deserializing a `Feed<T>`, only one entry is held in memory at any time, so feeds of any size can be
+/// processed.  The feed's own elements (`<id>`, `<title>`, `<link>` etc.) are skipped.
+///
+/// ```rust,ignore
+/// let file = BufReader::new(File::open("products.xml")?);
+///
+/// for entry in FeedEntries::<_, Product>::new(file) {
+///     println!("{:?}", entry?.content.properties);
+/// }
+/// ```
+pub struct FeedEntries<R: BufRead, T> {
+    reader: Reader<R>,
+    buf: Vec<u8>,
+    done: bool,
+    entry_type: PhantomData<T>,
+}
+
+impl<R: BufRead, T: DeserializeOwned> FeedEntries<R, T> {
+    pub fn new(reader: R) -> FeedEntries<R, T> {
+        FeedEntries {
+            reader: Reader::from_reader(reader),
+            buf: Vec::new(),
+            done: false,
+            entry_type: PhantomData,
+        }
+    }
+
+    // Copy the events of the entry that starts with `start` into a buffer, then deserialize that buffer
+    fn read_entry(&mut self, start: BytesStart<'static>) -> Result<Entry<T>, DeError> {
+        let mut writer = Writer::new(Vec::new());
+        let mut depth: usize = 0;
+        let mut event = Event::Start(start);
+
+        loop {
+            match &event {
+                Event::Start(_) => depth += 1,
+                Event::End(_) => depth -= 1,
+                Event::Eof => return Err(DeError::UnexpectedEof),
+                _ => {},
+            }
+
+            writer.write_event(event).map_err(quick_xml::Error::from)?;
+
+            if depth == 0 {
+                break;
+            }
+
+            self.buf.clear();
+            event = self.reader.read_event_into(&mut self.buf)?;
+        }
+
+        quick_xml::de::from_reader(writer.into_inner().as_slice())
+    }
+}
+
+impl<R: BufRead, T: DeserializeOwned> Iterator for FeedEntries<R, T> {
+    type Item = Result<Entry<T>, DeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            self.buf.clear();
+
+            match self.reader.read_event_into(&mut self.buf) {
+                Ok(Event::Start(elem)) if is_entry(&elem) => {
+                    let start = elem.into_owned();
+                    let entry = self.read_entry(start);
+                    self.done = entry.is_err();
+                    return Some(entry);
+                },
+                Ok(Event::Eof) => self.done = true,
+                Ok(_) => {},
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err.into()));
+                },
+            }
+        }
+
+        None
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Iterate over the `<entry>` elements of an Atom feed held in memory, returning the XML of each entry as a slice of
+/// the feed
+pub struct FeedEntrySlices<'a> {
+    xml: &'a str,
+    reader: Reader<&'a [u8]>,
+    done: bool,
+}
+
+impl<'a> FeedEntrySlices<'a> {
+    pub fn new(xml: &'a str) -> FeedEntrySlices<'a> {
+        FeedEntrySlices {
+            xml,
+            reader: Reader::from_str(xml),
+            done: false,
+        }
+    }
+}
+
+impl<'a> Iterator for FeedEntrySlices<'a> {
+    type Item = Result<&'a str, DeError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            // Text events include any whitespace, so the position before an event is the start of its tag
+            let entry_start = self.reader.buffer_position() as usize;
+
+            match self.reader.read_event() {
+                Ok(Event::Start(elem)) if is_entry(&elem) => match self.reader.read_to_end(elem.name()) {
+                    Ok(_) => {
+                        let entry_end = self.reader.buffer_position() as usize;
+                        return Some(Ok(&self.xml[entry_start..entry_end]));
+                    },
+                    Err(err) => {
+                        self.done = true;
+                        return Some(Err(err.into()));
+                    },
+                },
+                Ok(Event::Eof) => self.done = true,
+                Ok(_) => {},
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(err.into()));
+                },
+            }
+        }
+
+        None
+    }
+}
+
+/// Deserialize the `<entry>` elements of an Atom feed held in memory one at a time.
+///
+/// Since each entry is deserialized directly from `xml`, `T` can be a borrowed struct (generated using the
+/// `gen_borrowed_structs` option) whose string fields borrow from `xml` rather than being copied
+pub fn deserialize_feed_entries<'a, T>(xml: &'a str) -> impl Iterator<Item = Result<Entry<T>, DeError>> + 'a
+where
+    T: Deserialize<'a> + 'a,
+{
+    FeedEntrySlices::new(xml).map(|entry_xml| entry_xml.and_then(quick_xml::de::from_str))
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(feature = "parser")]
+#[cfg(test)]
+pub mod unit_tests;
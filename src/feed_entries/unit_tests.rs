@@ -0,0 +1,106 @@
+use std::{
+    borrow::Cow,
+    fs::{read_to_string, File},
+    io::BufReader,
+};
+
+use serde::Deserialize;
+
+use super::*;
+use crate::test_utils::*;
+
+static PATH_TO_MOCK_FEED_PRODUCTS: &str = "./test_data/mock_feed_products.xml";
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Product {
+    #[serde(rename = "ProductID")]
+    product_id: String,
+}
+
+// The source generated for the mock service's Product entity type with `gen_borrowed_structs` switched on.
+// (The srvc_doc unit tests check that this file still matches the generator's output)
+pub(crate) mod generated {
+    use crate as parse_sap_odata;
+    use serde::{Deserialize, Serialize};
+
+    include!("../../test_data/generated_product.rs");
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_read_entries_one_at_a_time() -> Result<(), String> {
+    let file = File::open(PATH_TO_MOCK_FEED_PRODUCTS).map_err(|err| err.to_string())?;
+    let entries = FeedEntries::<_, Product>::new(BufReader::new(file))
+        .collect::<Result<Vec<Entry<Product>>, DeError>>()
+        .map_err(|err| err.to_string())?;
+    let product_ids: Vec<String> = entries
+        .into_iter()
+        .filter_map(|entry| entry.content.properties)
+        .map(|product| product.product_id)
+        .collect();
+
+    handle_test_comparison(&product_ids.join(","), &"HT-1000,HT-1001,HT-1002".to_owned())
+}
+
+#[test]
+pub fn should_slice_entries_from_feed() -> Result<(), String> {
+    let xml = read_to_string(PATH_TO_MOCK_FEED_PRODUCTS).map_err(|err| err.to_string())?;
+    let slices = FeedEntrySlices::new(&xml)
+        .collect::<Result<Vec<&str>, DeError>>()
+        .map_err(|err| err.to_string())?;
+
+    handle_test_comparison(&slices.len(), &3)?;
+    handle_test_bool(
+        slices
+            .iter()
+            .all(|slice| slice.starts_with("<entry>") && slice.ends_with("</entry>")),
+    )
+}
+
+#[test]
+pub fn should_borrow_entry_values_from_feed() -> Result<(), String> {
+    let xml = read_to_string(PATH_TO_MOCK_FEED_PRODUCTS).map_err(|err| err.to_string())?;
+    let first_entry = deserialize_feed_entries::<generated::ProductBorrowed>(&xml)
+        .next()
+        .ok_or("Feed contains no entries")?
+        .map_err(|err| err.to_string())?;
+    let product = first_entry.content.properties.ok_or("Entry contains no properties")?;
+
+    handle_test_bool(matches!(product.product_id, Cow::Borrowed("HT-1000")))?;
+    handle_test_bool(matches!(product.name, Cow::Borrowed("Notebook Basic 15")))?;
+    handle_test_bool(matches!(product.supplier_id, Cow::Borrowed("0100000000")))?;
+    handle_test_bool(product.currency_code.is_none())?;
+    handle_test_comparison_opt(&product.price, &Some(rust_decimal::Decimal::new(956_000, 3)))?;
+
+    let owned = product.into_owned();
+    handle_test_comparison(&owned.product_id, &"HT-1000".to_owned())?;
+    handle_test_comparison(&owned.name, &"Notebook Basic 15".to_owned())
+}
+
+#[test]
+pub fn should_report_truncated_feed() -> Result<(), String> {
+    let xml = read_to_string(PATH_TO_MOCK_FEED_PRODUCTS).map_err(|err| err.to_string())?;
+    let truncated = &xml[..xml.find("</entry>").ok_or("Feed contains no entries")?];
+    let mut entries = FeedEntries::<_, Product>::new(truncated.as_bytes());
+
+    handle_test_bool(matches!(entries.next(), Some(Err(_))))?;
+    handle_test_bool(entries.next().is_none())
+}
+
+#[test]
+pub fn should_own_escaped_values_of_generated_borrowed_struct() -> Result<(), String> {
+    let xml = r#"<m:properties>
+        <d:ProductID>HT-1003</d:ProductID>
+        <d:Name>Notebook &amp; Case</d:Name>
+        <d:SupplierID>0100000001</d:SupplierID>
+        <d:CurrencyCode>EUR</d:CurrencyCode>
+        <d:Price m:null="true"/>
+        <d:ChangedAt>2024-01-01T10:00:00.0000000</d:ChangedAt>
+    </m:properties>"#;
+    let product = generated::ProductBorrowed::from_xml(xml).map_err(|err| err.to_string())?;
+
+    handle_test_bool(matches!(product.product_id, Cow::Borrowed("HT-1003")))?;
+    handle_test_bool(matches!(&product.name, Cow::Owned(name) if name.eq("Notebook & Case")))?;
+    handle_test_bool(matches!(product.currency_code, Some(Cow::Borrowed("EUR"))))
+}
@@ -0,0 +1,38 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use crate::{
+    hierarchy::HierarchyInfo,
+    parser::generate::{gen_opt_string, gen_owned_string},
+};
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Metadata Module Generation
+/// Generate the source code that declares an instance of this HierarchyInfo
+impl ToTokens for HierarchyInfo {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let node_property = gen_owned_string(&self.node_property);
+        let node_for = gen_owned_string(&self.node_for);
+        let external_key_property = gen_opt_string(&self.external_key_property);
+        let parent_node_property = gen_opt_string(&self.parent_node_property);
+        let level_property = gen_opt_string(&self.level_property);
+        let drill_state_property = gen_opt_string(&self.drill_state_property);
+        let descendant_count_property = gen_opt_string(&self.descendant_count_property);
+        let preorder_rank_property = gen_opt_string(&self.preorder_rank_property);
+        let sibling_rank_property = gen_opt_string(&self.sibling_rank_property);
+
+        tokens.extend(quote! {
+            HierarchyInfo {
+                node_property: #node_property,
+                node_for: #node_for,
+                external_key_property: #external_key_property,
+                parent_node_property: #parent_node_property,
+                level_property: #level_property,
+                drill_state_property: #drill_state_property,
+                descendant_count_property: #descendant_count_property,
+                preorder_rank_property: #preorder_rank_property,
+                sibling_rank_property: #sibling_rank_property,
+            }
+        })
+    }
+}
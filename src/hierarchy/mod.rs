@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use crate::property::Property;
+
+#[cfg(feature = "parser")]
+pub mod metadata;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Describes which properties of an entity type play which role in a hierarchy
+///
+/// A hierarchy is identified by the property annotated with `sap:hierarchy-node-for`.
+/// The other roles are played by properties whose `sap:hierarchy-*-for` annotation names this node property.
+///
+/// All property names are the OData names as they appear in the metadata document
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HierarchyInfo {
+    /// The property holding the ID of the hierarchy node
+    pub node_property: String,
+    /// The property for which this hierarchy is defined
+    pub node_for: String,
+    pub external_key_property: Option<String>,
+    pub parent_node_property: Option<String>,
+    pub level_property: Option<String>,
+    pub drill_state_property: Option<String>,
+    pub descendant_count_property: Option<String>,
+    pub preorder_rank_property: Option<String>,
+    pub sibling_rank_property: Option<String>,
+}
+
+impl HierarchyInfo {
+    /// Find all the hierarchies defined in a list of properties
+    ///
+    /// Hierarchies are returned in the alphabetical order of their node property names
+    pub fn find_in(props: &[Property]) -> Vec<HierarchyInfo> {
+        // Find the name of the property whose annotation refers to the node property
+        let find_role = |node_name: &str, role: fn(&Property) -> &Option<String>| {
+            props
+                .iter()
+                .find(|prop| role(prop).as_deref() == Some(node_name))
+                .map(|prop| prop.odata_name.clone())
+        };
+
+        let mut hierarchies: Vec<HierarchyInfo> = props
+            .iter()
+            .filter_map(|node| {
+                node.sap_annotations.hierarchy_node_for.as_ref().map(|node_for| {
+                    let node_name = node.odata_name.as_str();
+
+                    HierarchyInfo {
+                        node_property: node.odata_name.clone(),
+                        node_for: node_for.clone(),
+                        external_key_property: find_role(node_name, |p| {
+                            &p.sap_annotations.hierarchy_node_external_key_for
+                        }),
+                        parent_node_property: find_role(node_name, |p| &p.sap_annotations.hierarchy_parent_node_for),
+                        level_property: find_role(node_name, |p| &p.sap_annotations.hierarchy_level_for),
+                        drill_state_property: find_role(node_name, |p| &p.sap_annotations.hierarchy_drill_state_for),
+                        descendant_count_property: find_role(node_name, |p| {
+                            &p.sap_annotations.hierarchy_node_descendant_count_for
+                        }),
+                        preorder_rank_property: find_role(node_name, |p| {
+                            &p.sap_annotations.hierarchy_preorder_rank_for
+                        }),
+                        sibling_rank_property: find_role(node_name, |p| &p.sap_annotations.hierarchy_sibling_rank_for),
+                    }
+                })
+            })
+            .collect();
+
+        hierarchies.sort_by(|a, b| a.node_property.cmp(&b.node_property));
+        hierarchies
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Implemented by the generated structs of entity types that define a hierarchy.
+///
+/// If an entity type defines more than one hierarchy, this trait is implemented for the first one returned by
+/// `HierarchyInfo::find_in` that has a parent node property.  Hierarchies without a parent node property cannot be
+/// assembled into a tree, so an entity type none of whose hierarchies has one does not implement this trait
+pub trait HierarchyNode {
+    /// The ID of this node
+    fn node_id(&self) -> String;
+    /// The ID of the parent node. Root nodes have no parent ID, or an empty parent ID
+    fn parent_node_id(&self) -> Option<String>;
+    /// Determines the order of sibling nodes, taken from either the preorder rank or the sibling rank
+    fn rank(&self) -> Option<i64> {
+        None
+    }
+}
+
+/// A node in a tree assembled from a flat list of entities
+#[derive(Clone, Debug, PartialEq)]
+pub struct TreeNode<T> {
+    pub item: T,
+    pub children: Vec<TreeNode<T>>,
+}
+
+impl<T> TreeNode<T> {
+    /// The total number of nodes below this node
+    pub fn descendant_count(&self) -> usize {
+        self.children.iter().map(|child| 1 + child.descendant_count()).sum()
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Assemble a flat list of deserialized entities into a tree using the fields described by the entity's
+/// `HierarchyInfo`
+///
+/// Siblings are ordered by their rank, or if no rank is available, by their position in the list
+pub fn build_tree<T: HierarchyNode>(items: Vec<T>) -> Vec<TreeNode<T>> {
+    let mut ranked: Vec<(Option<i64>, T)> = items.into_iter().map(|item| (item.rank(), item)).collect();
+
+    // A stable sort keeps unranked items in their original order
+    ranked.sort_by_key(|(rank, _)| rank.unwrap_or(i64::MAX));
+
+    build_tree_by(
+        ranked.into_iter().map(|(_, item)| item).collect(),
+        |item| item.node_id(),
+        |item| item.parent_node_id(),
+    )
+}
+
+/// Assemble a flat list of items into a tree using functions that return each item's node ID and parent node ID
+///
+/// * Items whose parent is missing from the list become root nodes. This allows partially expanded hierarchies to be
+///   assembled
+/// * Items that form a cycle are also treated as root nodes
+/// * Siblings remain in the order in which they appear in the list
+pub fn build_tree_by<T, F, P>(items: Vec<T>, node_id: F, parent_node_id: P) -> Vec<TreeNode<T>>
+where
+    F: Fn(&T) -> String,
+    P: Fn(&T) -> Option<String>,
+{
+    let ids: Vec<String> = items.iter().map(&node_id).collect();
+    let parent_ids: Vec<Option<String>> = items
+        .iter()
+        .map(|item| parent_node_id(item).filter(|parent_id| !parent_id.is_empty()))
+        .collect();
+    let idx_of: HashMap<&str, usize> = ids.iter().enumerate().map(|(idx, id)| (id.as_str(), idx)).collect();
+
+    let mut child_idxs: Vec<Vec<usize>> = vec![vec![]; items.len()];
+    let mut root_idxs: Vec<usize> = vec![];
+
+    for (idx, parent_id) in parent_ids.iter().enumerate() {
+        match parent_id.as_deref().and_then(|parent_id| idx_of.get(parent_id)) {
+            Some(parent_idx) if *parent_idx != idx => child_idxs[*parent_idx].push(idx),
+            _ => root_idxs.push(idx),
+        }
+    }
+
+    // Items that are not reachable from a root node form a cycle, so the first of each cycle becomes a root node
+    let mut reachable = vec![false; items.len()];
+    let mut pending = root_idxs.clone();
+
+    while let Some(idx) = pending.pop() {
+        reachable[idx] = true;
+        pending.extend(&child_idxs[idx]);
+    }
+
+    for idx in 0..items.len() {
+        if !reachable[idx] {
+            root_idxs.push(idx);
+            pending.push(idx);
+
+            while let Some(cycle_idx) = pending.pop() {
+                if !reachable[cycle_idx] {
+                    reachable[cycle_idx] = true;
+                    pending.extend(&child_idxs[cycle_idx]);
+                }
+            }
+
+            // The former parent of the new root must no longer list it as a child
+            for children in child_idxs.iter_mut() {
+                children.retain(|child_idx| *child_idx != idx);
+            }
+        }
+    }
+
+    let mut slots: Vec<Option<T>> = items.into_iter().map(Some).collect();
+    root_idxs.sort();
+    root_idxs
+        .into_iter()
+        .map(|idx| assemble_node(idx, &mut slots, &child_idxs))
+        .collect()
+}
+
+fn assemble_node<T>(idx: usize, slots: &mut Vec<Option<T>>, child_idxs: &[Vec<usize>]) -> TreeNode<T> {
+    TreeNode {
+        item: slots[idx].take().unwrap(),
+        children: child_idxs[idx]
+            .iter()
+            .map(|child_idx| assemble_node(*child_idx, slots, child_idxs))
+            .collect(),
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(feature = "parser")]
+#[cfg(test)]
+pub mod unit_tests;
@@ -0,0 +1,121 @@
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+    str::FromStr,
+};
+
+use super::*;
+use crate::{edmx::data_services::schema::entity_type::EntityType, test_utils::*};
+use quote::quote;
+
+static PATH_TO_ENTITY_TYPE_COST_CENTER_HIERARCHY: &str = "./test_data/entity_type_cost_center_hierarchy.xml";
+
+pub fn fetch_entity_type_cost_center_hierarchy() -> Result<EntityType, String> {
+    let mut xml_buffer: Vec<u8> = Vec::new();
+    let test_data = File::open(Path::new(PATH_TO_ENTITY_TYPE_COST_CENTER_HIERARCHY)).unwrap();
+    let _file_size = BufReader::new(test_data).read_to_end(&mut xml_buffer);
+
+    match String::from_utf8(xml_buffer) {
+        Ok(xml) => EntityType::from_str(&xml).map_err(|err| err.to_string()),
+        Err(err) => Err(format!("XML test data was not in UTF8 format: {err}")),
+    }
+}
+
+// Hand-written equivalent of a deserialized hierarchy node entity
+#[derive(Debug, PartialEq)]
+struct TestNode {
+    id: &'static str,
+    parent: Option<&'static str>,
+    rank: Option<i64>,
+}
+
+impl HierarchyNode for TestNode {
+    fn node_id(&self) -> String {
+        self.id.to_string()
+    }
+    fn parent_node_id(&self) -> Option<String> {
+        self.parent.map(String::from)
+    }
+    fn rank(&self) -> Option<i64> {
+        self.rank
+    }
+}
+
+fn node(id: &'static str, parent: Option<&'static str>, rank: Option<i64>) -> TestNode {
+    TestNode { id, parent, rank }
+}
+
+fn ids_of(nodes: &[TreeNode<TestNode>]) -> String {
+    nodes.iter().map(|tn| tn.item.id).collect::<Vec<_>>().join(",")
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_find_hierarchy_info() -> Result<(), String> {
+    let cost_centers = fetch_entity_type_cost_center_hierarchy()?;
+    let hierarchies = HierarchyInfo::find_in(&cost_centers.properties);
+
+    handle_test_comparison(&hierarchies.len(), &1)?;
+
+    let hi = &hierarchies[0];
+    handle_test_comparison(&hi.node_property, &"HierarchyNode".to_string())?;
+    handle_test_comparison(&hi.node_for, &"CostCenter".to_string())?;
+    handle_test_comparison_opt(&hi.external_key_property, &Some("CostCenterExternalKey".to_string()))?;
+    handle_test_comparison_opt(&hi.parent_node_property, &Some("ParentNode".to_string()))?;
+    handle_test_comparison_opt(&hi.level_property, &Some("HierarchyLevel".to_string()))?;
+    handle_test_comparison_opt(&hi.drill_state_property, &Some("DrillState".to_string()))?;
+    handle_test_comparison_opt(&hi.descendant_count_property, &Some("DescendantCount".to_string()))?;
+    handle_test_comparison_opt(&hi.preorder_rank_property, &Some("PreorderRank".to_string()))?;
+    handle_test_comparison_opt(&hi.sibling_rank_property, &None)
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_build_tree_in_rank_order() -> Result<(), String> {
+    let tree = build_tree(vec![
+        node("B1", Some("B"), Some(4)),
+        node("A", Some(""), Some(1)),
+        node("B", None, Some(3)),
+        node("A1", Some("A"), Some(2)),
+    ]);
+
+    handle_test_comparison(&ids_of(&tree), &"A,B".to_string())?;
+    handle_test_comparison(&ids_of(&tree[0].children), &"A1".to_string())?;
+    handle_test_comparison(&ids_of(&tree[1].children), &"B1".to_string())?;
+    handle_test_comparison(&tree[0].descendant_count(), &1)
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_treat_orphans_and_cycles_as_roots() -> Result<(), String> {
+    let tree = build_tree(vec![
+        node("X", Some("Y"), None),
+        node("Y", Some("X"), None),
+        node("Z", Some("NOT_LOADED"), None),
+        node("Z1", Some("Z"), None),
+    ]);
+
+    handle_test_comparison(&ids_of(&tree), &"X,Z".to_string())?;
+    handle_test_comparison(&ids_of(&tree[0].children), &"Y".to_string())?;
+    handle_test_comparison(&tree[0].children[0].children.len(), &0)?;
+    handle_test_comparison(&ids_of(&tree[1].children), &"Z1".to_string())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_generate_hierarchy_info_instance() -> Result<(), String> {
+    let cost_centers = fetch_entity_type_cost_center_hierarchy()?;
+    let hierarchies = HierarchyInfo::find_in(&cost_centers.properties);
+    let hierarchy = &hierarchies[0];
+    let src_lines = tokens_to_rust_src(quote!(static HIERARCHY: HierarchyInfo = #hierarchy;));
+
+    handle_test_comparison(&src_lines[0], &"static HIERARCHY: HierarchyInfo = HierarchyInfo {".to_string())?;
+    handle_test_comparison(&src_lines[1], &"node_property: \"HierarchyNode\".to_owned(),".to_string())?;
+    handle_test_comparison(&src_lines[2], &"node_for: \"CostCenter\".to_owned(),".to_string())?;
+    handle_test_comparison(
+        &src_lines[4],
+        &"parent_node_property: Some(\"ParentNode\".to_owned()),".to_string(),
+    )?;
+    handle_test_comparison(&src_lines[9], &"sibling_rank_property: None,".to_string())
+}
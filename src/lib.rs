@@ -1,9 +1,22 @@
+pub mod amounts;
+pub mod analytics;
+pub mod capabilities;
+pub mod deserializers;
 pub mod edmx;
+pub mod feed_entries;
+pub mod hierarchy;
 pub mod macros;
+#[cfg(feature = "mock-server")]
+pub mod mock_server;
 pub mod oasis;
 pub mod property;
+pub mod query;
+pub mod round_trip;
+pub mod sanitise;
 pub mod sap_annotations;
 pub mod sap_semantics;
+pub mod sql;
+pub mod texts;
 pub mod utils;
 pub mod xml;
 
@@ -18,3 +18,24 @@ macro_rules! include_mod {
         include!(concat!(env!("OUT_DIR"), concat!("/", $mod_name, ".rs")));
     };
 }
+
+/// Include the tree of module files generated by the build script when the `split_modules` option is switched on.
+///
+/// You must specify the name of the metadata file.  Both the service document module and the metadata module are
+/// brought into scope:
+///
+/// ```rust,ignore
+/// parse_sap_odata::include_mod_tree!("gwsample_basic");
+///
+/// use gwsample_basic::*;
+/// use gwsample_basic_metadata::*;
+/// ```
+///
+/// # Note:
+/// **This macro assumes you have not modified the output directory of `parse-sap-odata`!**.
+#[macro_export]
+macro_rules! include_mod_tree {
+    ($mod_name: tt) => {
+        include!(concat!(env!("OUT_DIR"), concat!("/", $mod_name, "/mod.rs")));
+    };
+}
@@ -0,0 +1,278 @@
+use quick_xml::{
+    escape::{escape, unescape},
+    events::{BytesStart, Event},
+    Reader,
+};
+
+use super::{MockEntity, MockService};
+use crate::edmx::data_services::schema::{entity_container::entity_set::EntitySet, entity_type::EntityType, Schema};
+use crate::property::Property;
+
+static XML_DECL: &str = "<?xml version=\"1.0\" encoding=\"utf-8\"?>";
+static NAMESPACES: &str = "xmlns=\"http://www.w3.org/2005/Atom\" \
+    xmlns:m=\"http://schemas.microsoft.com/ado/2007/08/dataservices/metadata\" \
+    xmlns:d=\"http://schemas.microsoft.com/ado/2007/08/dataservices\"";
+static SCHEME: &str = "http://schemas.microsoft.com/ado/2007/08/dataservices/scheme";
+static REL_RELATED: &str = "http://schemas.microsoft.com/ado/2007/08/dataservices/related/";
+// Responses are deterministic so that they can be compared in tests
+static UPDATED: &str = "2000-01-01T00:00:00Z";
+static EDM_STRING: &str = "Edm.String";
+static ELEMENT_PROPERTIES: &[u8] = b"properties";
+static ATTR_M_NULL: &[u8] = b"m:null";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+pub fn gen_error(status: u16, message: &str) -> String {
+    format!(
+        "{XML_DECL}<error xmlns=\"http://schemas.microsoft.com/ado/2007/08/dataservices/metadata\">\
+        <code>{status}</code><message xml:lang=\"en\">{}</message></error>",
+        escape(message)
+    )
+}
+
+pub fn gen_service_document(base_url: &str, entity_set_names: &[&str]) -> String {
+    let collections: String = entity_set_names
+        .iter()
+        .map(|name| {
+            format!("<app:collection href=\"{name}\"><atom:title type=\"text\">{name}</atom:title></app:collection>")
+        })
+        .collect();
+
+    format!(
+        "{XML_DECL}<app:service xml:base=\"{}\" xmlns:app=\"http://www.w3.org/2007/app\" \
+        xmlns:atom=\"http://www.w3.org/2005/Atom\"><app:workspace><atom:title type=\"text\">Data</atom:title>\
+        {collections}</app:workspace></app:service>",
+        escape(base_url)
+    )
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// The key predicate of an entity, E.G. `('HT-1000')` or `(SalesOrderID='1',ItemPosition='10')`
+pub fn key_predicate(et: &EntityType, entity: &MockEntity) -> String {
+    let literal = |name: &str| {
+        let value = entity.get(name).cloned().flatten().unwrap_or_default();
+        let edm_type = et
+            .properties
+            .iter()
+            .find(|p| p.odata_name.eq(name))
+            .map(|p| p.edm_type.as_str())
+            .unwrap_or(EDM_STRING);
+
+        match edm_type {
+            "Edm.String" => format!("'{}'", value.replace('\'', "''")),
+            "Edm.Guid" => format!("guid'{value}'"),
+            "Edm.DateTime" => format!("datetime'{value}'"),
+            "Edm.DateTimeOffset" => format!("datetimeoffset'{value}'"),
+            "Edm.Time" => format!("time'{value}'"),
+            "Edm.Int64" => format!("{value}L"),
+            "Edm.Decimal" => format!("{value}M"),
+            _ => value,
+        }
+    };
+
+    let key_props = &et.key.property_refs;
+
+    if key_props.len() == 1 {
+        format!("({})", literal(&key_props[0].name))
+    } else {
+        let pairs: Vec<String> = key_props
+            .iter()
+            .map(|pr| format!("{}={}", pr.name, literal(&pr.name)))
+            .collect();
+        format!("({})", pairs.join(","))
+    }
+}
+
+// Properties of a complex type are nested within the element of the complex property
+fn gen_property(schema: &Schema, prop: &Property, path: &str, entity: &MockEntity) -> String {
+    let name = &prop.odata_name;
+
+    if !prop.edm_type.starts_with("Edm.") {
+        let ct_name = prop.edm_type.rsplit('.').next().unwrap_or_default();
+        let children: String = schema
+            .complex_types
+            .iter()
+            .flatten()
+            .find(|ct| ct.name.eq(ct_name))
+            .map(|ct| {
+                ct.properties
+                    .iter()
+                    .map(|child| gen_property(schema, child, &format!("{path}{name}/"), entity))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        return format!("<d:{name} m:type=\"{}\">{children}</d:{name}>", prop.edm_type);
+    }
+
+    let type_attr = if prop.edm_type.eq(EDM_STRING) {
+        String::new()
+    } else {
+        format!(" m:type=\"{}\"", prop.edm_type)
+    };
+
+    match entity.get(&format!("{path}{name}")).cloned().flatten() {
+        Some(value) => format!("<d:{name}{type_attr}>{}</d:{name}>", escape(value.as_str())),
+        None => format!("<d:{name}{type_attr} m:null=\"true\"/>"),
+    }
+}
+
+pub fn gen_entry(
+    service: &MockService,
+    entity_set: &EntitySet,
+    et: &EntityType,
+    entity: &MockEntity,
+    is_document: bool,
+) -> String {
+    let schema = service.schema();
+    let href = escape(format!("{}{}", entity_set.name, key_predicate(et, entity))).into_owned();
+
+    let nav_links: String = et
+        .navigations
+        .iter()
+        .map(|nav| {
+            let link_type = match service.nav_target(entity_set, nav) {
+                Some(target) if !target.is_many => "entry",
+                _ => "feed",
+            };
+            format!(
+                "<link href=\"{href}/{name}\" rel=\"{REL_RELATED}{name}\" type=\"application/atom+xml;type={link_type}\" \
+                title=\"{name}\"/>",
+                name = nav.name
+            )
+        })
+        .collect();
+
+    let properties: String = et
+        .properties
+        .iter()
+        .map(|prop| gen_property(schema, prop, "", entity))
+        .collect();
+
+    let (decl, namespaces) = if is_document {
+        (
+            XML_DECL.to_owned(),
+            format!(" xml:base=\"{}\" {NAMESPACES}", escape(service.base_url.as_str())),
+        )
+    } else {
+        (String::new(), String::new())
+    };
+
+    format!(
+        "{decl}<entry{namespaces}><id>{base}{href}</id><title type=\"text\">{href}</title><updated>{UPDATED}</updated>\
+        <category term=\"{}\" scheme=\"{SCHEME}\"/><link href=\"{href}\" rel=\"self\" title=\"{}\"/>{nav_links}\
+        <content type=\"application/xml\"><m:properties>{properties}</m:properties></content></entry>",
+        entity_set.entity_type,
+        et.name,
+        base = escape(service.base_url.as_str()),
+    )
+}
+
+pub fn gen_feed(
+    service: &MockService,
+    resource_path: &str,
+    entity_set: &EntitySet,
+    et: &EntityType,
+    entities: &[&MockEntity],
+    count: Option<usize>,
+) -> String {
+    let path = escape(resource_path);
+    let base = escape(service.base_url.as_str());
+    let count_elem = count.map(|c| format!("<m:count>{c}</m:count>")).unwrap_or_default();
+    let entries: String = entities
+        .iter()
+        .map(|entity| gen_entry(service, entity_set, et, entity, false))
+        .collect();
+
+    format!(
+        "{XML_DECL}<feed xml:base=\"{base}\" {NAMESPACES}><id>{base}{path}</id><title type=\"text\">{}</title>\
+        <updated>{UPDATED}</updated><author><name/></author><link href=\"{path}\" rel=\"self\" title=\"{}\"/>\
+        {count_elem}{entries}</feed>",
+        entity_set.name, entity_set.name
+    )
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn is_null(elem: &BytesStart) -> bool {
+    elem.attributes()
+        .flatten()
+        .any(|attr| attr.key.as_ref() == ATTR_M_NULL && attr.value.as_ref() == b"true")
+}
+
+// An element within <m:properties> whose value is still being read
+struct OpenProperty {
+    name: String,
+    has_children: bool,
+    is_null: bool,
+}
+
+/// Read the property values of every `<m:properties>` element in an Atom feed or entry
+pub fn parse_feed_entities(xml: &str) -> Result<Vec<MockEntity>, String> {
+    let mut reader = Reader::from_str(xml);
+    reader.config_mut().trim_text(true);
+
+    let mut entities: Vec<MockEntity> = vec![];
+    let mut current: Option<MockEntity> = None;
+    let mut open: Vec<OpenProperty> = vec![];
+    let mut text = String::new();
+
+    let path_of = |open: &[OpenProperty]| open.iter().map(|p| p.name.as_str()).collect::<Vec<_>>().join("/");
+
+    loop {
+        match reader.read_event().map_err(|err| err.to_string())? {
+            Event::Start(elem) if elem.local_name().as_ref() == ELEMENT_PROPERTIES && current.is_none() => {
+                current = Some(MockEntity::new());
+            },
+            Event::Start(elem) if current.is_some() => {
+                if let Some(parent) = open.last_mut() {
+                    parent.has_children = true;
+                }
+
+                open.push(OpenProperty {
+                    name: String::from_utf8_lossy(elem.local_name().as_ref()).into_owned(),
+                    has_children: false,
+                    is_null: is_null(&elem),
+                });
+                text.clear();
+            },
+            Event::Empty(elem) if current.is_some() => {
+                let name = String::from_utf8_lossy(elem.local_name().as_ref()).into_owned();
+                let path = if open.is_empty() { name } else { format!("{}/{name}", path_of(&open)) };
+                let value = if is_null(&elem) { None } else { Some(String::new()) };
+
+                current.as_mut().unwrap().insert(path, value);
+            },
+            Event::Text(t) if current.is_some() => {
+                let raw = t.decode().map_err(|err| err.to_string())?;
+                text.push_str(&unescape(&raw).map_err(|err| err.to_string())?);
+            },
+            Event::CData(t) if current.is_some() => {
+                text.push_str(&t.decode().map_err(|err| err.to_string())?);
+            },
+            Event::GeneralRef(r) if current.is_some() => {
+                let name = r.decode().map_err(|err| err.to_string())?;
+                text.push_str(&unescape(&format!("&{name};")).map_err(|err| err.to_string())?);
+            },
+            Event::End(elem) if current.is_some() => {
+                if open.is_empty() && elem.local_name().as_ref() == ELEMENT_PROPERTIES {
+                    entities.push(current.take().unwrap());
+                    continue;
+                }
+
+                let path = path_of(&open);
+
+                if let Some(prop) = open.pop() {
+                    if !prop.has_children {
+                        let value = if prop.is_null { None } else { Some(std::mem::take(&mut text)) };
+                        current.as_mut().unwrap().insert(path, value);
+                    }
+                }
+
+                text.clear();
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+
+    Ok(entities)
+}
@@ -0,0 +1,842 @@
+mod atom;
+mod sample_data;
+mod server;
+
+pub use sample_data::SampleDataGenerator;
+pub use server::MockServer;
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap},
+    str::FromStr,
+};
+
+use crate::edmx::{
+    data_services::schema::{
+        entity_container::entity_set::EntitySet, entity_type::navigation_property::NavigationProperty,
+        entity_type::EntityType, Schema,
+    },
+    Edmx,
+};
+
+static CONTENT_TYPE_XML: &str = "application/xml";
+static CONTENT_TYPE_ATOM_SVC: &str = "application/atomsvc+xml";
+static CONTENT_TYPE_ATOM_FEED: &str = "application/atom+xml;type=feed";
+static CONTENT_TYPE_ATOM_ENTRY: &str = "application/atom+xml;type=entry";
+static CONTENT_TYPE_TEXT: &str = "text/plain";
+static SEGMENT_METADATA: &str = "$metadata";
+static SEGMENT_COUNT: &str = "$count";
+static MULTIPLICITY_MANY: &str = "*";
+
+/// The property values of an entity, keyed by OData property name.
+///
+/// A value of `None` represents `null`.
+/// The properties of a complex type are keyed using a path such as `Address/City`
+pub type MockEntity = BTreeMap<String, Option<String>>;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[derive(Debug)]
+pub enum MockServerError {
+    /// The metadata document could not be parsed
+    Metadata(quick_xml::DeError),
+    /// The metadata document does not contain a schema with an entity container
+    NoEntityContainer,
+    /// Data was supplied for an entity set that does not exist in the metadata
+    UnknownEntitySet(String),
+    /// An Atom feed fixture could not be parsed
+    Fixture(String),
+    /// More sample entities were requested than the entity type has distinct key values
+    TooManySampleEntities { entity_type: String, max: u64 },
+}
+
+impl std::fmt::Display for MockServerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MockServerError::Metadata(err) => write!(f, "Unable to parse metadata: {err}"),
+            MockServerError::NoEntityContainer => write!(f, "Metadata does not contain an entity container"),
+            MockServerError::UnknownEntitySet(name) => write!(f, "Entity set {name} does not exist"),
+            MockServerError::Fixture(msg) => write!(f, "Unable to parse Atom feed fixture: {msg}"),
+            MockServerError::TooManySampleEntities { entity_type, max } => {
+                write!(f, "Entity type {entity_type} has only {max} distinct key values")
+            },
+        }
+    }
+}
+
+impl std::error::Error for MockServerError {}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// The response to a request handled by a `MockService`
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockResponse {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl MockResponse {
+    fn ok(content_type: &'static str, body: String) -> Self {
+        MockResponse {
+            status: 200,
+            content_type,
+            body,
+        }
+    }
+
+    fn error(status: u16, message: &str) -> Self {
+        MockResponse {
+            status,
+            content_type: CONTENT_TYPE_XML,
+            body: atom::gen_error(status, message),
+        }
+    }
+}
+
+// The result of following the resource path of a request
+enum Resource<'a> {
+    Collection(&'a EntitySet, Vec<&'a MockEntity>),
+    Single(&'a EntitySet, &'a MockEntity),
+}
+
+// The entity set reached by following a navigation property, and the pairs of properties that link the two entities
+struct NavTarget<'a> {
+    entity_set: &'a EntitySet,
+    is_many: bool,
+    property_pairs: Vec<(String, String)>,
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// An in-memory OData V2 service built from a metadata document.
+///
+/// The service answers `GET` requests for:
+/// * The service document and `$metadata`
+/// * Entity set feeds, honouring the query options `$filter` (comparisons combined with `and` and `or`), `$skip`,
+///   `$top` and `$inlinecount`
+/// * `$count` of an entity set
+/// * Single entities identified by their key, E.G. `ProductSet('HT-1000')`
+/// * Navigation from a single entity via an association, E.G. `BusinessPartnerSet('0100000000')/ToProducts`
+///
+/// All responses are in Atom XML format.
+/// The data served for each entity set is either supplied as `MockEntity` instances or loaded from Atom feed fixtures
+pub struct MockService {
+    service_root: String,
+    base_url: String,
+    edmx: Edmx,
+    metadata_xml: String,
+    data: HashMap<String, Vec<MockEntity>>,
+}
+
+impl MockService {
+    /// Create a service from the text of a metadata document.
+    /// The `service_root` is the path under which the service is served, E.G. `/sap/opu/odata/sap/GWSAMPLE_BASIC/`
+    pub fn new(metadata_xml: &str, service_root: &str) -> Result<Self, MockServerError> {
+        let edmx = Edmx::from_str(metadata_xml).map_err(MockServerError::Metadata)?;
+
+        if container_schema(&edmx).is_none() {
+            return Err(MockServerError::NoEntityContainer);
+        }
+
+        let service_root = format!("/{}/", service_root.trim_matches('/')).replace("//", "/");
+
+        Ok(MockService {
+            base_url: service_root.clone(),
+            service_root,
+            edmx,
+            metadata_xml: metadata_xml.to_owned(),
+            data: HashMap::new(),
+        })
+    }
+
+    pub fn service_root(&self) -> &str {
+        &self.service_root
+    }
+
+    fn schema(&self) -> &Schema {
+        // Checked when the service was created
+        container_schema(&self.edmx).unwrap()
+    }
+
+    fn entity_sets(&self) -> &[EntitySet] {
+        &self.schema().entity_container.as_ref().unwrap().entity_sets
+    }
+
+    fn entity_set(&self, name: &str) -> Option<&EntitySet> {
+        self.entity_sets().iter().find(|es| es.name.eq(name))
+    }
+
+    fn entity_type_of(&self, entity_set: &EntitySet) -> Option<&EntityType> {
+        let et_name = entity_set.entity_type.rsplit('.').next().unwrap_or_default();
+        self.schema().entity_types.iter().find(|et| et.name.eq(et_name))
+    }
+
+    /// Add entities to an entity set
+    pub fn add_entities(&mut self, entity_set: &str, entities: Vec<MockEntity>) -> Result<(), MockServerError> {
+        if self.entity_set(entity_set).is_none() {
+            return Err(MockServerError::UnknownEntitySet(entity_set.to_owned()));
+        }
+
+        self.data.entry(entity_set.to_owned()).or_default().extend(entities);
+        Ok(())
+    }
+
+    /// Add the entries of an Atom feed (E.G. a response captured from a real system) to an entity set.
+    /// Returns the number of entities added
+    pub fn load_feed(&mut self, entity_set: &str, atom_xml: &str) -> Result<usize, MockServerError> {
+        let entities = atom::parse_feed_entities(atom_xml).map_err(MockServerError::Fixture)?;
+        let count = entities.len();
+
+        self.add_entities(entity_set, entities)?;
+        Ok(count)
+    }
+
+    /// Add `count` generated entities to an entity set.
+    ///
+    /// Where the entity type refers to another entity set through a to-one association and that entity set already
+    /// contains data, the referring properties are given the values of existing entities so that navigation works
+    pub fn add_sample_entities(
+        &mut self,
+        entity_set: &str,
+        count: usize,
+        generator: &mut SampleDataGenerator,
+    ) -> Result<(), MockServerError> {
+        let entities = self.gen_sample_entities(entity_set, count, generator)?;
+        self.add_entities(entity_set, entities)
+    }
+
+    /// Generate an Atom feed containing `count` sample entities of an entity set, E.G. for use as a test fixture.
+    /// The generated entities are not added to the service
+    pub fn gen_sample_feed(
+        &self,
+        entity_set: &str,
+        count: usize,
+        generator: &mut SampleDataGenerator,
+    ) -> Result<String, MockServerError> {
+        let entities = self.gen_sample_entities(entity_set, count, generator)?;
+        let es = self.entity_set(entity_set).unwrap();
+        let et = self.entity_type_of(es).unwrap();
+        let entity_refs: Vec<&MockEntity> = entities.iter().collect();
+
+        Ok(atom::gen_feed(self, entity_set, es, et, &entity_refs, None))
+    }
+
+    fn gen_sample_entities(
+        &self,
+        entity_set: &str,
+        count: usize,
+        generator: &mut SampleDataGenerator,
+    ) -> Result<Vec<MockEntity>, MockServerError> {
+        let (es, et) = self
+            .entity_set(entity_set)
+            .and_then(|es| self.entity_type_of(es).map(|et| (es, et)))
+            .ok_or_else(|| MockServerError::UnknownEntitySet(entity_set.to_owned()))?;
+        let key_names: Vec<&str> = et.key.property_refs.iter().map(|pr| pr.name.as_str()).collect();
+        let mut entities = generator.gen_entities(self.schema(), et, count)?;
+
+        for nav in &et.navigations {
+            let target = match self.nav_target(es, nav) {
+                Some(target) if !target.is_many => target,
+                _ => continue,
+            };
+            let target_entities = self.entities_of(target.entity_set);
+
+            if target_entities.is_empty() {
+                continue;
+            }
+
+            for (idx, entity) in entities.iter_mut().enumerate() {
+                let target_entity = target_entities[idx % target_entities.len()];
+
+                for (prop_name, target_prop_name) in &target.property_pairs {
+                    if entity.contains_key(prop_name) && !key_names.contains(&prop_name.as_str()) {
+                        entity.insert(prop_name.clone(), target_entity.get(target_prop_name).cloned().flatten());
+                    }
+                }
+            }
+        }
+
+        Ok(entities)
+    }
+
+    fn entities_of(&self, entity_set: &EntitySet) -> Vec<&MockEntity> {
+        self.data
+            .get(&entity_set.name)
+            .map(|entities| entities.iter().collect())
+            .unwrap_or_default()
+    }
+
+    // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+    /// Handle a request whose target is a path (relative to the server) plus an optional query string
+    pub fn handle(&self, method: &str, target: &str) -> MockResponse {
+        if !method.eq_ignore_ascii_case("GET") {
+            return MockResponse::error(405, &format!("Method {method} is not supported"));
+        }
+
+        let (path, query) = target.split_once('?').unwrap_or((target, ""));
+        let path = percent_decode(path);
+        let root_no_slash = self.service_root.trim_end_matches('/');
+
+        let resource_path = if path.eq(root_no_slash) {
+            ""
+        } else if let Some(rel) = path.strip_prefix(&self.service_root) {
+            rel.trim_end_matches('/')
+        } else {
+            return MockResponse::error(404, &format!("No service at {path}"));
+        };
+
+        if resource_path.is_empty() {
+            let set_names: Vec<&str> = self.entity_sets().iter().map(|es| es.name.as_str()).collect();
+            return MockResponse::ok(CONTENT_TYPE_ATOM_SVC, atom::gen_service_document(&self.base_url, &set_names));
+        }
+
+        if resource_path.eq(SEGMENT_METADATA) {
+            return MockResponse::ok(CONTENT_TYPE_XML, self.metadata_xml.clone());
+        }
+
+        let options = parse_query_string(query);
+        let segments = split_path(resource_path);
+        let is_count = segments.last().map(|s| s.eq(SEGMENT_COUNT)).unwrap_or(false);
+        let resource_segments = if is_count { &segments[..segments.len() - 1] } else { &segments[..] };
+
+        let resource = match self.resolve(resource_segments) {
+            Ok(resource) => resource,
+            Err(response) => return response,
+        };
+
+        match resource {
+            Resource::Collection(entity_set, entities) => {
+                let et = self.entity_type_of(entity_set).unwrap();
+                let entities = match apply_filter(self.schema(), et, entities, options.get("$filter")) {
+                    Ok(entities) => entities,
+                    Err(msg) => return MockResponse::error(400, &msg),
+                };
+
+                if is_count {
+                    return MockResponse::ok(CONTENT_TYPE_TEXT, entities.len().to_string());
+                }
+
+                let count = options
+                    .get("$inlinecount")
+                    .filter(|v| v.eq(&"allpages"))
+                    .map(|_| entities.len());
+                let skip = match parse_count_option(&options, "$skip") {
+                    Ok(skip) => skip.unwrap_or(0),
+                    Err(msg) => return MockResponse::error(400, &msg),
+                };
+                let top = match parse_count_option(&options, "$top") {
+                    Ok(top) => top.unwrap_or(usize::MAX),
+                    Err(msg) => return MockResponse::error(400, &msg),
+                };
+                let page: Vec<&MockEntity> = entities.into_iter().skip(skip).take(top).collect();
+
+                MockResponse::ok(
+                    CONTENT_TYPE_ATOM_FEED,
+                    atom::gen_feed(self, resource_path, entity_set, et, &page, count),
+                )
+            },
+            Resource::Single(entity_set, entity) => {
+                if is_count {
+                    return MockResponse::ok(CONTENT_TYPE_TEXT, "1".to_owned());
+                }
+
+                let et = self.entity_type_of(entity_set).unwrap();
+                MockResponse::ok(CONTENT_TYPE_ATOM_ENTRY, atom::gen_entry(self, entity_set, et, entity, true))
+            },
+        }
+    }
+
+    // Follow the segments of a resource path to an entity set or a single entity
+    fn resolve(&self, segments: &[String]) -> Result<Resource<'_>, MockResponse> {
+        let Some((first, rest)) = segments.split_first() else {
+            return Err(MockResponse::error(404, "Resource not found"));
+        };
+
+        let (set_name, key_predicate) = split_key_predicate(first);
+        let entity_set = self
+            .entity_set(set_name)
+            .ok_or_else(|| MockResponse::error(404, &format!("Resource not found for segment '{set_name}'")))?;
+
+        let mut resource = match key_predicate {
+            Some(predicate) => Resource::Single(entity_set, self.find_by_key(entity_set, predicate)?),
+            None => Resource::Collection(entity_set, self.entities_of(entity_set)),
+        };
+
+        for segment in rest {
+            let Resource::Single(source_set, source) = resource else {
+                return Err(MockResponse::error(
+                    400,
+                    &format!("Segment '{segment}' must follow a single entity"),
+                ));
+            };
+
+            let (nav_name, key_predicate) = split_key_predicate(segment);
+            let source_type = self.entity_type_of(source_set).unwrap();
+            let nav = source_type
+                .navigations
+                .iter()
+                .find(|nav| nav.name.eq(nav_name))
+                .ok_or_else(|| MockResponse::error(404, &format!("Resource not found for segment '{nav_name}'")))?;
+            let target = self.nav_target(source_set, nav).ok_or_else(|| {
+                MockResponse::error(501, &format!("Navigation property {nav_name} cannot be resolved"))
+            })?;
+
+            let related: Vec<&MockEntity> = self
+                .entities_of(target.entity_set)
+                .into_iter()
+                .filter(|candidate| {
+                    target
+                        .property_pairs
+                        .iter()
+                        .all(|(src, tgt)| matches!((source.get(src), candidate.get(tgt)), (Some(Some(a)), Some(Some(b))) if a.eq(b)))
+                })
+                .collect();
+
+            resource = match (target.is_many, key_predicate) {
+                (true, None) => Resource::Collection(target.entity_set, related),
+                (true, Some(predicate)) => {
+                    let key_values = parse_key_predicate(predicate);
+                    let et = self.entity_type_of(target.entity_set).unwrap();
+                    let entity = related
+                        .into_iter()
+                        .find(|e| matches_key(et, e, &key_values))
+                        .ok_or_else(|| MockResponse::error(404, "Resource not found"))?;
+                    Resource::Single(target.entity_set, entity)
+                },
+                (false, _) => {
+                    let entity = related
+                        .into_iter()
+                        .next()
+                        .ok_or_else(|| MockResponse::error(404, "Resource not found"))?;
+                    Resource::Single(target.entity_set, entity)
+                },
+            };
+        }
+
+        Ok(resource)
+    }
+
+    fn find_by_key(&self, entity_set: &EntitySet, predicate: &str) -> Result<&MockEntity, MockResponse> {
+        let et = self.entity_type_of(entity_set).unwrap();
+        let key_values = parse_key_predicate(predicate);
+
+        self.data
+            .get(&entity_set.name)
+            .and_then(|entities| entities.iter().find(|e| matches_key(et, e, &key_values)))
+            .ok_or_else(|| MockResponse::error(404, "Resource not found"))
+    }
+
+    // Find the entity set at the other end of a navigation property using the association and association set
+    fn nav_target(&self, source_set: &EntitySet, nav: &NavigationProperty) -> Option<NavTarget<'_>> {
+        let schema = self.schema();
+        let assoc_name = nav.relationship.rsplit('.').next().unwrap_or_default();
+        let assoc = schema.associations.iter().find(|a| a.name.eq(assoc_name))?;
+        let to_end = assoc.ends.iter().find(|end| end.role.eq(&nav.to_role))?;
+
+        let from_assoc_set = schema
+            .entity_container
+            .as_ref()?
+            .association_sets
+            .iter()
+            .filter(|aset| aset.association.eq(&nav.relationship))
+            .find(|aset| {
+                aset.ends
+                    .iter()
+                    .any(|end| end.role.eq(&nav.from_role) && end.entity_set.as_deref() == Some(&source_set.name))
+            })
+            .and_then(|aset| aset.ends.iter().find(|end| end.role.eq(&nav.to_role)))
+            .and_then(|end| end.entity_set.as_deref())
+            .and_then(|name| self.entity_set(name));
+
+        let entity_set = from_assoc_set.or_else(|| {
+            self.entity_sets()
+                .iter()
+                .find(|es| Some(&es.entity_type) == to_end.end_type.as_ref())
+        })?;
+
+        let property_pairs = match &assoc.referential_constraint {
+            Some(rc) if rc.principal.role.eq(&nav.from_role) => rc
+                .principal
+                .property_refs
+                .iter()
+                .zip(rc.dependent.property_refs.iter())
+                .map(|(p, d)| (p.name.clone(), d.name.clone()))
+                .collect(),
+            Some(rc) => rc
+                .dependent
+                .property_refs
+                .iter()
+                .zip(rc.principal.property_refs.iter())
+                .map(|(d, p)| (d.name.clone(), p.name.clone()))
+                .collect(),
+            // Without a referential constraint, assume the target's key properties also exist in the source
+            None => self
+                .entity_type_of(entity_set)?
+                .key
+                .property_refs
+                .iter()
+                .map(|pr| (pr.name.clone(), pr.name.clone()))
+                .collect(),
+        };
+
+        Some(NavTarget {
+            entity_set,
+            is_many: to_end.multiplicity.as_deref() == Some(MULTIPLICITY_MANY),
+            property_pairs,
+        })
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn container_schema(edmx: &Edmx) -> Option<&Schema> {
+    edmx.data_services
+        .schemas
+        .iter()
+        .find(|schema| schema.entity_container.is_some())
+}
+
+fn percent_decode(s: &str) -> String {
+    let bytes = s.as_bytes();
+    let mut out: Vec<u8> = Vec::with_capacity(bytes.len());
+    let mut idx = 0;
+
+    while idx < bytes.len() {
+        if bytes[idx] == b'%' && idx + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[idx + 1..idx + 3]).unwrap_or_default();
+
+            if let Ok(b) = u8::from_str_radix(hex, 16) {
+                out.push(b);
+                idx += 3;
+                continue;
+            }
+        }
+
+        out.push(bytes[idx]);
+        idx += 1;
+    }
+
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn parse_query_string(query: &str) -> HashMap<String, String> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (percent_decode(name), percent_decode(value))
+        })
+        .collect()
+}
+
+fn parse_count_option(options: &HashMap<String, String>, name: &str) -> Result<Option<usize>, String> {
+    options
+        .get(name)
+        .map(|v| v.parse::<usize>().map_err(|_| format!("Invalid value '{v}' for {name}")))
+        .transpose()
+}
+
+// Split a string on a delimiter, ignoring delimiters inside single-quoted literals or parentheses
+fn split_outside_quotes(s: &str, delimiter: &str) -> Vec<String> {
+    let mut parts: Vec<String> = vec![];
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut depth: usize = 0;
+    let mut rest = s;
+
+    while let Some(c) = rest.chars().next() {
+        if !in_quotes && depth == 0 && rest.starts_with(delimiter) {
+            parts.push(std::mem::take(&mut current));
+            rest = &rest[delimiter.len()..];
+            continue;
+        }
+
+        match c {
+            '\'' => in_quotes = !in_quotes,
+            '(' if !in_quotes => depth += 1,
+            ')' if !in_quotes => depth = depth.saturating_sub(1),
+            _ => {},
+        }
+
+        current.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+
+    parts.push(current);
+    parts
+}
+
+fn split_path(path: &str) -> Vec<String> {
+    split_outside_quotes(path, "/")
+        .into_iter()
+        .filter(|segment| !segment.is_empty())
+        .collect()
+}
+
+// Split `ProductSet('HT-1000')` into `ProductSet` and `'HT-1000'`
+fn split_key_predicate(segment: &str) -> (&str, Option<&str>) {
+    match segment.find('(') {
+        Some(idx) if segment.ends_with(')') => (&segment[..idx], Some(&segment[idx + 1..segment.len() - 1])),
+        _ => (segment, None),
+    }
+}
+
+// Parse `'HT-1000'` or `SalesOrderID='1',ItemPosition='10'` into optional property names and literals
+fn parse_key_predicate(predicate: &str) -> Vec<(Option<String>, String)> {
+    split_outside_quotes(predicate, ",")
+        .into_iter()
+        .map(|part| match split_outside_quotes(&part, "=").as_slice() {
+            [name, literal] => (Some(name.trim().to_owned()), literal.trim().to_owned()),
+            _ => (None, part.trim().to_owned()),
+        })
+        .collect()
+}
+
+fn matches_key(et: &EntityType, entity: &MockEntity, key_values: &[(Option<String>, String)]) -> bool {
+    let key_props = &et.key.property_refs;
+
+    if key_values.len() != key_props.len() {
+        return false;
+    }
+
+    key_values.iter().zip(key_props.iter()).all(|((name, literal), key_prop)| {
+        let prop_name = name.as_deref().unwrap_or(&key_prop.name);
+        let edm_type = et
+            .properties
+            .iter()
+            .find(|prop| prop.odata_name.eq(prop_name))
+            .map_or("Edm.String", |prop| prop.edm_type.as_str());
+
+        match entity.get(prop_name) {
+            Some(Some(value)) => compare_values(edm_type, value, &literal_value(literal)) == Ordering::Equal,
+            _ => false,
+        }
+    })
+}
+
+// The value of an OData V2 literal without its quotes, type prefix or type suffix
+fn literal_value(literal: &str) -> String {
+    let quoted = literal.find('\'').filter(|_| literal.ends_with('\'') && literal.len() > 1);
+
+    if let Some(idx) = quoted {
+        return literal[idx + 1..literal.len() - 1].replace("''", "'");
+    }
+
+    let trimmed = literal.trim_end_matches(['M', 'm', 'L', 'l', 'D', 'd', 'F', 'f']);
+    if trimmed.len() < literal.len() && trimmed.parse::<f64>().is_ok() {
+        trimmed.to_owned()
+    } else {
+        literal.to_owned()
+    }
+}
+
+// Values of the numeric EDM types are compared as numbers, and all other values (including strings that look like
+// numbers, such as `'0100000000'`) are compared as text
+fn compare_values(edm_type: &str, a: &str, b: &str) -> Ordering {
+    match edm_type {
+        "Edm.Byte" | "Edm.SByte" | "Edm.Int16" | "Edm.Int32" | "Edm.Int64" => {
+            match (a.parse::<i128>(), b.parse::<i128>()) {
+                (Ok(x), Ok(y)) => x.cmp(&y),
+                _ => a.cmp(b),
+            }
+        },
+        "Edm.Decimal" | "Edm.Double" | "Edm.Single" => match (a.parse::<f64>(), b.parse::<f64>()) {
+            (Ok(x), Ok(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+            _ => a.cmp(b),
+        },
+        _ => a.cmp(b),
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// A `$filter` expression built from comparisons between a property and a literal, combined using `and` and `or`
+enum Filter {
+    And(Vec<Filter>),
+    Or(Vec<Filter>),
+    Comparison(Comparison),
+}
+
+impl Filter {
+    fn matches(&self, entity: &MockEntity) -> bool {
+        match self {
+            Filter::And(filters) => filters.iter().all(|filter| filter.matches(entity)),
+            Filter::Or(filters) => filters.iter().any(|filter| filter.matches(entity)),
+            Filter::Comparison(comparison) => comparison.matches(entity),
+        }
+    }
+}
+
+// A single comparison from a `$filter` expression
+struct Comparison {
+    property: String,
+    edm_type: String,
+    operator: String,
+    value: Option<String>,
+}
+
+impl Comparison {
+    fn matches(&self, entity: &MockEntity) -> bool {
+        let actual = entity.get(&self.property).cloned().flatten();
+
+        match (actual, &self.value) {
+            (Some(a), Some(b)) => {
+                let ord = compare_values(&self.edm_type, &a, b);
+                match self.operator.as_str() {
+                    "eq" => ord == Ordering::Equal,
+                    "ne" => ord != Ordering::Equal,
+                    "gt" => ord == Ordering::Greater,
+                    "ge" => ord != Ordering::Less,
+                    "lt" => ord == Ordering::Less,
+                    _ => ord != Ordering::Greater,
+                }
+            },
+            (None, None) => self.operator.eq("eq"),
+            (Some(_), None) | (None, Some(_)) => self.operator.eq("ne"),
+        }
+    }
+}
+
+// The byte index of the parenthesis that closes the one at the start of `s`, ignoring parentheses in quoted literals
+fn closing_paren(s: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_quotes = false;
+
+    for (idx, c) in s.char_indices() {
+        match c {
+            '\'' => in_quotes = !in_quotes,
+            '(' if !in_quotes => depth += 1,
+            ')' if !in_quotes => {
+                depth -= 1;
+
+                if depth == 0 {
+                    return Some(idx);
+                }
+            },
+            _ => {},
+        }
+    }
+
+    None
+}
+
+// Remove parentheses only where they enclose the whole expression
+fn strip_parens(s: &str) -> &str {
+    let mut s = s.trim();
+
+    while s.starts_with('(') && closing_paren(s) == Some(s.len() - 1) {
+        s = s[1..s.len() - 1].trim();
+    }
+
+    s
+}
+
+// The OData type of a property path such as `Price` or `Address/City`
+fn property_type<'a>(schema: &'a Schema, et: &'a EntityType, path: &str) -> Option<&'a str> {
+    let mut segments = path.split('/');
+    let first = segments.next()?;
+    let mut prop = et.properties.iter().find(|prop| prop.odata_name.eq(first))?;
+
+    for segment in segments {
+        let ct_name = prop.edm_type.rsplit('.').next().unwrap_or_default();
+        let ct = schema.complex_types.iter().flatten().find(|ct| ct.name.eq(ct_name))?;
+        prop = ct.properties.iter().find(|child| child.odata_name.eq(segment))?;
+    }
+
+    Some(&prop.edm_type)
+}
+
+// Check that a literal is valid for the OData type of the property with which it is compared, and return its value
+fn parse_literal(edm_type: &str, literal: &str) -> Result<Option<String>, String> {
+    if literal.eq("null") {
+        return Ok(None);
+    }
+
+    // Literals such as `datetime'2024-01-01T00:00:00'` must have the prefix of their type
+    let has_prefix = |prefix: &str| {
+        literal.len() > prefix.len() + 1
+            && literal[..prefix.len()].eq_ignore_ascii_case(prefix)
+            && literal[prefix.len()..].starts_with('\'')
+            && literal.ends_with('\'')
+    };
+    let number = |suffixes: &[char]| literal.trim_end_matches(suffixes).to_owned();
+
+    let is_valid = match edm_type {
+        "Edm.String" => has_prefix(""),
+        "Edm.Boolean" => matches!(literal, "true" | "false"),
+        "Edm.Byte" => literal.parse::<u8>().is_ok(),
+        "Edm.SByte" => literal.parse::<i8>().is_ok(),
+        "Edm.Int16" => literal.parse::<i16>().is_ok(),
+        "Edm.Int32" => literal.parse::<i32>().is_ok(),
+        "Edm.Int64" => number(&['L', 'l']).parse::<i64>().is_ok(),
+        "Edm.Decimal" => number(&['M', 'm']).parse::<f64>().is_ok(),
+        "Edm.Double" => number(&['D', 'd']).parse::<f64>().is_ok(),
+        "Edm.Single" => number(&['F', 'f']).parse::<f64>().is_ok(),
+        "Edm.DateTime" => has_prefix("datetime"),
+        "Edm.DateTimeOffset" => has_prefix("datetimeoffset"),
+        "Edm.Time" => has_prefix("time"),
+        "Edm.Guid" => has_prefix("guid"),
+        "Edm.Binary" => has_prefix("X") || has_prefix("binary"),
+        _ => false,
+    };
+
+    if is_valid {
+        Ok(Some(literal_value(literal)))
+    } else {
+        Err(format!("Invalid literal {literal} for a property of type {edm_type}"))
+    }
+}
+
+// Functions such as `substringof` and the operators `not`, `add`, `sub` etc are not supported, and `and` takes
+// precedence over `or`
+fn parse_filter(schema: &Schema, et: &EntityType, expression: &str) -> Result<Filter, String> {
+    let expression = strip_parens(expression);
+
+    for (operator, combine) in [(" or ", Filter::Or as fn(Vec<Filter>) -> Filter), (" and ", Filter::And)] {
+        let parts = split_outside_quotes(expression, operator);
+
+        if parts.len() > 1 {
+            return parts
+                .iter()
+                .map(|part| parse_filter(schema, et, part))
+                .collect::<Result<Vec<Filter>, String>>()
+                .map(combine);
+        }
+    }
+
+    let mut tokens = expression.splitn(3, ' ');
+
+    match (tokens.next(), tokens.next(), tokens.next()) {
+        (Some(property), Some(operator), Some(literal))
+            if matches!(operator, "eq" | "ne" | "gt" | "ge" | "lt" | "le") =>
+        {
+            let edm_type = property_type(schema, et, property)
+                .ok_or_else(|| format!("Property {property} does not exist in entity type {}", et.name))?;
+
+            Ok(Filter::Comparison(Comparison {
+                property: property.to_owned(),
+                edm_type: edm_type.to_owned(),
+                operator: operator.to_owned(),
+                value: parse_literal(edm_type, literal.trim())?,
+            }))
+        },
+        _ => Err(format!("Unsupported filter expression '{expression}'")),
+    }
+}
+
+fn apply_filter<'a>(
+    schema: &Schema,
+    et: &EntityType,
+    entities: Vec<&'a MockEntity>,
+    filter: Option<&String>,
+) -> Result<Vec<&'a MockEntity>, String> {
+    let Some(expression) = filter else {
+        return Ok(entities);
+    };
+
+    let filter = parse_filter(schema, et, expression)?;
+
+    Ok(entities.into_iter().filter(|entity| filter.matches(entity)).collect())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(feature = "parser")]
+#[cfg(test)]
+pub mod unit_tests;
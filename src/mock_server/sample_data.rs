@@ -0,0 +1,472 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::{MockEntity, MockServerError};
+use crate::{
+    edmx::data_services::schema::{entity_type::EntityType, Schema},
+    property::Property,
+    sap_annotations::display_format::SAPDisplayFormatProperty,
+    sap_semantics::property::SAPSemanticsProperty,
+};
+
+static FIRST_NAMES: &[&str] = &[
+    "Anna", "Ben", "Chloe", "David", "Emma", "Felix", "Grace", "Hiro", "Ines", "Jonas",
+];
+static LAST_NAMES: &[&str] = &[
+    "Becker", "Dubois", "Garcia", "Jensen", "Miller", "Nakamura", "Rossi", "Schmidt", "Smith", "Weber",
+];
+static HONORIFICS: &[&str] = &["Mr.", "Ms.", "Dr.", "Prof."];
+static COMPANIES: &[&str] = &[
+    "Becker Berlin",
+    "Delbont Industries",
+    "Panorama Studios",
+    "Telecomunicaciones Star",
+    "SAP",
+];
+static ORG_UNITS: &[&str] = &["Sales", "Purchasing", "Finance", "Logistics", "Marketing"];
+static JOB_TITLES: &[&str] = &["Sales Manager", "Buyer", "Accountant", "Developer", "Consultant"];
+static STREETS: &[&str] = &[
+    "Main Street",
+    "Dietmar-Hopp-Allee",
+    "Rue de Rivoli",
+    "Calle Mayor",
+    "Market Square",
+];
+static CITIES: &[&str] = &["Walldorf", "Berlin", "Paris", "Madrid", "London", "Tokyo", "Palo Alto"];
+static REGIONS: &[&str] = &["BW", "BE", "IDF", "MD", "LND", "CA"];
+static COUNTRIES: &[&str] = &["DE", "FR", "ES", "GB", "JP", "US"];
+static CURRENCIES: &[&str] = &["EUR", "USD", "GBP", "JPY", "CHF"];
+static UNITS: &[&str] = &["EA", "PC", "KG", "L", "M"];
+static ALPHANUMERIC: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+static BASE64: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+// Decimal values are kept to a readable magnitude even when the precision would allow more integer digits
+static MAX_DECIMAL_INTEGER_DIGITS: u16 = 6;
+static DEFAULT_DECIMAL_PRECISION: u16 = 16;
+// String keys are at most this many digits long, and decimal keys have at most this many digits
+static MAX_STRING_KEY_DIGITS: u32 = 10;
+static MAX_DECIMAL_KEY_DIGITS: u32 = 18;
+// Binary values are at most this many bytes long
+static MAX_BINARY_BYTES: usize = 9;
+// Date and time keys count forwards from 2020-01-01, and must not pass 9999-12-31 (both as days since 1970-01-01)
+static FIRST_KEY_DAY: u64 = 18_262;
+static LAST_KEY_DAY: u64 = 2_932_896;
+static SECONDS_PER_DAY: u64 = 86_400;
+// The largest integers that floating point keys can represent exactly
+static MAX_SINGLE_KEY: u64 = 1 << f32::MANTISSA_DIGITS;
+static MAX_DOUBLE_KEY: u64 = 1 << f64::MANTISSA_DIGITS;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generates sample property values that respect the constraints declared in the metadata.
+///
+/// * String values are never longer than `MaxLength`
+/// * Decimal values fit within `Precision` and have exactly `Scale` fractional digits
+/// * Properties with `sap:semantics` (E.G. `email`, `zip` or `currency-code`) receive plausible values of that kind
+/// * Properties with `sap:display-format="UpperCase"` are upper case, and those with `"Date"` have no time component
+/// * Key properties are derived from the entity's index so that they are unique, and `gen_entities` fails rather than
+///   generate more entities than the key properties have distinct values
+/// * Only nullable, non-key properties are ever `null`
+///
+/// The same seed always generates the same values
+pub struct SampleDataGenerator {
+    state: u64,
+    null_percentage: u8,
+}
+
+impl SampleDataGenerator {
+    /// Create a generator.
+    /// If no seed is supplied, one is taken from the system clock.
+    /// `null_percentage` is the approximate percentage of nullable properties that will be given a `null` value
+    pub fn new(seed: Option<u64>, null_percentage: u8) -> Self {
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_nanos() as u64)
+                .unwrap_or_default()
+        });
+
+        SampleDataGenerator {
+            state: seed,
+            null_percentage: null_percentage.min(100),
+        }
+    }
+
+    // SplitMix64
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // A value in the range `0..bound`
+    fn below(&mut self, bound: u64) -> u64 {
+        if bound == 0 {
+            0
+        } else {
+            self.next_u64() % bound
+        }
+    }
+
+    fn pick<'a>(&mut self, values: &[&'a str]) -> &'a str {
+        values[self.below(values.len() as u64) as usize]
+    }
+
+    fn digits(&mut self, len: usize) -> String {
+        (0..len).map(|_| char::from(b'0' + self.below(10) as u8)).collect()
+    }
+
+    fn alphanumeric(&mut self, len: usize) -> String {
+        (0..len)
+            .map(|_| char::from(ALPHANUMERIC[self.below(ALPHANUMERIC.len() as u64) as usize]))
+            .collect()
+    }
+
+    // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+    /// Generate `count` entities of the given entity type.
+    /// Fails if the entity type's key properties cannot hold `count` distinct values
+    pub fn gen_entities(
+        &mut self,
+        schema: &Schema,
+        et: &EntityType,
+        count: usize,
+    ) -> Result<Vec<MockEntity>, MockServerError> {
+        let max = Self::max_entities(et);
+
+        if count as u64 > max {
+            return Err(MockServerError::TooManySampleEntities {
+                entity_type: et.name.clone(),
+                max,
+            });
+        }
+
+        Ok((0..count).map(|idx| self.gen_entity(schema, et, idx)).collect())
+    }
+
+    /// The number of entities with unique keys that can be generated for an entity type.
+    /// The keys of an entity are unique if the value of any one key property is unique
+    pub fn max_entities(et: &EntityType) -> u64 {
+        et.properties
+            .iter()
+            .filter(|prop| et.key.property_refs.iter().any(|pr| pr.name.eq(&prop.odata_name)))
+            .map(Self::max_key_values)
+            .max()
+            .unwrap_or(u64::MAX)
+    }
+
+    // The number of distinct values generated for a key property
+    fn max_key_values(prop: &Property) -> u64 {
+        let key_days = LAST_KEY_DAY - FIRST_KEY_DAY + 1;
+
+        match prop.edm_type.trim_start_matches("Edm.") {
+            "Binary" => match Self::binary_len(prop).min(8) {
+                8 => u64::MAX,
+                len => (1u64 << (8 * len)) - 1,
+            },
+            "Boolean" => 2,
+            "Byte" => u8::MAX as u64,
+            "DateTime" | "DateTimeOffset" if Self::is_date_only(prop) => key_days,
+            "DateTime" | "DateTimeOffset" => key_days * SECONDS_PER_DAY,
+            "Decimal" => 10u64.pow(Self::decimal_key_digits(prop)) - 1,
+            "Double" => MAX_DOUBLE_KEY,
+            "Guid" => (1u64 << 48) - 1,
+            "Int16" => i16::MAX as u64,
+            "Int32" => i32::MAX as u64,
+            "Int64" => i64::MAX as u64,
+            "SByte" => i8::MAX as u64,
+            "Single" => MAX_SINGLE_KEY,
+            "Time" => SECONDS_PER_DAY,
+            _ => 10u64.pow(Self::string_key_width(prop) as u32) - 1,
+        }
+    }
+
+    fn is_date_only(prop: &Property) -> bool {
+        prop.sap_annotations.display_format == Some(SAPDisplayFormatProperty::Date)
+    }
+
+    // Up to 9 bytes, rounded down to a multiple of 3 so that the Base64 encoding needs no padding, but never more than
+    // MaxLength
+    fn binary_len(prop: &Property) -> usize {
+        let max_length = prop.max_length.map(usize::from).filter(|ml| *ml > 0);
+
+        (max_length.unwrap_or(MAX_BINARY_BYTES).clamp(3, MAX_BINARY_BYTES) / 3 * 3)
+            .min(max_length.unwrap_or(MAX_BINARY_BYTES))
+    }
+
+    // Decimal keys use the integer digits, or the fractional digits if there are no integer digits
+    fn decimal_key_digits(prop: &Property) -> u32 {
+        let precision = prop.precision.unwrap_or(DEFAULT_DECIMAL_PRECISION).max(1);
+        let scale = prop.scale.unwrap_or(0).min(precision);
+        let digits = if precision > scale { precision - scale } else { scale };
+
+        (digits as u32).min(MAX_DECIMAL_KEY_DIGITS)
+    }
+
+    fn string_key_width(prop: &Property) -> usize {
+        prop.max_length
+            .map(|ml| ml as usize)
+            .filter(|ml| *ml > 0)
+            .unwrap_or(MAX_STRING_KEY_DIGITS as usize)
+            .min(MAX_STRING_KEY_DIGITS as usize)
+    }
+
+    /// Generate a single entity.
+    /// The `index` of the entity within its entity set is used to derive unique key values
+    pub fn gen_entity(&mut self, schema: &Schema, et: &EntityType, index: usize) -> MockEntity {
+        let mut entity = MockEntity::new();
+
+        for prop in &et.properties {
+            let is_key = et.key.property_refs.iter().any(|pr| pr.name.eq(&prop.odata_name));
+            self.gen_property_into(&mut entity, schema, prop, "", is_key, index);
+        }
+
+        entity
+    }
+
+    fn gen_property_into(
+        &mut self,
+        entity: &mut MockEntity,
+        schema: &Schema,
+        prop: &Property,
+        path: &str,
+        is_key: bool,
+        index: usize,
+    ) {
+        let name = format!("{path}{}", prop.odata_name);
+
+        if !prop.edm_type.starts_with("Edm.") {
+            let ct_name = prop.edm_type.rsplit('.').next().unwrap_or_default();
+
+            if let Some(ct) = schema.complex_types.iter().flatten().find(|ct| ct.name.eq(ct_name)) {
+                for child in &ct.properties {
+                    self.gen_property_into(entity, schema, child, &format!("{name}/"), false, index);
+                }
+            }
+
+            return;
+        }
+
+        let value = if !is_key && prop.nullable && self.below(100) < self.null_percentage as u64 {
+            None
+        } else {
+            Some(self.gen_value(prop, is_key, index))
+        };
+
+        entity.insert(name, value);
+    }
+
+    /// Generate a value for a property of an EDM type, formatted as it would appear in an Atom feed
+    pub fn gen_value(&mut self, prop: &Property, is_key: bool, index: usize) -> String {
+        let edm_type = prop.edm_type.trim_start_matches("Edm.");
+        let display_format = prop.sap_annotations.display_format.as_ref();
+        let serial = index as u64 + 1;
+
+        // Key values are derived from the position of the entity in its set, which `gen_entities` keeps within the
+        // domain of the key type
+        let offset = serial - 1;
+
+        match edm_type {
+            "Binary" => {
+                let len = Self::binary_len(prop);
+                let bytes: Vec<u8> = if is_key {
+                    let serial_bytes = serial.to_be_bytes();
+                    let serial_len = len.min(serial_bytes.len());
+                    let mut bytes = vec![0; len - serial_len];
+                    bytes.extend_from_slice(&serial_bytes[serial_bytes.len() - serial_len..]);
+                    bytes
+                } else {
+                    (0..len).map(|_| self.below(256) as u8).collect()
+                };
+
+                to_base64(&bytes)
+            },
+            "Boolean" if is_key => (offset == 1).to_string(),
+            "Boolean" => (self.below(2) == 1).to_string(),
+            "Byte" | "SByte" | "Int16" | "Int32" | "Int64" | "Double" | "Single" if is_key => serial.to_string(),
+            "Byte" => self.below(256).to_string(),
+            "SByte" => self.below(128).to_string(),
+            "Int16" => self.below(1_000).to_string(),
+            "Int32" => self.below(100_000).to_string(),
+            "Int64" => self.below(10_000_000).to_string(),
+            "Decimal" => self.gen_decimal(prop, is_key, serial),
+            "Double" | "Single" => format!("{}.{:02}", self.below(10_000), self.below(100)),
+            "DateTime" | "DateTimeOffset" if is_key => {
+                let (day, secs) = if Self::is_date_only(prop) {
+                    (FIRST_KEY_DAY + offset, 0)
+                } else {
+                    (FIRST_KEY_DAY + offset / SECONDS_PER_DAY, offset % SECONDS_PER_DAY)
+                };
+                let (year, month, day) = civil_from_days(day);
+
+                format!(
+                    "{year}-{month:02}-{day:02}T{:02}:{:02}:{:02}",
+                    secs / 3_600,
+                    secs / 60 % 60,
+                    secs % 60
+                )
+            },
+            "DateTime" | "DateTimeOffset" => {
+                // Offsets are omitted because the generated structs deserialize both types as a NaiveDateTime
+                let (hh, mm, ss) = if display_format == Some(&SAPDisplayFormatProperty::Date) {
+                    (0, 0, 0)
+                } else {
+                    (self.below(24), self.below(60), self.below(60))
+                };
+                format!(
+                    "{}-{:02}-{:02}T{hh:02}:{mm:02}:{ss:02}",
+                    2020 + self.below(6),
+                    1 + self.below(12),
+                    1 + self.below(28)
+                )
+            },
+            "Time" if is_key => format!("PT{:02}H{:02}M{:02}S", offset / 3_600, offset / 60 % 60, offset % 60),
+            "Time" => format!("PT{:02}H{:02}M{:02}S", self.below(24), self.below(60), self.below(60)),
+            "Guid" if is_key => format!("00000000-0000-4000-8000-{serial:012x}"),
+            "Guid" => {
+                let hex: String = (0..2).map(|_| format!("{:016x}", self.next_u64())).collect();
+                format!(
+                    "{}-{}-4{}-a{}-{}",
+                    &hex[0..8],
+                    &hex[8..12],
+                    &hex[13..16],
+                    &hex[17..20],
+                    &hex[20..32]
+                )
+            },
+            _ => {
+                let value = self.gen_string(prop, is_key, serial);
+
+                if display_format == Some(&SAPDisplayFormatProperty::UpperCase) {
+                    value.to_uppercase()
+                } else {
+                    value
+                }
+            },
+        }
+    }
+
+    fn gen_decimal(&mut self, prop: &Property, is_key: bool, serial: u64) -> String {
+        let precision = prop.precision.unwrap_or(DEFAULT_DECIMAL_PRECISION).max(1);
+        let scale = prop.scale.unwrap_or(0).min(precision);
+
+        // Key values are the position of the entity in its set, held in the fractional digits if there are no integer
+        // digits
+        if is_key {
+            let scale = scale as usize;
+
+            return match (precision as usize - scale, scale) {
+                (_, 0) => serial.to_string(),
+                (0, _) => format!("0.{serial:0scale$}"),
+                _ => format!("{serial}.{:0scale$}", 0),
+            };
+        }
+
+        let int_digits = (precision - scale).min(MAX_DECIMAL_INTEGER_DIGITS) as u32;
+        let int_part = self.below(10u64.pow(int_digits));
+
+        if scale == 0 {
+            int_part.to_string()
+        } else {
+            format!("{int_part}.{}", self.digits(scale as usize))
+        }
+    }
+
+    fn gen_string(&mut self, prop: &Property, is_key: bool, serial: u64) -> String {
+        let max_length = prop.max_length.map(|ml| ml as usize).filter(|ml| *ml > 0);
+
+        // Key values are the zero-padded position of the entity in its set
+        if is_key {
+            let width = Self::string_key_width(prop);
+            return format!("{serial:0width$}");
+        }
+
+        let value = match prop.sap_annotations.semantics.as_ref() {
+            Some(semantics) => self.gen_semantic_string(semantics, serial),
+            None => None,
+        }
+        .unwrap_or_else(|| format!("{} {serial}", prop.odata_name));
+
+        match max_length {
+            Some(ml) if value.chars().count() > ml => {
+                // A semantic value that would be truncated is still better than random characters
+                if prop.sap_annotations.semantics.is_some() {
+                    value.chars().take(ml).collect()
+                } else {
+                    self.alphanumeric(ml)
+                }
+            },
+            _ => value,
+        }
+    }
+
+    fn gen_semantic_string(&mut self, semantics: &SAPSemanticsProperty, serial: u64) -> Option<String> {
+        use SAPSemanticsProperty::*;
+
+        let value = match semantics {
+            TelephoneNumber | WorkCellphoneNumber | FaxNumber => format!("+49 6227 7{}", self.digits(5)),
+            EmailAddress
+            | PreferredEmailAddress
+            | EmailFrom
+            | EmailSender
+            | EmailToList
+            | EmailCCList
+            | EmailBCCList => format!(
+                "{}.{}@example.com",
+                self.pick(FIRST_NAMES).to_lowercase(),
+                self.pick(LAST_NAMES).to_lowercase()
+            ),
+            URL | PhotoURL => format!("https://www.example.com/{serial}"),
+            Fullname => format!("{} {}", self.pick(FIRST_NAMES), self.pick(LAST_NAMES)),
+            FirstOrGivenName | MiddleName | Nickname => self.pick(FIRST_NAMES).to_owned(),
+            LastName => self.pick(LAST_NAMES).to_owned(),
+            Title => self.pick(HONORIFICS).to_owned(),
+            City => self.pick(CITIES).to_owned(),
+            Street => format!("{} {}", self.pick(STREETS), 1 + self.below(200)),
+            Country => self.pick(COUNTRIES).to_owned(),
+            Region => self.pick(REGIONS).to_owned(),
+            PostalCode => self.digits(5),
+            PostOfficeBox => format!("PO Box {}", self.digits(4)),
+            OrganizationName => self.pick(COMPANIES).to_owned(),
+            OrganizationalUnit => self.pick(ORG_UNITS).to_owned(),
+            OrganizationalRole | JobTitle => self.pick(JOB_TITLES).to_owned(),
+            CurrencyCode => self.pick(CURRENCIES).to_owned(),
+            UnitOfMeasure => self.pick(UNITS).to_owned(),
+            _ => return None,
+        };
+
+        Some(value)
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// The (year, month, day) of a number of days since 1970-01-01 in the proleptic Gregorian calendar
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    // Count from 0000-03-01 so that leap days fall at the end of each year
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+
+    (era * 400 + year_of_era + u64::from(month <= 2), month, day)
+}
+
+fn to_base64(bytes: &[u8]) -> String {
+    bytes
+        .chunks(3)
+        .flat_map(|chunk| {
+            let triple = chunk
+                .iter()
+                .enumerate()
+                .fold(0u32, |acc, (idx, b)| acc | ((*b as u32) << (16 - 8 * idx)));
+
+            (0..4).map(move |idx| match idx <= chunk.len() {
+                true => char::from(BASE64[((triple >> (18 - 6 * idx)) & 0x3F) as usize]),
+                false => '=',
+            })
+        })
+        .collect()
+}
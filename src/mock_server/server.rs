@@ -0,0 +1,136 @@
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::Duration,
+};
+
+use super::{MockResponse, MockService};
+
+static POLL_INTERVAL: Duration = Duration::from_millis(10);
+static READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Serves a `MockService` over HTTP on a local address.
+///
+/// The server runs on a background thread that stops when the `MockServer` is dropped.
+///
+/// ```rust,ignore
+/// let metadata = std::fs::read_to_string("./odata/gwsample_basic.xml")?;
+/// let mut service = MockService::new(&metadata, "/sap/opu/odata/iwbep/GWSAMPLE_BASIC/")?;
+/// service.load_feed("ProductSet", &std::fs::read_to_string("./fixtures/product_set.xml")?)?;
+///
+/// // Port 0 lets the operating system choose a free port
+/// let server = MockServer::start(service, "127.0.0.1:0")?;
+/// let url = format!("{}ProductSet?$top=2", server.base_url());
+/// ```
+pub struct MockServer {
+    address: SocketAddr,
+    base_url: String,
+    stop: Arc<AtomicBool>,
+    worker: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    pub fn start(mut service: MockService, address: &str) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        let address = listener.local_addr()?;
+        listener.set_nonblocking(true)?;
+
+        service.base_url = format!("http://{address}{}", service.service_root);
+
+        let base_url = service.base_url.clone();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_requested = stop.clone();
+
+        let worker = thread::spawn(move || {
+            while !stop_requested.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    // A failed connection must not stop the server
+                    Ok((stream, _)) => {
+                        let _ = handle_connection(&service, stream);
+                    },
+                    Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => thread::sleep(POLL_INTERVAL),
+                    Err(_) => break,
+                }
+            }
+        });
+
+        Ok(MockServer {
+            address,
+            base_url,
+            stop,
+            worker: Some(worker),
+        })
+    }
+
+    pub fn address(&self) -> SocketAddr {
+        self.address
+    }
+
+    /// The URL of the service document, E.G. `http://127.0.0.1:50123/sap/opu/odata/iwbep/GWSAMPLE_BASIC/`
+    pub fn base_url(&self) -> &str {
+        &self.base_url
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+
+        if let Some(worker) = self.worker.take() {
+            let _ = worker.join();
+        }
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        501 => "Not Implemented",
+        _ => "Internal Server Error",
+    }
+}
+
+fn handle_connection(service: &MockService, mut stream: TcpStream) -> std::io::Result<()> {
+    stream.set_nonblocking(false)?;
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+
+    // Skip the request headers. Request bodies are not supported
+    loop {
+        let mut header = String::new();
+
+        if reader.read_line(&mut header)? == 0 || header.trim().is_empty() {
+            break;
+        }
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let response = match (parts.next(), parts.next()) {
+        (Some(method), Some(target)) => service.handle(method, target),
+        _ => MockResponse::error(400, "Malformed request line"),
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nDataServiceVersion: 2.0\r\nConnection: close\r\n\r\n",
+        response.status,
+        reason_phrase(response.status),
+        response.content_type,
+        response.body.len()
+    )?;
+    stream.write_all(response.body.as_bytes())?;
+    stream.flush()
+}
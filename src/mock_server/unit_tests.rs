@@ -0,0 +1,402 @@
+use std::{
+    collections::BTreeSet,
+    fs::File,
+    io::{BufReader, Read, Write},
+    net::TcpStream,
+    path::Path,
+};
+
+use parse_sap_atom_feed::atom::feed::Feed;
+
+use super::*;
+use crate::{
+    feed_entries::unit_tests::generated,
+    query::filter::{FilterBuilder, FilterField},
+    sap_annotations::filter_restriction::SAPFilterRestrictionProperty,
+    test_utils::*,
+};
+
+static PATH_TO_MOCK_SERVICE: &str = "./test_data/mock_service.xml";
+static PATH_TO_MOCK_FEED_PRODUCTS: &str = "./test_data/mock_feed_products.xml";
+static SERVICE_ROOT: &str = "/sap/opu/odata/iwbep/GWSAMPLE_BASIC/";
+
+fn read_test_data(path: &str) -> String {
+    let mut xml = String::new();
+    let test_data = File::open(Path::new(path)).unwrap();
+    let _file_size = BufReader::new(test_data).read_to_string(&mut xml);
+    xml
+}
+
+fn business_partner(id: &str, name: &str, city: &str) -> MockEntity {
+    MockEntity::from([
+        ("BusinessPartnerID".to_owned(), Some(id.to_owned())),
+        ("CompanyName".to_owned(), Some(name.to_owned())),
+        ("Address/City".to_owned(), Some(city.to_owned())),
+        ("Address/Country".to_owned(), None),
+    ])
+}
+
+fn fetch_mock_service() -> Result<MockService, String> {
+    let mut service =
+        MockService::new(&read_test_data(PATH_TO_MOCK_SERVICE), SERVICE_ROOT).map_err(|err| err.to_string())?;
+
+    service
+        .load_feed("ProductSet", &read_test_data(PATH_TO_MOCK_FEED_PRODUCTS))
+        .map_err(|err| err.to_string())?;
+    service
+        .add_entities(
+            "BusinessPartnerSet",
+            vec![
+                business_partner("0100000000", "SAP", "Walldorf"),
+                business_partner("0100000001", "Becker & Berlin", "Berlin"),
+            ],
+        )
+        .map_err(|err| err.to_string())?;
+
+    Ok(service)
+}
+
+fn get(service: &MockService, rel_path: &str) -> MockResponse {
+    service.handle("GET", &format!("{SERVICE_ROOT}{rel_path}"))
+}
+
+// Hand-written equivalent of a generated filter field enum
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ProductFilterField {
+    ProductID,
+    SupplierID,
+    Price,
+}
+
+impl FilterField for ProductFilterField {
+    fn odata_name(&self) -> &'static str {
+        match *self {
+            Self::ProductID => "ProductID",
+            Self::SupplierID => "SupplierID",
+            Self::Price => "Price",
+        }
+    }
+    fn is_required_in_filter(&self) -> bool {
+        false
+    }
+    fn filter_restriction(&self) -> Option<SAPFilterRestrictionProperty> {
+        None
+    }
+    fn required_fields() -> &'static [Self] {
+        &[]
+    }
+}
+
+fn count_filtered(service: &MockService, filter: &FilterBuilder<ProductFilterField>) -> Result<MockResponse, String> {
+    let expression = filter.build().map_err(|err| err.to_string())?;
+    Ok(get(service, &format!("ProductSet/$count?$filter={expression}")))
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_load_atom_feed_fixture() -> Result<(), String> {
+    let entities = atom::parse_feed_entities(&read_test_data(PATH_TO_MOCK_FEED_PRODUCTS))?;
+
+    handle_test_comparison(&entities.len(), &3)?;
+    handle_test_comparison_opt(&entities[0]["Name"], &Some("Notebook Basic 15".to_owned()))?;
+    handle_test_comparison_opt(&entities[1]["ChangedAt"], &None)
+}
+
+#[test]
+pub fn should_serve_service_document_and_metadata() -> Result<(), String> {
+    let service = fetch_mock_service()?;
+    let svc_doc = service.handle("GET", SERVICE_ROOT.trim_end_matches('/'));
+    let metadata = get(&service, "$metadata");
+
+    handle_test_comparison(&svc_doc.content_type, &"application/atomsvc+xml")?;
+    handle_test_bool(svc_doc.body.contains("<app:collection href=\"ProductSet\">"))?;
+    handle_test_comparison(&metadata.body, &read_test_data(PATH_TO_MOCK_SERVICE))
+}
+
+#[test]
+pub fn should_serve_entity_set_with_query_options() -> Result<(), String> {
+    let service = fetch_mock_service()?;
+    let response = get(
+        &service,
+        "ProductSet?$filter=Price%20gt%201000M&$skip=1&$top=5&$inlinecount=allpages",
+    );
+
+    handle_test_comparison(&response.status, &200)?;
+    handle_test_bool(response.body.contains("<m:count>2</m:count>"))?;
+    handle_test_bool(!response.body.contains("<d:ProductID>HT-1001</d:ProductID>"))?;
+    handle_test_bool(response.body.contains("<d:ProductID>HT-1002</d:ProductID>"))?;
+    handle_test_bool(response.body.contains("<d:ChangedAt m:type=\"Edm.DateTime\" m:null=\"true\"/>"))?;
+    handle_test_comparison(
+        &get(&service, "ProductSet/$count?$filter=SupplierID eq '0100000000'").body,
+        &"2".to_owned(),
+    )
+}
+
+#[test]
+pub fn should_serve_single_entity_by_key() -> Result<(), String> {
+    let service = fetch_mock_service()?;
+    let response = get(&service, "BusinessPartnerSet('0100000001')");
+
+    handle_test_comparison(&response.content_type, &"application/atom+xml;type=entry")?;
+    handle_test_bool(response.body.contains("<d:CompanyName>Becker &amp; Berlin</d:CompanyName>"))?;
+    handle_test_bool(response.body.contains(
+        "<d:Address m:type=\"GWSAMPLE_BASIC.CT_Address\"><d:City>Berlin</d:City><d:PostalCode m:null=\"true\"/><d:Country m:null=\"true\"/></d:Address>",
+    ))?;
+    handle_test_comparison(&get(&service, "ProductSet(ProductID='HT-9999')").status, &404)
+}
+
+#[test]
+pub fn should_navigate_via_associations() -> Result<(), String> {
+    let service = fetch_mock_service()?;
+    let products = get(&service, "BusinessPartnerSet('0100000000')/ToProducts");
+    let supplier = get(&service, "ProductSet('HT-1002')/ToSupplier");
+
+    handle_test_comparison(&products.content_type, &"application/atom+xml;type=feed")?;
+    handle_test_bool(products.body.contains("<d:ProductID>HT-1000</d:ProductID>"))?;
+    handle_test_bool(!products.body.contains("<d:ProductID>HT-1002</d:ProductID>"))?;
+    handle_test_comparison(&supplier.content_type, &"application/atom+xml;type=entry")?;
+    handle_test_bool(supplier.body.contains("<d:BusinessPartnerID>0100000001</d:BusinessPartnerID>"))
+}
+
+#[test]
+pub fn should_filter_using_filter_builder_expressions() -> Result<(), String> {
+    let service = fetch_mock_service()?;
+    let between = FilterBuilder::new().eq(ProductFilterField::SupplierID, "'0100000000'").between(
+        ProductFilterField::Price,
+        "1000M",
+        "1300M",
+    );
+    let alternatives = FilterBuilder::new()
+        .eq(ProductFilterField::ProductID, "'HT-1000'")
+        .eq(ProductFilterField::ProductID, "'HT-1002'");
+    let exclusion = FilterBuilder::new()
+        .ge(ProductFilterField::Price, "900M")
+        .ne(ProductFilterField::ProductID, "'HT-1000'");
+
+    handle_test_comparison(&count_filtered(&service, &between)?.body, &"1".to_owned())?;
+    handle_test_comparison(&count_filtered(&service, &alternatives)?.body, &"2".to_owned())?;
+    handle_test_comparison(&count_filtered(&service, &exclusion)?.body, &"2".to_owned())?;
+    let grouped = get(
+        &service,
+        "ProductSet/$count?$filter=(SupplierID eq '0100000000') and (Price ge 1000M)",
+    );
+
+    handle_test_comparison(&grouped.body, &"1".to_owned())
+}
+
+#[test]
+pub fn should_compare_string_values_as_text() -> Result<(), String> {
+    let service = fetch_mock_service()?;
+
+    // '100000000' would equal '0100000000' if the values were compared as numbers
+    handle_test_comparison(&get(&service, "BusinessPartnerSet('100000000')").status, &404)?;
+    handle_test_comparison(&get(&service, "BusinessPartnerSet('0100000000')").status, &200)?;
+    handle_test_comparison(
+        &get(&service, "BusinessPartnerSet/$count?$filter=BusinessPartnerID eq '100000000'").body,
+        &"0".to_owned(),
+    )?;
+    // As text, '0100000001' sorts before '2'
+    handle_test_comparison(
+        &get(&service, "BusinessPartnerSet/$count?$filter=BusinessPartnerID lt '2'").body,
+        &"2".to_owned(),
+    )
+}
+
+#[test]
+pub fn should_reject_invalid_filter_terms() -> Result<(), String> {
+    let service = fetch_mock_service()?;
+
+    for filter in [
+        "Category eq 'Notebooks'",
+        "Price ge 'cheap'",
+        "ProductID eq HT-1000",
+        "(Price ge 1000M",
+        "Price ge 1000M or",
+    ] {
+        handle_test_comparison(&get(&service, &format!("ProductSet?$filter={filter}")).status, &400)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn should_reject_unsupported_requests() -> Result<(), String> {
+    let service = fetch_mock_service()?;
+
+    handle_test_comparison(
+        &service.handle("DELETE", &format!("{SERVICE_ROOT}ProductSet('HT-1000')")).status,
+        &405,
+    )?;
+    handle_test_comparison(&get(&service, "ProductSet?$filter=substringof('Basic',Name)").status, &400)?;
+    handle_test_comparison(&get(&service, "NoSuchSet").status, &404)
+}
+
+#[test]
+pub fn should_generate_sample_feed_within_constraints() -> Result<(), String> {
+    let service = fetch_mock_service()?;
+    let feed = service
+        .gen_sample_feed("ProductSet", 20, &mut SampleDataGenerator::new(Some(42), 0))
+        .map_err(|err| err.to_string())?;
+    let entities = atom::parse_feed_entities(&feed)?;
+    let product_ids: BTreeSet<String> = entities.iter().filter_map(|e| e["ProductID"].clone()).collect();
+
+    handle_test_comparison(&entities.len(), &20)?;
+    handle_test_comparison(&product_ids.len(), &20)?;
+    handle_test_bool(product_ids.iter().all(|id| id.len() <= 10))?;
+    handle_test_bool(entities.iter().all(|e| {
+        e["Price"]
+            .as_deref()
+            .and_then(|price| price.split_once('.'))
+            .map(|(_, fraction)| fraction.len() == 3)
+            .unwrap_or(false)
+    }))?;
+    handle_test_bool(entities.iter().all(|e| {
+        ["EUR", "USD", "GBP", "JPY", "CHF"].contains(&e["CurrencyCode"].as_deref().unwrap_or_default())
+    }))?;
+    handle_test_bool(entities.iter().all(|e| e["ChangedAt"].is_some()))
+}
+
+#[test]
+pub fn should_parse_sample_feed_into_generated_struct() -> Result<(), String> {
+    let service = fetch_mock_service()?;
+    let xml = service
+        .gen_sample_feed("ProductSet", 20, &mut SampleDataGenerator::new(Some(42), 0))
+        .map_err(|err| err.to_string())?;
+    let feed = xml.parse::<Feed<generated::Product>>().map_err(|err| err.to_string())?;
+    let products: Vec<generated::Product> = feed
+        .entries
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|entry| entry.content.properties)
+        .collect();
+    let product_ids: Vec<String> = atom::parse_feed_entities(&xml)?
+        .iter()
+        .filter_map(|e| e["ProductID"].clone())
+        .collect();
+
+    handle_test_comparison(&products.len(), &20)?;
+    handle_test_bool(products.iter().all(|p| p.price.is_some() && p.changed_at.is_some()))?;
+    handle_test_bool(products.iter().map(|p| &p.product_id).eq(product_ids.iter()))
+}
+
+#[test]
+pub fn should_generate_unique_keys_for_narrow_key_types() -> Result<(), String> {
+    let service = fetch_mock_service()?;
+    let mut generator = SampleDataGenerator::new(Some(3), 0);
+
+    for (key_type, facets, max) in [
+        ("Edm.Boolean", "", 2),
+        ("Edm.Binary", r#"MaxLength="1""#, 255),
+        ("Edm.Byte", "", 255),
+        ("Edm.SByte", "", 127),
+        ("Edm.String", r#"MaxLength="1""#, 9),
+        ("Edm.Decimal", r#"Precision="2" Scale="2""#, 99),
+        ("Edm.Decimal", r#"Precision="3" Scale="1""#, 99),
+    ] {
+        let et: EntityType = quick_xml::de::from_str(&format!(
+            r#"<EntityType Name="Narrow"><Key><PropertyRef Name="ID"/></Key><Property Name="ID" Type="{key_type}" Nullable="false" {facets}/></EntityType>"#
+        ))
+        .map_err(|err| err.to_string())?;
+        let keys: BTreeSet<String> = generator
+            .gen_entities(service.schema(), &et, max)
+            .map_err(|err| err.to_string())?
+            .iter()
+            .filter_map(|e| e["ID"].clone())
+            .collect();
+
+        handle_test_comparison(&keys.len(), &max)?;
+        handle_test_bool(matches!(
+            generator.gen_entities(service.schema(), &et, max + 1),
+            Err(MockServerError::TooManySampleEntities { max: limit, .. }) if limit == max as u64
+        ))?;
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn should_generate_unique_keys_for_wide_key_types() -> Result<(), String> {
+    let service = fetch_mock_service()?;
+    let mut generator = SampleDataGenerator::new(Some(3), 0);
+    let count = 100;
+
+    for (key_type, facets) in [
+        ("Edm.Binary", ""),
+        ("Edm.DateTime", ""),
+        ("Edm.DateTime", r#"sap:display-format="Date""#),
+        ("Edm.DateTimeOffset", ""),
+        ("Edm.Double", ""),
+        ("Edm.Guid", ""),
+        ("Edm.Single", ""),
+        ("Edm.Time", ""),
+    ] {
+        let et: EntityType = quick_xml::de::from_str(&format!(
+            r#"<EntityType Name="Wide"><Key><PropertyRef Name="ID"/></Key><Property Name="ID" Type="{key_type}" Nullable="false" {facets}/></EntityType>"#
+        ))
+        .map_err(|err| err.to_string())?;
+        let keys: BTreeSet<String> = generator
+            .gen_entities(service.schema(), &et, count)
+            .map_err(|err| err.to_string())?
+            .iter()
+            .filter_map(|e| e["ID"].clone())
+            .collect();
+
+        handle_test_comparison(&keys.len(), &count)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn should_generate_identical_sample_data_from_the_same_seed() -> Result<(), String> {
+    let service = fetch_mock_service()?;
+    let gen_feed = |seed: u64| {
+        service
+            .gen_sample_feed("BusinessPartnerSet", 5, &mut SampleDataGenerator::new(Some(seed), 25))
+            .map_err(|err| err.to_string())
+    };
+    let feed = gen_feed(7)?;
+    let entities = atom::parse_feed_entities(&feed)?;
+
+    handle_test_comparison(&feed, &gen_feed(7)?)?;
+    handle_test_bool(feed.ne(&gen_feed(8)?))?;
+    handle_test_bool(entities.iter().all(|e| e["CompanyName"].is_some()))?;
+    handle_test_bool(entities.iter().all(|e| {
+        e["EmailAddress"].as_ref().map(|email| email.contains('@')).unwrap_or(true)
+            && e["Address/PostalCode"].as_ref().map(|zip| zip.len() == 5).unwrap_or(true)
+    }))
+}
+
+#[test]
+pub fn should_add_sample_entities_that_refer_to_existing_entities() -> Result<(), String> {
+    let mut service = fetch_mock_service()?;
+    service
+        .add_sample_entities("ProductSet", 4, &mut SampleDataGenerator::new(Some(1), 0))
+        .map_err(|err| err.to_string())?;
+
+    handle_test_bool(get(&service, "ProductSet/$count").body.eq("7"))?;
+    handle_test_comparison(
+        &get(&service, "ProductSet/$count?$filter=SupplierID eq '0100000001'").body,
+        &"3".to_owned(),
+    )?;
+    handle_test_bool(get(&service, "ProductSet('0000000002')/ToSupplier").body.contains("0100000001"))
+}
+
+#[test]
+pub fn should_serve_over_http() -> Result<(), String> {
+    let server = MockServer::start(fetch_mock_service()?, "127.0.0.1:0").map_err(|err| err.to_string())?;
+    let mut stream = TcpStream::connect(server.address()).map_err(|err| err.to_string())?;
+
+    write!(
+        stream,
+        "GET {SERVICE_ROOT}ProductSet('HT-1000') HTTP/1.1\r\nHost: localhost\r\n\r\n"
+    )
+    .map_err(|err| err.to_string())?;
+
+    let mut response = String::new();
+    stream.read_to_string(&mut response).map_err(|err| err.to_string())?;
+
+    handle_test_bool(response.starts_with("HTTP/1.1 200 OK\r\n"))?;
+    handle_test_bool(response.contains(&format!("xml:base=\"{}\"", server.base_url())))?;
+    handle_test_bool(response.contains("<d:Price m:type=\"Edm.Decimal\">956.000</d:Price>"))
+}
@@ -0,0 +1,228 @@
+use crate::{
+    amounts::{UnitKind, UnitPairing},
+    edmx::data_services::schema::{entity_container::entity_set::EntitySet, entity_type::EntityType},
+    property::Property,
+    sap_annotations::{display_format::SAPDisplayFormatProperty, field_control::SAPFieldControlProperty},
+    sap_semantics::property::SAPSemanticsProperty,
+};
+
+static VALUE_LIST_FIXED_VALUES: &str = "fixed-values";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// The value of a V4 vocabulary annotation
+#[derive(Clone, Debug, PartialEq)]
+pub enum AnnotationValue {
+    Bool(bool),
+    String(String),
+    Path(String),
+    EnumMember(&'static str, &'static str),
+    PropertyPaths(Vec<String>),
+    Record(Vec<(&'static str, AnnotationValue)>),
+}
+
+/// A V4 vocabulary annotation whose term is qualified by the vocabulary's alias, E.G. `Common.Label`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Annotation {
+    pub term: &'static str,
+    pub value: AnnotationValue,
+}
+
+impl Annotation {
+    fn new(term: &'static str, value: AnnotationValue) -> Annotation {
+        Annotation { term, value }
+    }
+
+    fn tag(term: &'static str) -> Annotation {
+        Annotation::new(term, AnnotationValue::Bool(true))
+    }
+
+    fn record(term: &'static str, name: &'static str, value: bool) -> Annotation {
+        Annotation::new(term, AnnotationValue::Record(vec![(name, AnnotationValue::Bool(value))]))
+    }
+
+    /// The alias of the vocabulary to which this annotation's term belongs
+    pub fn vocabulary_alias(&self) -> &'static str {
+        self.term.split('.').next().unwrap_or_default()
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Map the SAP annotations of a property to their V4 vocabulary equivalents
+///
+/// See https://sap.github.io/odata-vocabularies/docs/v2-annotations.html#element-edmproperty
+pub fn property_annotations(prop: &Property, unit_pairings: &[UnitPairing]) -> Vec<Annotation> {
+    let sap_annos = &prop.sap_annotations;
+    let mut annotations = vec![];
+
+    if let Some(label) = &sap_annos.label {
+        annotations.push(Annotation::new("Common.Label", AnnotationValue::String(label.clone())));
+    }
+
+    if let Some(heading) = &sap_annos.heading {
+        annotations.push(Annotation::new("Common.Heading", AnnotationValue::String(heading.clone())));
+    }
+
+    if let Some(quick_info) = &sap_annos.quick_info {
+        annotations.push(Annotation::new("Common.QuickInfo", AnnotationValue::String(quick_info.clone())));
+    }
+
+    if let Some(text) = &sap_annos.text {
+        annotations.push(Annotation::new("Common.Text", AnnotationValue::Path(text.clone())));
+    }
+
+    if let Some(pairing) = unit_pairings
+        .iter()
+        .find(|pairing| pairing.amount_property.eq(&prop.odata_name))
+    {
+        let term = match pairing.kind {
+            UnitKind::Currency => "Measures.ISOCurrency",
+            _ => "Measures.Unit",
+        };
+        annotations.push(Annotation::new(term, AnnotationValue::Path(pairing.unit_property.clone())));
+    }
+
+    match sap_annos.semantics {
+        Some(SAPSemanticsProperty::CurrencyCode) => annotations.push(Annotation::tag("Common.IsCurrency")),
+        Some(SAPSemanticsProperty::UnitOfMeasure) => annotations.push(Annotation::tag("Common.IsUnit")),
+        _ => {},
+    }
+
+    match (sap_annos.is_creatable, sap_annos.is_updatable) {
+        (false, false) => annotations.push(Annotation::tag("Core.Computed")),
+        (true, false) => annotations.push(Annotation::tag("Core.Immutable")),
+        _ => {},
+    }
+
+    match sap_annos.display_format {
+        Some(SAPDisplayFormatProperty::NonNegative) => annotations.push(Annotation::tag("Common.IsDigitSequence")),
+        Some(SAPDisplayFormatProperty::UpperCase) => annotations.push(Annotation::tag("Common.IsUpperCase")),
+        // Date is expressed by converting the property's type to Edm.Date
+        _ => {},
+    }
+
+    if let Some(field_control) = &sap_annos.field_control {
+        let member = match field_control {
+            SAPFieldControlProperty::Hidden => "Hidden",
+            SAPFieldControlProperty::ReadOnly => "ReadOnly",
+            SAPFieldControlProperty::Optional => "Optional",
+            SAPFieldControlProperty::Mandatory => "Mandatory",
+        };
+        annotations.push(Annotation::new(
+            "Common.FieldControl",
+            AnnotationValue::EnumMember("Common.FieldControlType", member),
+        ));
+    }
+
+    if !sap_annos.is_visible {
+        annotations.push(Annotation::tag("UI.Hidden"));
+    }
+
+    if sap_annos.value_list.as_deref() == Some(VALUE_LIST_FIXED_VALUES) {
+        annotations.push(Annotation::tag("Common.ValueListWithFixedValues"));
+    }
+
+    annotations
+}
+
+// Names of the properties for which the predicate is true
+fn property_names(et: &EntityType, predicate: fn(&Property) -> bool) -> Vec<String> {
+    et.properties
+        .iter()
+        .filter(|prop| predicate(prop))
+        .map(|prop| prop.odata_name.clone())
+        .collect()
+}
+
+/// Map the SAP annotations of an entity set (together with the `sap:filterable`, `sap:required-in-filter` and
+/// `sap:sortable` annotations of its entity type's properties) to V4 capabilities
+///
+/// See https://sap.github.io/odata-vocabularies/docs/v2-annotations.html#element-edmentityset
+pub fn entity_set_annotations(es: &EntitySet, et: Option<&EntityType>) -> Vec<Annotation> {
+    let sap_annos = &es.sap_annotations;
+    let mut annotations = vec![];
+
+    if let Some(label) = &sap_annos.label {
+        annotations.push(Annotation::new("Common.Label", AnnotationValue::String(label.clone())));
+    }
+
+    if !sap_annos.is_creatable {
+        annotations.push(Annotation::record("Capabilities.InsertRestrictions", "Insertable", false));
+    }
+
+    if !sap_annos.is_updatable {
+        annotations.push(Annotation::record("Capabilities.UpdateRestrictions", "Updatable", false));
+    }
+
+    if !sap_annos.is_deletable {
+        annotations.push(Annotation::record("Capabilities.DeleteRestrictions", "Deletable", false));
+    }
+
+    // Entity sets are searchable by default in V4, but not in V2
+    if !sap_annos.is_searchable {
+        annotations.push(Annotation::record("Capabilities.SearchRestrictions", "Searchable", false));
+    }
+
+    if !sap_annos.is_pageable {
+        annotations.push(Annotation::new("Capabilities.SkipSupported", AnnotationValue::Bool(false)));
+    }
+
+    if !sap_annos.is_pageable || !sap_annos.is_topable {
+        annotations.push(Annotation::new("Capabilities.TopSupported", AnnotationValue::Bool(false)));
+    }
+
+    if !sap_annos.is_countable {
+        annotations.push(Annotation::record("Capabilities.CountRestrictions", "Countable", false));
+    }
+
+    if !sap_annos.is_addressable {
+        annotations.push(Annotation::new("Capabilities.IndexableByKey", AnnotationValue::Bool(false)));
+    }
+
+    if sap_annos.change_tracking_enabled {
+        annotations.push(Annotation::record("Capabilities.ChangeTracking", "Supported", true));
+    }
+
+    let required_in_filter = et.map_or(vec![], |et| {
+        property_names(et, |prop| prop.sap_annotations.is_required_in_filter)
+    });
+    let non_filterable = et.map_or(vec![], |et| property_names(et, |prop| !prop.sap_annotations.is_filterable));
+    let non_sortable = et.map_or(vec![], |et| property_names(et, |prop| !prop.sap_annotations.is_sortable));
+
+    let mut filter_restrictions = vec![];
+
+    if sap_annos.requires_filter {
+        filter_restrictions.push(("RequiresFilter", AnnotationValue::Bool(true)));
+    }
+
+    if !required_in_filter.is_empty() {
+        filter_restrictions.push(("RequiredProperties", AnnotationValue::PropertyPaths(required_in_filter)));
+    }
+
+    if !non_filterable.is_empty() {
+        filter_restrictions.push(("NonFilterableProperties", AnnotationValue::PropertyPaths(non_filterable)));
+    }
+
+    if !filter_restrictions.is_empty() {
+        annotations.push(Annotation::new(
+            "Capabilities.FilterRestrictions",
+            AnnotationValue::Record(filter_restrictions),
+        ));
+    }
+
+    if !non_sortable.is_empty() {
+        annotations.push(Annotation::new(
+            "Capabilities.SortRestrictions",
+            AnnotationValue::Record(vec![("NonSortableProperties", AnnotationValue::PropertyPaths(non_sortable))]),
+        ));
+    }
+
+    annotations
+}
+
+/// Map the `sap:label` annotation of an entity type or function import to `Common.Label`
+pub fn label_annotations(label: Option<&String>) -> Vec<Annotation> {
+    label
+        .iter()
+        .map(|label| Annotation::new("Common.Label", AnnotationValue::String(label.to_string())))
+        .collect()
+}
@@ -0,0 +1,235 @@
+use serde_json::{json, Map, Value};
+
+use super::{
+    annotations::{Annotation, AnnotationValue},
+    CsdlNavigationProperty, CsdlOperation, CsdlProperty, CsdlSchema, CsdlStructuredType,
+};
+
+static DEFAULT_TYPE: &str = "Edm.String";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn annotation_value(value: &AnnotationValue) -> Value {
+    match value {
+        AnnotationValue::Bool(b) => json!(b),
+        AnnotationValue::String(s) => json!(s),
+        AnnotationValue::Path(path) => json!({ "$Path": path }),
+        AnnotationValue::EnumMember(_, member) => json!(member),
+        AnnotationValue::PropertyPaths(paths) => paths.iter().map(|path| json!({ "$PropertyPath": path })).collect(),
+        AnnotationValue::Record(members) => Value::Object(
+            members
+                .iter()
+                .map(|(name, value)| (name.to_string(), annotation_value(value)))
+                .collect(),
+        ),
+    }
+}
+
+fn insert_annotations(obj: &mut Map<String, Value>, annotations: &[Annotation]) {
+    for annotation in annotations {
+        obj.insert(format!("@{}", annotation.term), annotation_value(&annotation.value));
+    }
+}
+
+// Members whose value is the default for the CSDL JSON format are omitted
+fn insert_type(obj: &mut Map<String, Value>, edm_type: &str, nullable: bool, max_length: Option<u16>) {
+    if edm_type.ne(DEFAULT_TYPE) {
+        obj.insert("$Type".to_owned(), json!(edm_type));
+    }
+
+    if nullable {
+        obj.insert("$Nullable".to_owned(), json!(true));
+    }
+
+    if let Some(max_length) = max_length {
+        obj.insert("$MaxLength".to_owned(), json!(max_length));
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn property(prop: &CsdlProperty) -> Value {
+    let mut obj = Map::new();
+    insert_type(&mut obj, &prop.edm_type, prop.nullable, prop.max_length);
+
+    if let Some(precision) = prop.precision {
+        obj.insert("$Precision".to_owned(), json!(precision));
+    }
+
+    if let Some(scale) = &prop.scale {
+        let scale_value = scale.parse::<u16>().map_or(json!(scale), |scale| json!(scale));
+        obj.insert("$Scale".to_owned(), scale_value);
+    }
+
+    insert_annotations(&mut obj, &prop.annotations);
+    Value::Object(obj)
+}
+
+fn navigation(nav: &CsdlNavigationProperty) -> Value {
+    let mut obj = Map::new();
+    obj.insert("$Kind".to_owned(), json!("NavigationProperty"));
+    obj.insert("$Type".to_owned(), json!(nav.target_type));
+
+    if nav.is_collection {
+        obj.insert("$Collection".to_owned(), json!(true));
+    } else if nav.nullable {
+        obj.insert("$Nullable".to_owned(), json!(true));
+    }
+
+    if let Some(partner) = &nav.partner {
+        obj.insert("$Partner".to_owned(), json!(partner));
+    }
+
+    if !nav.referential_constraints.is_empty() {
+        obj.insert(
+            "$ReferentialConstraint".to_owned(),
+            Value::Object(
+                nav.referential_constraints
+                    .iter()
+                    .map(|(property, referenced_property)| (property.clone(), json!(referenced_property)))
+                    .collect(),
+            ),
+        );
+    }
+
+    Value::Object(obj)
+}
+
+fn structured_type(st: &CsdlStructuredType) -> Value {
+    let mut obj = Map::new();
+
+    match &st.keys {
+        Some(keys) => {
+            obj.insert("$Kind".to_owned(), json!("EntityType"));
+            obj.insert("$Key".to_owned(), json!(keys));
+        },
+        None => {
+            obj.insert("$Kind".to_owned(), json!("ComplexType"));
+        },
+    }
+
+    if st.has_stream {
+        obj.insert("$HasStream".to_owned(), json!(true));
+    }
+
+    for prop in &st.properties {
+        obj.insert(prop.name.clone(), property(prop));
+    }
+
+    for nav in &st.navigations {
+        obj.insert(nav.name.clone(), navigation(nav));
+    }
+
+    insert_annotations(&mut obj, &st.annotations);
+    Value::Object(obj)
+}
+
+// Operations are overloadable, so each one is represented as an array of overloads
+fn operation(op: &CsdlOperation) -> Value {
+    let mut obj = Map::new();
+    obj.insert("$Kind".to_owned(), json!(if op.is_action { "Action" } else { "Function" }));
+
+    if !op.parameters.is_empty() {
+        let parameters: Vec<Value> = op
+            .parameters
+            .iter()
+            .map(|param| {
+                let mut param_obj = Map::new();
+                param_obj.insert("$Name".to_owned(), json!(param.name));
+                insert_type(&mut param_obj, &param.edm_type, param.nullable, param.max_length);
+                Value::Object(param_obj)
+            })
+            .collect();
+        obj.insert("$Parameter".to_owned(), json!(parameters));
+    }
+
+    if let Some((return_type, is_collection)) = &op.return_type {
+        let mut return_obj = Map::new();
+
+        if *is_collection {
+            return_obj.insert("$Collection".to_owned(), json!(true));
+        }
+
+        if return_type.ne(DEFAULT_TYPE) {
+            return_obj.insert("$Type".to_owned(), json!(return_type));
+        }
+
+        obj.insert("$ReturnType".to_owned(), Value::Object(return_obj));
+    }
+
+    json!([obj])
+}
+
+fn entity_container(schema: &CsdlSchema) -> Option<(String, Value)> {
+    let ec = schema.entity_container.as_ref()?;
+    let mut obj = Map::new();
+    obj.insert("$Kind".to_owned(), json!("EntityContainer"));
+
+    for es in &ec.entity_sets {
+        let mut es_obj = Map::new();
+        es_obj.insert("$Collection".to_owned(), json!(true));
+        es_obj.insert("$Type".to_owned(), json!(es.entity_type));
+
+        if !es.bindings.is_empty() {
+            es_obj.insert(
+                "$NavigationPropertyBinding".to_owned(),
+                Value::Object(es.bindings.iter().map(|(path, target)| (path.clone(), json!(target))).collect()),
+            );
+        }
+
+        insert_annotations(&mut es_obj, &es.annotations);
+        obj.insert(es.name.clone(), Value::Object(es_obj));
+    }
+
+    for op in &schema.operations {
+        let mut import_obj = Map::new();
+        let op_member = if op.is_action { "$Action" } else { "$Function" };
+        import_obj.insert(op_member.to_owned(), json!(format!("{}.{}", schema.namespace, op.name)));
+
+        if let Some(entity_set) = &op.entity_set {
+            import_obj.insert("$EntitySet".to_owned(), json!(entity_set));
+        }
+
+        insert_annotations(&mut import_obj, &op.annotations);
+        obj.insert(op.name.clone(), Value::Object(import_obj));
+    }
+
+    Some((ec.name.clone(), Value::Object(obj)))
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+pub fn render(schema: &CsdlSchema) -> Value {
+    let mut schema_obj = Map::new();
+
+    for st in schema.entity_types.iter().chain(schema.complex_types.iter()) {
+        schema_obj.insert(st.name.clone(), structured_type(st));
+    }
+
+    for op in &schema.operations {
+        schema_obj.insert(op.name.clone(), operation(op));
+    }
+
+    let mut document = Map::new();
+    document.insert("$Version".to_owned(), json!("4.0"));
+
+    if let Some((ec_name, ec)) = entity_container(schema) {
+        document.insert("$EntityContainer".to_owned(), json!(format!("{}.{ec_name}", schema.namespace)));
+        schema_obj.insert(ec_name, ec);
+    }
+
+    let references: Map<String, Value> = schema
+        .vocabularies()
+        .into_iter()
+        .map(|(alias, namespace, uri)| {
+            (
+                uri.to_owned(),
+                json!({ "$Include": [{ "$Namespace": namespace, "$Alias": alias }] }),
+            )
+        })
+        .collect();
+
+    if !references.is_empty() {
+        document.insert("$Reference".to_owned(), Value::Object(references));
+    }
+
+    document.insert(schema.namespace.clone(), Value::Object(schema_obj));
+    Value::Object(document)
+}
@@ -0,0 +1,386 @@
+mod annotations;
+mod json;
+mod xml;
+
+use annotations::{entity_set_annotations, label_annotations, property_annotations, Annotation};
+
+use crate::{
+    amounts::UnitPairing,
+    edmx::data_services::schema::{entity_container::function_import::FunctionImport, entity_type::EntityType, Schema},
+    property::Property,
+    sap_annotations::display_format::SAPDisplayFormatProperty,
+};
+
+static MULTIPLICITY_MANY: &str = "*";
+static MULTIPLICITY_ZERO_OR_ONE: &str = "0..1";
+static HTTP_METHOD_GET: &str = "GET";
+static COLLECTION_PREFIX: &str = "Collection(";
+
+pub static SUFFIX_CSDL_V4_XML: &str = ".v4.xml";
+pub static SUFFIX_CSDL_V4_JSON: &str = ".v4.json";
+
+// Vocabularies whose terms are used by the converted annotations, as (alias, namespace, URI)
+static VOCABULARIES: [(&str, &str, &str); 5] = [
+    (
+        "Capabilities",
+        "Org.OData.Capabilities.V1",
+        "https://oasis-tcs.github.io/odata-vocabularies/vocabularies/Org.OData.Capabilities.V1.xml",
+    ),
+    (
+        "Common",
+        "com.sap.vocabularies.Common.v1",
+        "https://sap.github.io/odata-vocabularies/vocabularies/Common.xml",
+    ),
+    (
+        "Core",
+        "Org.OData.Core.V1",
+        "https://oasis-tcs.github.io/odata-vocabularies/vocabularies/Org.OData.Core.V1.xml",
+    ),
+    (
+        "Measures",
+        "Org.OData.Measures.V1",
+        "https://oasis-tcs.github.io/odata-vocabularies/vocabularies/Org.OData.Measures.V1.xml",
+    ),
+    (
+        "UI",
+        "com.sap.vocabularies.UI.v1",
+        "https://sap.github.io/odata-vocabularies/vocabularies/UI.xml",
+    ),
+];
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Intermediate V4 model from which both the XML and the JSON representations are rendered
+struct CsdlProperty {
+    name: String,
+    edm_type: String,
+    nullable: bool,
+    max_length: Option<u16>,
+    precision: Option<u16>,
+    // Either a number or "variable"
+    scale: Option<String>,
+    annotations: Vec<Annotation>,
+}
+
+struct CsdlNavigationProperty {
+    name: String,
+    target_type: String,
+    is_collection: bool,
+    nullable: bool,
+    partner: Option<String>,
+    // Pairs of (dependent property, principal property)
+    referential_constraints: Vec<(String, String)>,
+}
+
+struct CsdlStructuredType {
+    name: String,
+    // None for a complex type
+    keys: Option<Vec<String>>,
+    has_stream: bool,
+    properties: Vec<CsdlProperty>,
+    navigations: Vec<CsdlNavigationProperty>,
+    annotations: Vec<Annotation>,
+}
+
+struct CsdlParameter {
+    name: String,
+    edm_type: String,
+    nullable: bool,
+    max_length: Option<u16>,
+}
+
+struct CsdlOperation {
+    name: String,
+    is_action: bool,
+    parameters: Vec<CsdlParameter>,
+    // Type name and whether the type is a collection
+    return_type: Option<(String, bool)>,
+    entity_set: Option<String>,
+    annotations: Vec<Annotation>,
+}
+
+struct CsdlEntitySet {
+    name: String,
+    entity_type: String,
+    // Pairs of (navigation property, target entity set)
+    bindings: Vec<(String, String)>,
+    annotations: Vec<Annotation>,
+}
+
+struct CsdlEntityContainer {
+    name: String,
+    entity_sets: Vec<CsdlEntitySet>,
+}
+
+struct CsdlSchema {
+    namespace: String,
+    entity_types: Vec<CsdlStructuredType>,
+    complex_types: Vec<CsdlStructuredType>,
+    operations: Vec<CsdlOperation>,
+    entity_container: Option<CsdlEntityContainer>,
+}
+
+impl CsdlSchema {
+    // The vocabularies used by at least one annotation
+    fn vocabularies(&self) -> Vec<(&'static str, &'static str, &'static str)> {
+        let types = self.entity_types.iter().chain(self.complex_types.iter());
+        let mut aliases: Vec<&str> = types
+            .flat_map(|st| {
+                st.annotations
+                    .iter()
+                    .chain(st.properties.iter().flat_map(|prop| prop.annotations.iter()))
+            })
+            .chain(self.operations.iter().flat_map(|op| op.annotations.iter()))
+            .chain(
+                self.entity_container
+                    .iter()
+                    .flat_map(|ec| ec.entity_sets.iter().flat_map(|es| es.annotations.iter())),
+            )
+            .map(Annotation::vocabulary_alias)
+            .collect();
+        aliases.sort();
+        aliases.dedup();
+
+        VOCABULARIES
+            .into_iter()
+            .filter(|(alias, _, _)| aliases.contains(alias))
+            .collect()
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Conversion from the V2 model
+fn unqualified_name(qualified_name: &str) -> &str {
+    qualified_name.rsplit_once('.').map_or(qualified_name, |(_, name)| name)
+}
+
+// Edm.DateTime and Edm.Time do not exist in V4
+fn to_v4_type(edm_type: &str, is_date: bool) -> String {
+    match edm_type {
+        "Edm.DateTime" if is_date => "Edm.Date".to_owned(),
+        "Edm.DateTime" => "Edm.DateTimeOffset".to_owned(),
+        "Edm.Time" => "Edm.TimeOfDay".to_owned(),
+        _ => match edm_type.strip_prefix(COLLECTION_PREFIX) {
+            Some(item_type) => format!("{COLLECTION_PREFIX}{}", to_v4_type(item_type.trim_end_matches(')'), false)),
+            None => edm_type.to_owned(),
+        },
+    }
+}
+
+fn convert_property(prop: &Property, unit_pairings: &[UnitPairing]) -> CsdlProperty {
+    let is_date = prop.sap_annotations.display_format == Some(SAPDisplayFormatProperty::Date);
+    let edm_type = to_v4_type(&prop.edm_type, is_date);
+
+    CsdlProperty {
+        name: prop.odata_name.clone(),
+        // Edm.Date has no precision
+        precision: if edm_type.eq("Edm.Date") { None } else { prop.precision },
+        edm_type,
+        nullable: prop.nullable,
+        max_length: prop.max_length,
+        scale: if prop.sap_annotations.has_variable_scale {
+            Some("variable".to_owned())
+        } else {
+            prop.scale.map(|scale| scale.to_string())
+        },
+        annotations: property_annotations(prop, unit_pairings),
+    }
+}
+
+fn convert_navigations(schema: &Schema, et: &EntityType) -> Vec<CsdlNavigationProperty> {
+    et.navigations
+        .iter()
+        .filter_map(|nav| {
+            let assoc = schema
+                .associations
+                .iter()
+                .find(|assoc| assoc.name.eq(unqualified_name(&nav.relationship)))?;
+            let to_end = assoc.ends.iter().find(|end| end.role.eq(&nav.to_role))?;
+            let target_type = to_end.end_type.clone()?;
+
+            // The partner is the navigation property of the target entity type that uses the same association in the
+            // opposite direction
+            let partner = schema
+                .entity_types
+                .iter()
+                .find(|target| target.name.eq(unqualified_name(&target_type)))
+                .and_then(|target| {
+                    target
+                        .navigations
+                        .iter()
+                        .find(|other| other.relationship.eq(&nav.relationship) && other.from_role.eq(&nav.to_role))
+                })
+                .map(|other| other.name.clone());
+
+            // In V4, the referential constraint belongs to the navigation property of the dependent entity type
+            let referential_constraints = match &assoc.referential_constraint {
+                Some(rc) if rc.dependent.role.eq(&nav.from_role) => rc
+                    .dependent
+                    .property_refs
+                    .iter()
+                    .zip(rc.principal.property_refs.iter())
+                    .map(|(dependent, principal)| (dependent.name.clone(), principal.name.clone()))
+                    .collect(),
+                _ => vec![],
+            };
+
+            Some(CsdlNavigationProperty {
+                name: nav.name.clone(),
+                target_type,
+                is_collection: to_end.multiplicity.as_deref() == Some(MULTIPLICITY_MANY),
+                nullable: to_end.multiplicity.as_deref() == Some(MULTIPLICITY_ZERO_OR_ONE),
+                partner,
+                referential_constraints,
+            })
+        })
+        .collect()
+}
+
+fn convert_entity_type(schema: &Schema, et: &EntityType) -> CsdlStructuredType {
+    let unit_pairings = UnitPairing::find_in(&et.properties);
+
+    CsdlStructuredType {
+        name: et.name.clone(),
+        keys: Some(et.key.property_refs.iter().map(|key| key.name.clone()).collect()),
+        has_stream: et.has_stream,
+        properties: et
+            .properties
+            .iter()
+            .map(|prop| convert_property(prop, &unit_pairings))
+            .collect(),
+        navigations: convert_navigations(schema, et),
+        annotations: label_annotations(et.sap_label.as_ref()),
+    }
+}
+
+// A V2 function import invoked using GET becomes a V4 function, otherwise it becomes a V4 action
+fn convert_function_import(fi: &FunctionImport) -> CsdlOperation {
+    let return_type = match fi.return_type.as_str() {
+        "" => None,
+        return_type => {
+            let v4_type = to_v4_type(return_type, false);
+
+            match v4_type.strip_prefix(COLLECTION_PREFIX) {
+                Some(item_type) => Some((item_type.trim_end_matches(')').to_owned(), true)),
+                None => Some((v4_type, false)),
+            }
+        },
+    };
+
+    CsdlOperation {
+        name: fi.name.clone(),
+        is_action: !fi.http_method.eq_ignore_ascii_case(HTTP_METHOD_GET),
+        parameters: fi
+            .parameters
+            .iter()
+            .flatten()
+            .map(|param| CsdlParameter {
+                name: param.parameter_name.clone(),
+                edm_type: to_v4_type(&param.parameter_type, false),
+                nullable: param.nullable,
+                max_length: param.max_length.as_ref().and_then(|max_length| max_length.parse().ok()),
+            })
+            .collect(),
+        return_type,
+        entity_set: fi.entity_set.clone(),
+        annotations: label_annotations(fi.sap_annotations.label.as_ref()),
+    }
+}
+
+fn convert_entity_container(schema: &Schema) -> Option<CsdlEntityContainer> {
+    let ent_cont = schema.entity_container.as_ref()?;
+
+    let entity_sets = ent_cont
+        .entity_sets
+        .iter()
+        .map(|es| {
+            let et = schema
+                .entity_types
+                .iter()
+                .find(|et| et.name.eq(unqualified_name(&es.entity_type)));
+
+            // Each association set linking this entity set to another becomes a navigation property binding
+            let bindings = ent_cont
+                .association_sets
+                .iter()
+                .filter_map(|aset| {
+                    let from_end = aset.ends.iter().find(|end| end.entity_set.as_ref() == Some(&es.name))?;
+                    let to_end = aset.ends.iter().find(|end| end.role.ne(&from_end.role))?;
+                    let nav = et?
+                        .navigations
+                        .iter()
+                        .find(|nav| nav.relationship.eq(&aset.association) && nav.from_role.eq(&from_end.role))?;
+
+                    Some((nav.name.clone(), to_end.entity_set.clone()?))
+                })
+                .collect();
+
+            CsdlEntitySet {
+                name: es.name.clone(),
+                entity_type: es.entity_type.clone(),
+                bindings,
+                annotations: entity_set_annotations(es, et),
+            }
+        })
+        .collect();
+
+    Some(CsdlEntityContainer {
+        name: ent_cont.name.clone(),
+        entity_sets,
+    })
+}
+
+fn convert_schema(schema: &Schema) -> CsdlSchema {
+    CsdlSchema {
+        namespace: schema.namespace.clone(),
+        entity_types: schema.entity_types.iter().map(|et| convert_entity_type(schema, et)).collect(),
+        complex_types: schema
+            .complex_types
+            .iter()
+            .flatten()
+            .map(|ct| {
+                let unit_pairings = UnitPairing::find_in(&ct.properties);
+
+                CsdlStructuredType {
+                    name: ct.name.clone(),
+                    keys: None,
+                    has_stream: false,
+                    properties: ct
+                        .properties
+                        .iter()
+                        .map(|prop| convert_property(prop, &unit_pairings))
+                        .collect(),
+                    navigations: vec![],
+                    annotations: vec![],
+                }
+            })
+            .collect(),
+        operations: schema
+            .entity_container
+            .iter()
+            .flat_map(|ec| ec.function_imports.iter().flatten())
+            .map(convert_function_import)
+            .collect(),
+        entity_container: convert_entity_container(schema),
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Convert an OData V2 schema into an OData V4 CSDL XML document
+///
+/// * Associations become navigation properties with partners and referential constraints
+/// * Association sets become navigation property bindings
+/// * Function imports invoked using `GET` become functions, all others become actions
+/// * SAP annotations are mapped to the V4 vocabulary terms described in SAP's documentation
+pub fn gen_csdl_v4_xml(schema: &Schema) -> Vec<u8> {
+    xml::render(&convert_schema(schema)).unwrap_or_default()
+}
+
+/// Convert an OData V2 schema into an OData V4 CSDL JSON document, using the same mapping as
+/// [`gen_csdl_v4_xml`]
+pub fn gen_csdl_v4_json(schema: &Schema) -> Vec<u8> {
+    serde_json::to_vec_pretty(&json::render(&convert_schema(schema))).unwrap_or_default()
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(test)]
+pub mod unit_tests;
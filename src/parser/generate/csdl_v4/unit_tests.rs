@@ -0,0 +1,183 @@
+use serde_json::{json, Value};
+
+use super::{annotations::*, *};
+use crate::{
+    edmx::data_services::schema::entity_container::entity_set::EntitySet, test_utils::*,
+    texts::unit_tests::fetch_entity_type_product_with_texts,
+};
+
+fn find_annotation<'a>(annotations: &'a [Annotation], term: &str) -> Result<&'a AnnotationValue, String> {
+    annotations
+        .iter()
+        .find(|annotation| annotation.term.eq(term))
+        .map(|annotation| &annotation.value)
+        .ok_or(format!("Annotation {term} not found"))
+}
+
+fn check_annotation(annotations: &[Annotation], term: &str, expected: &AnnotationValue) -> Result<(), String> {
+    let received = find_annotation(annotations, term)?;
+
+    if received.eq(expected) {
+        Ok(())
+    } else {
+        Err(format!("Expected '{expected:?}' for {term}.  Got '{received:?}' instead"))
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_convert_to_csdl_v4_xml() -> Result<(), String> {
+    let edmx = fetch_mock_service_edmx()?;
+    let schema = edmx
+        .data_services
+        .fetch_schema("GWSAMPLE_BASIC")
+        .ok_or("Schema GWSAMPLE_BASIC not found")?;
+    let xml = String::from_utf8(gen_csdl_v4_xml(schema)).map_err(|err| err.to_string())?;
+
+    // The document must be well-formed
+    let mut reader = quick_xml::Reader::from_str(&xml);
+    while !matches!(
+        reader.read_event().map_err(|err| err.to_string())?,
+        quick_xml::events::Event::Eof
+    ) {}
+
+    for expected in [
+        r#"<edmx:Include Namespace="Org.OData.Measures.V1" Alias="Measures"/>"#,
+        r#"<Property Name="ChangedAt" Type="Edm.DateTimeOffset" Precision="7">"#,
+        r#"<Annotation Term="Measures.ISOCurrency" Path="CurrencyCode"/>"#,
+        r#"<NavigationProperty Name="ToProducts" Type="Collection(GWSAMPLE_BASIC.Product)" Partner="ToSupplier"/>"#,
+        r#"<NavigationProperty Name="ToSupplier" Type="GWSAMPLE_BASIC.BusinessPartner" Nullable="false" Partner="ToProducts">"#,
+        r#"<ReferentialConstraint Property="SupplierID" ReferencedProperty="BusinessPartnerID"/>"#,
+        r#"<NavigationPropertyBinding Path="ToSupplier" Target="BusinessPartnerSet"/>"#,
+        r#"<PropertyValue Property="Deletable" Bool="false"/>"#,
+        r#"<Action Name="RegenerateAllData">"#,
+        r#"<ActionImport Name="RegenerateAllData" Action="GWSAMPLE_BASIC.RegenerateAllData"/>"#,
+        r#"<Function Name="FindProducts">"#,
+        r#"<FunctionImport Name="FindProducts" Function="GWSAMPLE_BASIC.FindProducts" EntitySet="ProductSet">"#,
+    ] {
+        handle_test_comparison_opt(&xml.contains(expected).then_some(expected), &Some(expected))?;
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn should_convert_to_csdl_v4_json() -> Result<(), String> {
+    let edmx = fetch_mock_service_edmx()?;
+    let schema = edmx
+        .data_services
+        .fetch_schema("GWSAMPLE_BASIC")
+        .ok_or("Schema GWSAMPLE_BASIC not found")?;
+    let csdl: Value = serde_json::from_slice(&gen_csdl_v4_json(schema)).map_err(|err| err.to_string())?;
+
+    handle_test_comparison(&csdl["$Version"], &json!("4.0"))?;
+    handle_test_comparison(&csdl["$EntityContainer"], &json!("GWSAMPLE_BASIC.GWSAMPLE_BASIC_Entities"))?;
+
+    let ns = &csdl["GWSAMPLE_BASIC"];
+    handle_test_comparison(&ns["Product"]["$Key"], &json!(["ProductID"]))?;
+    handle_test_comparison(
+        &ns["Product"]["Price"],
+        &json!({
+            "$Type": "Edm.Decimal",
+            "$Nullable": true,
+            "$Precision": 16,
+            "$Scale": 3,
+            "@Common.Label": "Unit Price",
+            "@Measures.ISOCurrency": { "$Path": "CurrencyCode" }
+        }),
+    )?;
+    handle_test_comparison(
+        &ns["Product"]["ToSupplier"],
+        &json!({
+            "$Kind": "NavigationProperty",
+            "$Type": "GWSAMPLE_BASIC.BusinessPartner",
+            "$Partner": "ToProducts",
+            "$ReferentialConstraint": { "SupplierID": "BusinessPartnerID" }
+        }),
+    )?;
+    handle_test_comparison(&ns["RegenerateAllData"][0]["$Kind"], &json!("Action"))?;
+    handle_test_comparison(
+        &ns["FindProducts"][0]["$ReturnType"],
+        &json!({ "$Collection": true, "$Type": "GWSAMPLE_BASIC.Product" }),
+    )?;
+
+    let container = &ns["GWSAMPLE_BASIC_Entities"];
+    handle_test_comparison(
+        &container["BusinessPartnerSet"]["$NavigationPropertyBinding"],
+        &json!({ "ToProducts": "ProductSet" }),
+    )?;
+    handle_test_comparison(
+        &container["FindProducts"],
+        &json!({
+            "$Function": "GWSAMPLE_BASIC.FindProducts",
+            "$EntitySet": "ProductSet",
+            "@Common.Label": "Find Products"
+        }),
+    )
+}
+
+#[test]
+pub fn should_map_sap_annotations_to_v4_vocabularies() -> Result<(), String> {
+    let product = fetch_entity_type_product_with_texts()?;
+    let unit_pairings = UnitPairing::find_in(&product.properties);
+    let annotations_of = |name: &str| {
+        product
+            .properties
+            .iter()
+            .find(|prop| prop.odata_name.eq(name))
+            .map(|prop| property_annotations(prop, &unit_pairings))
+            .ok_or(format!("Property {name} not found"))
+    };
+
+    // sap:updatable="false"
+    handle_test_bool(find_annotation(&annotations_of("ProductID")?, "Core.Immutable").is_ok())?;
+    // sap:creatable="false" sap:updatable="false"
+    handle_test_bool(find_annotation(&annotations_of("SupplierName")?, "Core.Computed").is_ok())?;
+
+    check_annotation(
+        &annotations_of("SupplierID")?,
+        "Common.Text",
+        &AnnotationValue::Path("SupplierName".to_owned()),
+    )?;
+    check_annotation(
+        &annotations_of("WeightMeasure")?,
+        "Measures.Unit",
+        &AnnotationValue::Path("WeightUnit".to_owned()),
+    )?;
+
+    let es: EntitySet = quick_xml::de::from_str(
+        r#"<EntitySet Name="ProductSet" EntityType="GWSAMPLE_BASIC.Product" sap:creatable="false"
+           sap:pageable="false" sap:searchable="true" sap:requires-filter="true" sap:content-version="1"/>"#,
+    )
+    .map_err(|err| err.to_string())?;
+    let es_annotations = entity_set_annotations(&es, Some(&product));
+
+    check_annotation(
+        &es_annotations,
+        "Capabilities.InsertRestrictions",
+        &AnnotationValue::Record(vec![("Insertable", AnnotationValue::Bool(false))]),
+    )?;
+    handle_test_bool(find_annotation(&es_annotations, "Capabilities.SearchRestrictions").is_err())?;
+    check_annotation(&es_annotations, "Capabilities.SkipSupported", &AnnotationValue::Bool(false))?;
+    check_annotation(
+        &es_annotations,
+        "Capabilities.SortRestrictions",
+        &AnnotationValue::Record(vec![(
+            "NonSortableProperties",
+            AnnotationValue::PropertyPaths(vec![
+                "Name".to_owned(),
+                "NameLanguage".to_owned(),
+                "Description".to_owned(),
+                "DescriptionLanguage".to_owned(),
+            ]),
+        )]),
+    )?;
+
+    match find_annotation(&es_annotations, "Capabilities.FilterRestrictions")? {
+        AnnotationValue::Record(members) => {
+            handle_test_comparison(&members[0].0, &"RequiresFilter")?;
+            handle_test_comparison(&members[1].0, &"NonFilterableProperties")
+        },
+        other => Err(format!("Expected a record, got {other:?}")),
+    }
+}
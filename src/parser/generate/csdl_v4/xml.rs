@@ -0,0 +1,318 @@
+use std::io::Result;
+
+use quick_xml::{
+    events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event},
+    Writer,
+};
+
+use super::{
+    annotations::{Annotation, AnnotationValue},
+    CsdlNavigationProperty, CsdlOperation, CsdlProperty, CsdlSchema, CsdlStructuredType,
+};
+
+static XMLNS_EDMX: &str = "http://docs.oasis-open.org/odata/ns/edmx";
+static XMLNS_EDM: &str = "http://docs.oasis-open.org/odata/ns/edm";
+
+type XmlWriter = Writer<Vec<u8>>;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn start_tag(name: &str, attrs: &[(&str, &str)]) -> BytesStart<'static> {
+    let mut elem = BytesStart::new(name.to_owned());
+
+    for attr in attrs {
+        elem.push_attribute(*attr);
+    }
+
+    elem
+}
+
+fn write_empty(writer: &mut XmlWriter, name: &str, attrs: &[(&str, &str)]) -> Result<()> {
+    writer.write_event(Event::Empty(start_tag(name, attrs)))
+}
+
+fn write_start(writer: &mut XmlWriter, name: &str, attrs: &[(&str, &str)]) -> Result<()> {
+    writer.write_event(Event::Start(start_tag(name, attrs)))
+}
+
+fn write_end(writer: &mut XmlWriter, name: &str) -> Result<()> {
+    writer.write_event(Event::End(BytesEnd::new(name.to_owned())))
+}
+
+// Write an element that is empty if it has no children
+fn write_element<F>(
+    writer: &mut XmlWriter,
+    name: &str,
+    attrs: &[(&str, &str)],
+    has_children: bool,
+    children: F,
+) -> Result<()>
+where
+    F: FnOnce(&mut XmlWriter) -> Result<()>,
+{
+    if has_children {
+        write_start(writer, name, attrs)?;
+        children(writer)?;
+        write_end(writer, name)
+    } else {
+        write_empty(writer, name, attrs)
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Write an annotation value either as an attribute of `elem_name` (for constant and path expressions), or as the
+// child of `elem_name` (for collections and records)
+fn write_annotation_value(
+    writer: &mut XmlWriter,
+    elem_name: &str,
+    name_attr: (&str, &str),
+    value: &AnnotationValue,
+) -> Result<()> {
+    let bool_str = |b: &bool| if *b { "true" } else { "false" };
+
+    match value {
+        AnnotationValue::Bool(b) => write_empty(writer, elem_name, &[name_attr, ("Bool", bool_str(b))]),
+        AnnotationValue::String(s) => write_empty(writer, elem_name, &[name_attr, ("String", s)]),
+        AnnotationValue::Path(path) => write_empty(writer, elem_name, &[name_attr, ("Path", path)]),
+        AnnotationValue::EnumMember(enum_type, member) => write_empty(
+            writer,
+            elem_name,
+            &[name_attr, ("EnumMember", &format!("{enum_type}/{member}"))],
+        ),
+        AnnotationValue::PropertyPaths(paths) => {
+            write_start(writer, elem_name, &[name_attr])?;
+            write_start(writer, "Collection", &[])?;
+
+            for path in paths {
+                write_start(writer, "PropertyPath", &[])?;
+                writer.write_event(Event::Text(BytesText::new(path)))?;
+                write_end(writer, "PropertyPath")?;
+            }
+
+            write_end(writer, "Collection")?;
+            write_end(writer, elem_name)
+        },
+        AnnotationValue::Record(members) => {
+            write_start(writer, elem_name, &[name_attr])?;
+            write_start(writer, "Record", &[])?;
+
+            for (member_name, member_value) in members {
+                write_annotation_value(writer, "PropertyValue", ("Property", member_name), member_value)?;
+            }
+
+            write_end(writer, "Record")?;
+            write_end(writer, elem_name)
+        },
+    }
+}
+
+fn write_annotations(writer: &mut XmlWriter, annotations: &[Annotation]) -> Result<()> {
+    for annotation in annotations {
+        write_annotation_value(writer, "Annotation", ("Term", annotation.term), &annotation.value)?;
+    }
+
+    Ok(())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn write_property(writer: &mut XmlWriter, prop: &CsdlProperty) -> Result<()> {
+    let max_length = prop.max_length.map(|max_length| max_length.to_string());
+    let precision = prop.precision.map(|precision| precision.to_string());
+    let mut attrs = vec![("Name", prop.name.as_str()), ("Type", prop.edm_type.as_str())];
+
+    if !prop.nullable {
+        attrs.push(("Nullable", "false"));
+    }
+
+    if let Some(max_length) = &max_length {
+        attrs.push(("MaxLength", max_length));
+    }
+
+    if let Some(precision) = &precision {
+        attrs.push(("Precision", precision));
+    }
+
+    if let Some(scale) = &prop.scale {
+        attrs.push(("Scale", scale));
+    }
+
+    write_element(writer, "Property", &attrs, !prop.annotations.is_empty(), |writer| {
+        write_annotations(writer, &prop.annotations)
+    })
+}
+
+fn write_navigation(writer: &mut XmlWriter, nav: &CsdlNavigationProperty) -> Result<()> {
+    let nav_type = if nav.is_collection {
+        format!("Collection({})", nav.target_type)
+    } else {
+        nav.target_type.clone()
+    };
+    let mut attrs = vec![("Name", nav.name.as_str()), ("Type", nav_type.as_str())];
+
+    if !nav.is_collection && !nav.nullable {
+        attrs.push(("Nullable", "false"));
+    }
+
+    if let Some(partner) = &nav.partner {
+        attrs.push(("Partner", partner));
+    }
+
+    write_element(
+        writer,
+        "NavigationProperty",
+        &attrs,
+        !nav.referential_constraints.is_empty(),
+        |writer| {
+            for (property, referenced_property) in &nav.referential_constraints {
+                write_empty(
+                    writer,
+                    "ReferentialConstraint",
+                    &[("Property", property), ("ReferencedProperty", referenced_property)],
+                )?;
+            }
+
+            Ok(())
+        },
+    )
+}
+
+fn write_structured_type(writer: &mut XmlWriter, st: &CsdlStructuredType) -> Result<()> {
+    let elem_name = if st.keys.is_some() { "EntityType" } else { "ComplexType" };
+    let mut attrs = vec![("Name", st.name.as_str())];
+
+    if st.has_stream {
+        attrs.push(("HasStream", "true"));
+    }
+
+    write_start(writer, elem_name, &attrs)?;
+
+    if let Some(keys) = &st.keys {
+        write_start(writer, "Key", &[])?;
+
+        for key in keys {
+            write_empty(writer, "PropertyRef", &[("Name", key)])?;
+        }
+
+        write_end(writer, "Key")?;
+    }
+
+    for prop in &st.properties {
+        write_property(writer, prop)?;
+    }
+
+    for nav in &st.navigations {
+        write_navigation(writer, nav)?;
+    }
+
+    write_annotations(writer, &st.annotations)?;
+    write_end(writer, elem_name)
+}
+
+fn write_operation(writer: &mut XmlWriter, op: &CsdlOperation) -> Result<()> {
+    let elem_name = if op.is_action { "Action" } else { "Function" };
+    write_start(writer, elem_name, &[("Name", &op.name)])?;
+
+    for param in &op.parameters {
+        let max_length = param.max_length.map(|max_length| max_length.to_string());
+        let mut attrs = vec![("Name", param.name.as_str()), ("Type", param.edm_type.as_str())];
+
+        if !param.nullable {
+            attrs.push(("Nullable", "false"));
+        }
+
+        if let Some(max_length) = &max_length {
+            attrs.push(("MaxLength", max_length));
+        }
+
+        write_empty(writer, "Parameter", &attrs)?;
+    }
+
+    if let Some((return_type, is_collection)) = &op.return_type {
+        let return_type = if *is_collection {
+            format!("Collection({return_type})")
+        } else {
+            return_type.clone()
+        };
+        write_empty(writer, "ReturnType", &[("Type", &return_type)])?;
+    }
+
+    write_end(writer, elem_name)
+}
+
+fn write_entity_container(writer: &mut XmlWriter, schema: &CsdlSchema) -> Result<()> {
+    let Some(ec) = &schema.entity_container else {
+        return Ok(());
+    };
+
+    write_start(writer, "EntityContainer", &[("Name", &ec.name)])?;
+
+    for es in &ec.entity_sets {
+        let has_children = !es.bindings.is_empty() || !es.annotations.is_empty();
+
+        write_element(
+            writer,
+            "EntitySet",
+            &[("Name", &es.name), ("EntityType", &es.entity_type)],
+            has_children,
+            |writer| {
+                for (path, target) in &es.bindings {
+                    write_empty(writer, "NavigationPropertyBinding", &[("Path", path), ("Target", target)])?;
+                }
+
+                write_annotations(writer, &es.annotations)
+            },
+        )?;
+    }
+
+    for op in &schema.operations {
+        let qualified_name = format!("{}.{}", schema.namespace, op.name);
+        let (elem_name, op_attr) = if op.is_action {
+            ("ActionImport", "Action")
+        } else {
+            ("FunctionImport", "Function")
+        };
+        let mut attrs = vec![("Name", op.name.as_str()), (op_attr, qualified_name.as_str())];
+
+        if let Some(entity_set) = &op.entity_set {
+            attrs.push(("EntitySet", entity_set));
+        }
+
+        write_element(writer, elem_name, &attrs, !op.annotations.is_empty(), |writer| {
+            write_annotations(writer, &op.annotations)
+        })?;
+    }
+
+    write_end(writer, "EntityContainer")
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+pub fn render(schema: &CsdlSchema) -> Result<Vec<u8>> {
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+
+    writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("utf-8"), None)))?;
+    write_start(&mut writer, "edmx:Edmx", &[("Version", "4.0"), ("xmlns:edmx", XMLNS_EDMX)])?;
+
+    for (alias, namespace, uri) in schema.vocabularies() {
+        write_start(&mut writer, "edmx:Reference", &[("Uri", uri)])?;
+        write_empty(&mut writer, "edmx:Include", &[("Namespace", namespace), ("Alias", alias)])?;
+        write_end(&mut writer, "edmx:Reference")?;
+    }
+
+    write_start(&mut writer, "edmx:DataServices", &[])?;
+    write_start(&mut writer, "Schema", &[("Namespace", &schema.namespace), ("xmlns", XMLNS_EDM)])?;
+
+    for st in schema.entity_types.iter().chain(schema.complex_types.iter()) {
+        write_structured_type(&mut writer, st)?;
+    }
+
+    for op in &schema.operations {
+        write_operation(&mut writer, op)?;
+    }
+
+    write_entity_container(&mut writer, schema)?;
+
+    write_end(&mut writer, "Schema")?;
+    write_end(&mut writer, "edmx:DataServices")?;
+    write_end(&mut writer, "edmx:Edmx")?;
+
+    Ok(writer.into_inner())
+}
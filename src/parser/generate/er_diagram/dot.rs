@@ -0,0 +1,78 @@
+use super::{ErAttribute, ErDiagram, ErEntity, ErRelationship};
+
+static INDENT: &str = "  ";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Characters with a special meaning in record labels and quoted strings must be escaped
+fn escape(s: &str) -> String {
+    s.chars().fold(String::new(), |mut acc, c| {
+        if "{}|<>\"\\".contains(c) {
+            acc.push('\\');
+        }
+
+        acc.push(c);
+        acc
+    })
+}
+
+fn attribute(attr: &ErAttribute) -> String {
+    let markers: Vec<&str> = [(attr.is_key, "PK"), (attr.is_foreign_key, "FK")]
+        .into_iter()
+        .filter_map(|(is_set, marker)| is_set.then_some(marker))
+        .collect();
+    let name_and_type = format!("{} : {}", escape(&attr.name), escape(&attr.type_name));
+
+    // Each line of the record is left-justified
+    if markers.is_empty() {
+        format!("{name_and_type}\\l")
+    } else {
+        format!("{name_and_type} ({})\\l", markers.join(", "))
+    }
+}
+
+fn entity(entity: &ErEntity) -> String {
+    let attributes: String = entity.attributes.iter().map(attribute).collect();
+
+    format!(
+        "{INDENT}\"{}\" [label=\"{{{}|{attributes}}}\"];\n",
+        escape(&entity.name),
+        escape(&entity.name)
+    )
+}
+
+fn relationship(rel: &ErRelationship) -> String {
+    let constraints: String = rel
+        .constraints
+        .iter()
+        .map(|(principal, dependent)| format!("\\n{} = {}", escape(principal), escape(dependent)))
+        .collect();
+    let style = if rel.is_identifying { "solid" } else { "dashed" };
+
+    format!(
+        "{INDENT}\"{}\" -> \"{}\" [label=\"{}{constraints}\", taillabel=\"{}\", headlabel=\"{}\", style={style}];\n",
+        escape(&rel.from_entity),
+        escape(&rel.to_entity),
+        escape(&rel.name),
+        escape(&rel.from_multiplicity),
+        escape(&rel.to_multiplicity),
+    )
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+pub fn render(diagram: &ErDiagram) -> String {
+    let mut out = format!("digraph \"{}\" {{\n", escape(&diagram.name));
+    out.push_str(&format!("{INDENT}rankdir=LR;\n"));
+    out.push_str(&format!("{INDENT}node [shape=record, fontname=\"Helvetica\", fontsize=10];\n"));
+    out.push_str(&format!("{INDENT}edge [fontname=\"Helvetica\", fontsize=9, arrowhead=none];\n"));
+
+    for e in &diagram.entities {
+        out.push_str(&entity(e));
+    }
+
+    for rel in &diagram.relationships {
+        out.push_str(&relationship(rel));
+    }
+
+    out.push_str("}\n");
+    out
+}
@@ -0,0 +1,63 @@
+use super::{ErAttribute, ErDiagram, ErRelationship};
+
+static INDENT: &str = "    ";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Crow's foot notation for the left-hand (from) and right-hand (to) ends of a relationship
+fn cardinality(multiplicity: &str, is_left: bool) -> &'static str {
+    match (multiplicity, is_left) {
+        ("0..1", true) => "|o",
+        ("0..1", false) => "o|",
+        ("*", true) => "}o",
+        ("*", false) => "o{",
+        _ => "||",
+    }
+}
+
+fn attribute(attr: &ErAttribute) -> String {
+    let markers: Vec<&str> = [(attr.is_key, "PK"), (attr.is_foreign_key, "FK")]
+        .into_iter()
+        .filter_map(|(is_set, marker)| is_set.then_some(marker))
+        .collect();
+
+    if markers.is_empty() {
+        format!("{INDENT}{INDENT}{} {}\n", attr.type_name, attr.name)
+    } else {
+        format!("{INDENT}{INDENT}{} {} {}\n", attr.type_name, attr.name, markers.join(", "))
+    }
+}
+
+fn relationship(rel: &ErRelationship) -> String {
+    // Identifying relationships are drawn with a solid line, all others with a dashed line
+    let line = if rel.is_identifying { "--" } else { ".." };
+
+    format!(
+        "{INDENT}{} {}{line}{} {} : \"{}\"\n",
+        rel.from_entity,
+        cardinality(&rel.from_multiplicity, true),
+        cardinality(&rel.to_multiplicity, false),
+        rel.to_entity,
+        rel.name
+    )
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+pub fn render(diagram: &ErDiagram) -> String {
+    let mut out = format!("---\ntitle: {}\n---\nerDiagram\n", diagram.name);
+
+    for entity in &diagram.entities {
+        out.push_str(&format!("{INDENT}{} {{\n", entity.name));
+
+        for attr in &entity.attributes {
+            out.push_str(&attribute(attr));
+        }
+
+        out.push_str(&format!("{INDENT}}}\n"));
+    }
+
+    for rel in &diagram.relationships {
+        out.push_str(&relationship(rel));
+    }
+
+    out
+}
@@ -0,0 +1,185 @@
+mod dot;
+mod mermaid;
+
+use crate::edmx::data_services::schema::{
+    association::{end::End, Association},
+    entity_type::EntityType,
+    Schema,
+};
+
+static EDM_PREFIX: &str = "Edm.";
+
+pub static SUFFIX_MERMAID: &str = ".er.mmd";
+pub static SUFFIX_GRAPHVIZ: &str = ".er.dot";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Intermediate model from which both the Mermaid and the Graphviz diagrams are rendered
+struct ErAttribute {
+    name: String,
+    // The unqualified type name, E.G. "String" or "CT_Address"
+    type_name: String,
+    is_key: bool,
+    is_foreign_key: bool,
+}
+
+struct ErEntity {
+    name: String,
+    attributes: Vec<ErAttribute>,
+}
+
+// An association whose "from" end is the principal if the association has a referential constraint
+struct ErRelationship {
+    name: String,
+    from_entity: String,
+    from_multiplicity: String,
+    to_entity: String,
+    to_multiplicity: String,
+    // Pairs of (principal property, dependent property)
+    constraints: Vec<(String, String)>,
+    // True if the dependent's foreign key is part of its own key
+    is_identifying: bool,
+}
+
+struct ErDiagram {
+    name: String,
+    entities: Vec<ErEntity>,
+    relationships: Vec<ErRelationship>,
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn unqualified_name(qualified_name: &str) -> &str {
+    qualified_name.rsplit_once('.').map_or(qualified_name, |(_, name)| name)
+}
+
+fn type_name(edm_type: &str) -> String {
+    edm_type
+        .strip_prefix(EDM_PREFIX)
+        .unwrap_or_else(|| unqualified_name(edm_type))
+        .to_owned()
+}
+
+// The entity types used by the named entity sets, or all entity types if no entity sets are named
+fn selected_entity_types<'a>(schema: &'a Schema, entity_sets: &[String]) -> Vec<&'a EntityType> {
+    if entity_sets.is_empty() {
+        return schema.entity_types.iter().collect();
+    }
+
+    let selected_type_names: Vec<&str> = schema
+        .entity_container
+        .iter()
+        .flat_map(|ec| ec.entity_sets.iter())
+        .filter(|es| entity_sets.contains(&es.name))
+        .map(|es| unqualified_name(&es.entity_type))
+        .collect();
+
+    schema
+        .entity_types
+        .iter()
+        .filter(|et| selected_type_names.contains(&et.name.as_str()))
+        .collect()
+}
+
+fn convert_association(assoc: &Association, entity_types: &[&EntityType]) -> Option<ErRelationship> {
+    // The metadata cannot be parsed unless an association has exactly two ends
+    let [first_end, second_end] = &assoc.ends;
+    let end_type = |end: &End| end.end_type.as_deref().map(unqualified_name).map(str::to_owned);
+    let multiplicity = |end: &End| end.multiplicity.clone().unwrap_or_default();
+
+    // Both ends must be in the diagram
+    let is_selected = |end: &End| end_type(end).is_some_and(|name| entity_types.iter().any(|et| et.name.eq(&name)));
+
+    if !is_selected(first_end) || !is_selected(second_end) {
+        return None;
+    }
+
+    let (from_end, to_end) = match &assoc.referential_constraint {
+        Some(rc) if rc.principal.role.eq(&second_end.role) => (second_end, first_end),
+        _ => (first_end, second_end),
+    };
+
+    let (constraints, is_identifying) = match &assoc.referential_constraint {
+        Some(rc) => {
+            let dependent_keys = entity_types
+                .iter()
+                .find(|et| Some(&et.name) == end_type(to_end).as_ref())
+                .map(|et| &et.key.property_refs);
+            let is_identifying = rc.dependent.property_refs.iter().all(|dependent| {
+                dependent_keys.is_some_and(|keys| keys.iter().any(|key| key.name.eq(&dependent.name)))
+            });
+
+            (
+                rc.principal
+                    .property_refs
+                    .iter()
+                    .zip(rc.dependent.property_refs.iter())
+                    .map(|(principal, dependent)| (principal.name.clone(), dependent.name.clone()))
+                    .collect(),
+                is_identifying,
+            )
+        },
+        None => (vec![], false),
+    };
+
+    Some(ErRelationship {
+        name: assoc.name.clone(),
+        from_entity: end_type(from_end)?,
+        from_multiplicity: multiplicity(from_end),
+        to_entity: end_type(to_end)?,
+        to_multiplicity: multiplicity(to_end),
+        constraints,
+        is_identifying,
+    })
+}
+
+fn convert_schema(schema: &Schema, entity_sets: &[String]) -> ErDiagram {
+    let entity_types = selected_entity_types(schema, entity_sets);
+    let relationships: Vec<ErRelationship> = schema
+        .associations
+        .iter()
+        .filter_map(|assoc| convert_association(assoc, &entity_types))
+        .collect();
+
+    let entities = entity_types
+        .iter()
+        .map(|et| ErEntity {
+            name: et.name.clone(),
+            attributes: et
+                .properties
+                .iter()
+                .map(|prop| ErAttribute {
+                    name: prop.odata_name.clone(),
+                    type_name: type_name(&prop.edm_type),
+                    is_key: et.key.property_refs.iter().any(|key| key.name.eq(&prop.odata_name)),
+                    is_foreign_key: relationships.iter().any(|rel| {
+                        rel.to_entity.eq(&et.name)
+                            && rel.constraints.iter().any(|(_, dependent)| dependent.eq(&prop.odata_name))
+                    }),
+                })
+                .collect(),
+        })
+        .collect();
+
+    ErDiagram {
+        name: schema.namespace.clone(),
+        entities,
+        relationships,
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate a Mermaid `erDiagram` showing the entity types of a schema and the associations between them
+///
+/// If `entity_sets` is not empty, the diagram is restricted to the entity types of the named entity sets and the
+/// associations between those entity types
+pub fn gen_mermaid_er_diagram(schema: &Schema, entity_sets: &[String]) -> Vec<u8> {
+    mermaid::render(&convert_schema(schema, entity_sets)).into_bytes()
+}
+
+/// Generate a Graphviz DOT digraph showing the same information as [`gen_mermaid_er_diagram`]
+pub fn gen_graphviz_er_diagram(schema: &Schema, entity_sets: &[String]) -> Vec<u8> {
+    dot::render(&convert_schema(schema, entity_sets)).into_bytes()
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(test)]
+pub mod unit_tests;
@@ -0,0 +1,64 @@
+use super::*;
+use crate::test_utils::*;
+
+fn contains_line(diagram: Vec<u8>, expected: &str) -> Result<(), String> {
+    let lines = to_rust_src(diagram);
+    let received = lines.iter().map(|line| line.trim()).find(|line| line.eq(&expected));
+
+    handle_test_comparison_opt(&received, &Some(expected))
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_generate_mermaid_er_diagram() -> Result<(), String> {
+    let edmx = fetch_mock_service_edmx()?;
+    let schema = edmx
+        .data_services
+        .fetch_schema("GWSAMPLE_BASIC")
+        .ok_or("Schema GWSAMPLE_BASIC not found")?;
+
+    for expected in [
+        "erDiagram",
+        "BusinessPartner {",
+        "String BusinessPartnerID PK",
+        "String ProductID PK",
+        "String SupplierID FK",
+        "Decimal Price",
+        "BusinessPartner ||..o{ Product : \"Assoc_BusinessPartner_Products\"",
+    ] {
+        contains_line(gen_mermaid_er_diagram(schema, &[]), expected)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+pub fn should_generate_graphviz_er_diagram() -> Result<(), String> {
+    let edmx = fetch_mock_service_edmx()?;
+    let schema = edmx
+        .data_services
+        .fetch_schema("GWSAMPLE_BASIC")
+        .ok_or("Schema GWSAMPLE_BASIC not found")?;
+
+    contains_line(
+        gen_graphviz_er_diagram(schema, &[]),
+        r#""BusinessPartner" -> "Product" [label="Assoc_BusinessPartner_Products\nBusinessPartnerID = SupplierID", taillabel="1", headlabel="*", style=dashed];"#,
+    )
+}
+
+#[test]
+pub fn should_restrict_er_diagram_to_entity_sets() -> Result<(), String> {
+    let edmx = fetch_mock_service_edmx()?;
+    let schema = edmx
+        .data_services
+        .fetch_schema("GWSAMPLE_BASIC")
+        .ok_or("Schema GWSAMPLE_BASIC not found")?;
+    let diagram =
+        String::from_utf8(gen_mermaid_er_diagram(schema, &["ProductSet".to_owned()])).map_err(|err| err.to_string())?;
+
+    handle_test_bool(diagram.contains("Product {"))?;
+    handle_test_bool(!diagram.contains("BusinessPartner"))?;
+
+    // Without the association, SupplierID is no longer shown as a foreign key
+    handle_test_bool(diagram.contains("String SupplierID\n"))
+}
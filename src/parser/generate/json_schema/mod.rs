@@ -0,0 +1,243 @@
+use serde_json::{json, Map, Value};
+
+use crate::{
+    edmx::data_services::schema::Schema,
+    property::{metadata::PropertyType, Property},
+};
+
+static JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+static JSON_SCHEMA_DEFS_PREFIX: &str = "#/$defs/";
+// The largest Int64 has 19 digits
+static MAX_INT64_DIGITS: u16 = 19;
+
+pub static SUFFIX_JSON_SCHEMA: &str = ".schema.json";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Inclusive range of values for each integer EDM type
+fn integer_range(edm_type: &str) -> Option<(i64, i64)> {
+    match edm_type {
+        "Byte" => Some((u8::MIN as i64, u8::MAX as i64)),
+        "SByte" => Some((i8::MIN as i64, i8::MAX as i64)),
+        "Int16" => Some((i16::MIN as i64, i16::MAX as i64)),
+        "Int32" => Some((i32::MIN as i64, i32::MAX as i64)),
+        _ => None,
+    }
+}
+
+// OData V2 JSON transfers `Edm.Decimal` values as strings, so the number of integer digits permitted by `precision`
+// and the number of fractional digits permitted by `scale` are expressed as a pattern.  (A floating point `multipleOf`
+// cannot represent most scales exactly, and causes validators to reject valid values)
+fn decimal_pattern(precision: Option<u16>, scale: u16, has_variable_scale: bool) -> String {
+    let digits = |min: u16, max: Option<u16>| match max {
+        Some(max) => format!("\\d{{{min},{max}}}"),
+        None if min == 0 => "\\d*".to_owned(),
+        None => "\\d+".to_owned(),
+    };
+    // A variable scale permits up to `precision` digits on either side of the decimal point
+    let (integer_digits, fraction_digits) = if has_variable_scale {
+        (precision, precision)
+    } else {
+        (precision.map(|precision| precision.saturating_sub(scale)), Some(scale))
+    };
+    let integer_part = match integer_digits {
+        Some(0) => "0".to_owned(),
+        max => digits(1, max),
+    };
+
+    // At least one digit is required, either before or after the decimal point
+    if fraction_digits == Some(0) {
+        format!("^-?{integer_part}$")
+    } else {
+        format!(
+            "^-?({integer_part}(\\.{})?|\\.{})$",
+            digits(0, fraction_digits),
+            digits(1, fraction_digits)
+        )
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate the JSON Schema for the non-null values of an EDM type (such as `Decimal`), constrained by the facets
+/// declared in the metadata
+pub fn gen_edm_type_schema(
+    edm_type: &str,
+    max_length: Option<u16>,
+    precision: Option<u16>,
+    scale: Option<u16>,
+    has_variable_scale: bool,
+    pattern: Option<&String>,
+) -> Map<String, Value> {
+    let mut type_schema = Map::new();
+
+    // OData V2 JSON transfers `Edm.Int64` values as strings to avoid losing precision
+    if edm_type.eq("Int64") {
+        type_schema.insert("type".to_owned(), json!("string"));
+        type_schema.insert("pattern".to_owned(), json!(format!("^-?\\d{{1,{MAX_INT64_DIGITS}}}$")));
+        return type_schema;
+    }
+
+    if let Some((min, max)) = integer_range(edm_type) {
+        type_schema.insert("type".to_owned(), json!("integer"));
+        type_schema.insert("minimum".to_owned(), json!(min));
+        type_schema.insert("maximum".to_owned(), json!(max));
+        return type_schema;
+    }
+
+    match edm_type {
+        "Boolean" => {
+            type_schema.insert("type".to_owned(), json!("boolean"));
+        },
+        "Decimal" => {
+            type_schema.insert("type".to_owned(), json!("string"));
+            type_schema.insert(
+                "pattern".to_owned(),
+                json!(decimal_pattern(precision, scale.unwrap_or(0), has_variable_scale)),
+            );
+        },
+        "Double" | "Single" => {
+            type_schema.insert("type".to_owned(), json!("number"));
+        },
+        "DateTime" | "DateTimeOffset" => {
+            type_schema.insert("type".to_owned(), json!("string"));
+            type_schema.insert("format".to_owned(), json!("date-time"));
+        },
+        "Guid" => {
+            type_schema.insert("type".to_owned(), json!("string"));
+            type_schema.insert("format".to_owned(), json!("uuid"));
+        },
+        "Time" => {
+            type_schema.insert("type".to_owned(), json!("string"));
+            type_schema.insert("format".to_owned(), json!("duration"));
+        },
+        "Binary" => {
+            type_schema.insert("type".to_owned(), json!("string"));
+            type_schema.insert("contentEncoding".to_owned(), json!("base64"));
+        },
+        _ => {
+            type_schema.insert("type".to_owned(), json!("string"));
+
+            if let Some(max_length) = max_length.filter(|max_length| *max_length > 0) {
+                type_schema.insert("maxLength".to_owned(), json!(max_length));
+            }
+
+            if let Some(regexp) = pattern {
+                type_schema.insert("pattern".to_owned(), json!(regexp));
+            }
+        },
+    }
+
+    type_schema
+}
+
+/// Generate the JSON Schema of a property.
+///
+/// Complex types are referenced using `ref_prefix` followed by the name of the complex type, and the property's
+/// `sap:label` and `sap:quickinfo` annotations become the schema's `title` and `description`
+pub fn gen_property_schema(prop: &Property, ref_prefix: &str) -> Value {
+    let mut prop_schema = match prop.get_property_type() {
+        PropertyType::Edm(edm_type, _) => {
+            let mut type_schema = gen_edm_type_schema(
+                &edm_type,
+                prop.max_length,
+                prop.precision,
+                prop.scale,
+                prop.sap_annotations.has_variable_scale,
+                prop.sap_annotations.validation_regexp.as_ref(),
+            );
+
+            if prop.nullable {
+                if let Some(type_name) = type_schema.remove("type") {
+                    type_schema.insert("type".to_owned(), json!([type_name, "null"]));
+                }
+            }
+
+            type_schema
+        },
+        PropertyType::Complex(ct_name) => {
+            let type_ref = json!({ "$ref": format!("{ref_prefix}{ct_name}") });
+            let mut type_schema = Map::new();
+
+            if prop.nullable {
+                type_schema.insert("anyOf".to_owned(), json!([type_ref, { "type": "null" }]));
+            } else {
+                type_schema.extend(type_ref.as_object().cloned().unwrap_or_default());
+            }
+
+            type_schema
+        },
+        // Nothing is known about the property's values
+        PropertyType::Unqualified => Map::new(),
+    };
+
+    if let Some(label) = &prop.sap_annotations.label {
+        prop_schema.insert("title".to_owned(), json!(label));
+    }
+
+    if let Some(quick_info) = &prop.sap_annotations.quick_info {
+        prop_schema.insert("description".to_owned(), json!(quick_info));
+    }
+
+    Value::Object(prop_schema)
+}
+
+/// Generate the JSON Schema of an object containing the supplied properties.
+///
+/// The names of the key properties are listed as `required`
+pub fn gen_object_schema(label: Option<&String>, props: &[Property], keys: &[String], ref_prefix: &str) -> Value {
+    let properties: Map<String, Value> = props
+        .iter()
+        .map(|prop| (prop.odata_name.clone(), gen_property_schema(prop, ref_prefix)))
+        .collect();
+
+    let mut object_schema = Map::new();
+    object_schema.insert("type".to_owned(), json!("object"));
+
+    if let Some(label) = label {
+        object_schema.insert("title".to_owned(), json!(label));
+    }
+
+    object_schema.insert("properties".to_owned(), Value::Object(properties));
+
+    if !keys.is_empty() {
+        object_schema.insert("required".to_owned(), json!(keys));
+    }
+
+    Value::Object(object_schema)
+}
+
+/// Generate a JSON Schema definition for each entity type and complex type in the schema, using `ref_prefix` to refer
+/// from one definition to another
+pub fn gen_type_schemas(schema: &Schema, ref_prefix: &str) -> Map<String, Value> {
+    let mut type_schemas = Map::new();
+
+    for ct in schema.complex_types.iter().flatten() {
+        type_schemas.insert(ct.name.clone(), gen_object_schema(None, &ct.properties, &[], ref_prefix));
+    }
+
+    for et in &schema.entity_types {
+        let keys: Vec<String> = et.key.property_refs.iter().map(|key| key.name.clone()).collect();
+        type_schemas.insert(
+            et.name.clone(),
+            gen_object_schema(et.sap_label.as_ref(), &et.properties, &keys, ref_prefix),
+        );
+    }
+
+    type_schemas
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate a JSON Schema document in which each entity type and complex type is declared under `$defs`
+pub fn gen_json_schema(odata_srv_name: &str, schema: &Schema) -> Vec<u8> {
+    let document = json!({
+        "$schema": JSON_SCHEMA_DIALECT,
+        "$id": format!("{odata_srv_name}{SUFFIX_JSON_SCHEMA}"),
+        "title": schema.namespace,
+        "$defs": gen_type_schemas(schema, JSON_SCHEMA_DEFS_PREFIX),
+    });
+
+    serde_json::to_vec_pretty(&document).unwrap_or_default()
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(test)]
+pub mod unit_tests;
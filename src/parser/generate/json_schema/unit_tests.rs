@@ -0,0 +1,106 @@
+use serde_json::{json, Value};
+
+use super::*;
+use crate::test_utils::*;
+
+fn fetch_json_schema() -> Result<Value, String> {
+    let edmx = fetch_mock_service_edmx()?;
+    let schema = edmx
+        .data_services
+        .fetch_schema("GWSAMPLE_BASIC")
+        .ok_or("Schema GWSAMPLE_BASIC not found")?;
+
+    serde_json::from_slice(&gen_json_schema("mock_service", schema)).map_err(|err| err.to_string())
+}
+
+fn fetch_def<'a>(json_schema: &'a Value, pointer: &str) -> Result<&'a Value, String> {
+    json_schema
+        .pointer(pointer)
+        .ok_or(format!("JSON Schema does not contain {pointer}"))
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_generate_json_schema_document() -> Result<(), String> {
+    let json_schema = fetch_json_schema()?;
+
+    handle_test_comparison(&json_schema["$schema"], &json!(JSON_SCHEMA_DIALECT))?;
+    handle_test_comparison(&json_schema["$id"], &json!("mock_service.schema.json"))?;
+    handle_test_comparison(&json_schema["title"], &json!("GWSAMPLE_BASIC"))?;
+
+    let defs = fetch_def(&json_schema, "/$defs")?.as_object().ok_or("$defs is not an object")?;
+    handle_test_comparison(
+        &defs.keys().cloned().collect::<Vec<String>>().join(","),
+        &"BusinessPartner,CT_Address,Product".to_owned(),
+    )?;
+
+    // Complex types are referenced from the entity types that use them
+    handle_test_comparison(
+        fetch_def(&json_schema, "/$defs/BusinessPartner/properties/Address")?,
+        &json!({ "$ref": "#/$defs/CT_Address" }),
+    )?;
+    handle_test_comparison(
+        fetch_def(&json_schema, "/$defs/BusinessPartner/required")?,
+        &json!(["BusinessPartnerID"]),
+    )?;
+    handle_test_bool(fetch_def(&json_schema, "/$defs/CT_Address/required").is_err())
+}
+
+#[test]
+pub fn should_generate_json_schema_for_properties() -> Result<(), String> {
+    let json_schema = fetch_json_schema()?;
+
+    handle_test_comparison(
+        fetch_def(&json_schema, "/$defs/Product/properties/ProductID")?,
+        &json!({ "type": "string", "maxLength": 10, "title": "Product ID" }),
+    )?;
+    handle_test_comparison(
+        fetch_def(&json_schema, "/$defs/Product/properties/Price")?,
+        &json!({ "type": ["string", "null"], "pattern": r"^-?(\d{1,13}(\.\d{0,3})?|\.\d{1,3})$", "title": "Unit Price" }),
+    )?;
+    handle_test_comparison(
+        fetch_def(&json_schema, "/$defs/Product/properties/ChangedAt")?,
+        &json!({ "type": ["string", "null"], "format": "date-time", "title": "Time Stamp" }),
+    )?;
+    handle_test_comparison(
+        fetch_def(&json_schema, "/$defs/CT_Address/properties/Country")?,
+        &json!({ "type": ["string", "null"], "maxLength": 3, "title": "Country" }),
+    )
+}
+
+#[test]
+pub fn should_express_decimal_scale_as_pattern() -> Result<(), String> {
+    // A floating point multipleOf of 0.001 would cause some validators to reject 0.3
+    let scale_3 = gen_edm_type_schema("Decimal", None, Some(16), Some(3), false, None);
+    handle_test_comparison(&scale_3["type"], &json!("string"))?;
+    handle_test_comparison(&scale_3["pattern"], &json!(r"^-?(\d{1,13}(\.\d{0,3})?|\.\d{1,3})$"))?;
+    handle_test_bool(!scale_3.contains_key("multipleOf"))?;
+
+    // Scales beyond the range of f64 are still expressed exactly
+    handle_test_comparison(
+        &gen_edm_type_schema("Decimal", None, None, Some(400), false, None)["pattern"],
+        &json!(r"^-?(\d+(\.\d{0,400})?|\.\d{1,400})$"),
+    )?;
+    handle_test_comparison(
+        &gen_edm_type_schema("Decimal", None, Some(5), None, false, None)["pattern"],
+        &json!(r"^-?\d{1,5}$"),
+    )?;
+    // Values with no integer digits have at most a zero before the decimal point
+    handle_test_comparison(
+        &gen_edm_type_schema("Decimal", None, Some(3), Some(3), false, None)["pattern"],
+        &json!(r"^-?(0(\.\d{0,3})?|\.\d{1,3})$"),
+    )?;
+    // A variable scale is only bounded by the precision
+    handle_test_comparison(
+        &gen_edm_type_schema("Decimal", None, Some(16), Some(3), true, None)["pattern"],
+        &json!(r"^-?(\d{1,16}(\.\d{0,16})?|\.\d{1,16})$"),
+    )?;
+    handle_test_comparison(
+        &gen_edm_type_schema("Decimal", None, None, None, true, None)["pattern"],
+        &json!(r"^-?(\d+(\.\d*)?|\.\d+)$"),
+    )?;
+    handle_test_comparison(
+        &Value::Object(gen_edm_type_schema("Int64", None, None, None, false, None)),
+        &json!({ "type": "string", "pattern": r"^-?\d{1,19}$" }),
+    )
+}
@@ -1,8 +1,8 @@
 use check_keyword::CheckKeyword;
+use proc_macro2::TokenStream;
+use quote::quote;
 
-use crate::parser::generate::syntax_fragments::{
-    END_BLOCK, METADATA, PROPERTY, RUSTC_ALLOW_DEAD_CODE, SEPARATOR,
-};
+use crate::parser::generate::syntax_fragments::METADATA;
 use crate::{
     edmx::data_services::schema::complex_type::ComplexType,
     parser::generate::*,
@@ -12,59 +12,59 @@ use crate::{
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// Generate metadata complex type structs, writing output into `out` and returning skipped type names
-pub fn gen_metadata_complex_types_into(out: &mut Vec<u8>, cts: &[ComplexType]) -> Vec<String> {
-    let (mut src, skipped_cts) = gen_metadata_complex_types(cts);
-    out.append(&mut src);
+pub fn gen_metadata_complex_types_into(out: &mut TokenStream, cts: &[ComplexType]) -> Vec<String> {
+    let (src, skipped_cts) = gen_metadata_complex_types(cts);
+    out.extend(src);
     skipped_cts
 }
 
-pub fn gen_metadata_complex_types(cts: &[ComplexType]) -> (Vec<u8>, Vec<String>) {
+pub fn gen_metadata_complex_types(cts: &[ComplexType]) -> (TokenStream, Vec<String>) {
     let mut skipped_cts: Vec<String> = vec![];
-    let mut ignored_cts: usize = 0;
 
-    let out_buffer: Vec<u8> =
-        cts.into_iter()
-            .enumerate()
-            .fold(gen_comment_separator_for("COMPLEX TYPES"), |mut acc, (idx, ct)| {
-                if idx > 0 && idx + ignored_cts + 1 < cts.len() {
-                    acc.extend_from_slice(SEPARATOR);
-                }
+    let out_buffer: TokenStream = cts.iter().fold(TokenStream::new(), |mut acc, ct| {
+        match gen_metadata_complex_type_src(ct) {
+            Some(ct_src) => acc.extend(ct_src),
+            // This is just a simple type pretending to have a complex
+            None => skipped_cts.push(ct.name.clone()),
+        }
 
-                // If the complex type contains only one field and that field's name suffix is a basic Rust type, then
-                // the complex type can be replaced with a single variable of the Rust type.
-                // This happens with certain SAP complex types such as `CT_String` which just contains a single field
-                // called `String`.
-                let ct_name = to_upper_camel_case(&ct.name);
+        acc
+    });
 
-                if ct.properties.len() > 1 && !ct_name.is_keyword() {
-                    let ct_name = format!("{}{}", to_upper_camel_case(&ct.name), METADATA);
-                    let mut ct_props: Vec<_> = ct.properties.iter().collect();
-                    ct_props.sort();
+    (out_buffer, skipped_cts)
+}
 
-                    acc.append(&mut gen_metadata_complex_type(&ct_name, &ct_props));
-                } else {
-                    // This is just a simple type pretending to have a complex
-                    skipped_cts.push(ct.name.clone());
-                    ignored_cts += 1;
-                }
+/// ComplexType -> Rust metadata declaration, or `None` if the complex type is just a wrapper around a basic Rust type
+pub fn gen_metadata_complex_type_src(ct: &ComplexType) -> Option<TokenStream> {
+    // If the complex type contains only one field and that field's name suffix is a basic Rust type, then
+    // the complex type can be replaced with a single variable of the Rust type.
+    // This happens with certain SAP complex types such as `CT_String` which just contains a single field
+    // called `String`.
+    let ct_name = to_upper_camel_case(&ct.name);
 
-                acc
-            });
+    if ct.properties.len() > 1 && !ct_name.is_keyword() {
+        let ct_name = format!("{}{}", ct_name, METADATA);
+        let mut ct_props: Vec<_> = ct.properties.iter().collect();
+        ct_props.sort();
 
-    (out_buffer, skipped_cts)
+        Some(gen_metadata_complex_type(&ct_name, &ct_props))
+    } else {
+        None
+    }
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// ComplexType -> Rust metadata declaration
-fn gen_metadata_complex_type(ct_name: &str, ct_props: &[&Property]) -> Vec<u8> {
-    let mut out_buffer: Vec<u8> = Vec::new();
-    out_buffer.extend_from_slice(RUSTC_ALLOW_DEAD_CODE);
-    out_buffer.extend_from_slice(&*gen_start_struct(ct_name));
+fn gen_metadata_complex_type(ct_name: &str, ct_props: &[&Property]) -> TokenStream {
+    let struct_ident = gen_ident(ct_name);
+    let fields = ct_props
+        .iter()
+        .map(|ct_prop| gen_ident(&odata_name_to_rust_safe_name(&ct_prop.odata_name)));
 
-    for ct_prop in ct_props {
-        gen_struct_field_into(&mut out_buffer, &odata_name_to_rust_safe_name(&ct_prop.odata_name), PROPERTY);
+    quote! {
+        #[allow(dead_code)]
+        pub struct #struct_ident {
+            #(pub #fields: Property,)*
+        }
     }
-
-    out_buffer.extend_from_slice(END_BLOCK);
-    out_buffer
 }
@@ -1,125 +1,162 @@
 use std::collections::BTreeSet;
 
+use proc_macro2::TokenStream;
+use quote::quote;
+
 use crate::{
+    amounts::UnitPairing,
+    analytics::AnalyticalProperty,
     edmx::data_services::schema::{complex_type::ComplexType, entity_type::EntityType, Schema},
+    hierarchy::HierarchyInfo,
     parser::generate::{syntax_fragments::*, *},
     property::metadata::PropertyType,
+    texts::TextPairing,
     utils::{odata_name_to_rust_safe_name, to_upper_camel_case},
 };
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// Generate metadata entity type structs, writing output into `out`
-pub fn gen_metadata_entity_types_into(out: &mut Vec<u8>, schema: &Schema, skipped_cts: &[String]) {
-    out.append(&mut gen_metadata_entity_types(schema, skipped_cts));
+pub fn gen_metadata_entity_types_into(out: &mut TokenStream, schema: &Schema, skipped_cts: &[String]) {
+    out.extend(gen_metadata_entity_types(schema, skipped_cts));
 }
 
-pub fn gen_metadata_entity_types(schema: &Schema, skipped_cts: &[String]) -> Vec<u8> {
-    let mut used_subtypes: BTreeSet<&[u8]> = BTreeSet::new();
-    let ets: &Vec<EntityType> = &schema.entity_types;
+pub fn gen_metadata_entity_types(schema: &Schema, skipped_cts: &[String]) -> TokenStream {
+    let mut out_buffer: TokenStream = schema
+        .entity_types
+        .iter()
+        .map(|entity| gen_metadata_entity_type_src(entity, schema, skipped_cts))
+        .collect();
 
-    let mut out_buffer: Vec<u8> = ets.into_iter().enumerate().fold(
-        // Accumulator's initial value is an EntityType comment separator
-        gen_comment_separator_for(ENTITY_TYPES),
-        |mut acc, (idx, entity)| {
-            if idx > 0 {
-                acc.extend_from_slice(SEPARATOR);
-            }
+    out_buffer.extend(gen_metadata_entity_type_uses(&schema.entity_types));
+    out_buffer
+}
 
-            // Accumulate a set of subtypes used within the SAP Annotations field of each property
-            for prop in &entity.properties {
-                used_subtypes.extend(prop.sap_annotations.used_subtypes());
-            }
+/// EDM EntityType -> Rust metadata struct and its impl
+pub fn gen_metadata_entity_type_src(entity: &EntityType, schema: &Schema, skipped_cts: &[String]) -> TokenStream {
+    let mut out_buffer = gen_metadata_entity_type(entity, skipped_cts);
+    out_buffer.extend(gen_metadata_entity_type_impl(entity, &schema.complex_types));
+    out_buffer
+}
+
+/// Generate usage declaration(s) for all subtypes used by the metadata of the entity types
+pub fn gen_metadata_entity_type_uses(ets: &[EntityType]) -> TokenStream {
+    let mut used_subtypes: BTreeSet<&str> = BTreeSet::new();
+
+    for entity in ets {
+        // Accumulate a set of subtypes used within the SAP Annotations field of each property
+        for prop in &entity.properties {
+            used_subtypes.extend(prop.sap_annotations.used_subtypes());
+        }
 
-            acc.append(&mut gen_metadata_entity_type(entity, &skipped_cts));
-            acc.append(&mut gen_metadata_entity_type_impl(entity, &schema.complex_types));
+        if !UnitPairing::find_in(&entity.properties).is_empty() {
+            used_subtypes.insert(PATH_TO_AMOUNTS_UNIT_PAIRING);
+        }
 
-            acc
-        },
-    );
+        if entity.is_aggregate() {
+            used_subtypes.insert(PATH_TO_ANALYTICS_ANALYTICAL_PROPERTY);
+        }
 
-    // Add usage declaration(s) for all subtypes across all the SAPAnnotationsProperty instances
-    for subtype in used_subtypes {
-        out_buffer.append(&mut gen_use_path(subtype));
+        if !HierarchyInfo::find_in(&entity.properties).is_empty() {
+            used_subtypes.insert(PATH_TO_HIERARCHY_INFO);
+        }
     }
 
-    out_buffer
+    used_subtypes.into_iter().map(gen_use_path).collect()
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// EDM EntityType -> Rust metadata instance
-fn gen_metadata_entity_type(entity: &EntityType, skipped_cts: &[String]) -> Vec<u8> {
-    let struct_name = format!("{}{}", to_upper_camel_case(&entity.name), METADATA);
-    let key_type = gen_vector_of_type(PROPERTYREF);
-
-    let mut out_buffer: Vec<u8> = Vec::new();
-    out_buffer.extend_from_slice(RUSTC_ALLOW_DEAD_CODE);
-    out_buffer.extend_from_slice(&*gen_start_struct(&struct_name));
-
-    gen_struct_field_into(&mut out_buffer, FIELD_NAME_KEY, &key_type);
+fn gen_metadata_entity_type(entity: &EntityType, skipped_cts: &[String]) -> TokenStream {
+    let struct_ident = gen_ident(&format!("{}{}", to_upper_camel_case(&entity.name), METADATA));
 
     let mut props: Vec<_> = entity.properties.iter().collect();
     props.sort();
 
     // Metadata fields are either of type Property or of some complex type
-    for prop in props {
-        let prop_name = odata_name_to_rust_safe_name(&prop.odata_name);
+    let fields = props.into_iter().filter_map(|prop| {
+        let prop_ident = gen_ident(&odata_name_to_rust_safe_name(&prop.odata_name));
 
         match prop.get_property_type() {
-            PropertyType::Edm(_, _) => {
-                gen_struct_field_into(&mut out_buffer, &prop_name, PROPERTY);
-            },
+            PropertyType::Edm(_, _) => Some(quote!(pub #prop_ident: Property,)),
 
             PropertyType::Complex(cmplx_type) => {
                 // Is the current property really a complex type or just a wrapper around a basic Rust type?
                 if skipped_cts.contains(&cmplx_type) {
                     // A basic Rust type's metadata is a Property instance
-                    gen_struct_field_into(&mut out_buffer, &prop_name, PROPERTY);
+                    Some(quote!(pub #prop_ident: Property,))
                 } else {
                     // This really is a complex type
-                    let metadata_type_name =
-                        [to_upper_camel_case(&cmplx_type).as_bytes(), METADATA.as_bytes()].concat();
-
-                    gen_struct_field_into(&mut out_buffer, &prop_name, &metadata_type_name);
+                    let metadata_type_ident = gen_ident(&format!("{}{METADATA}", to_upper_camel_case(&cmplx_type)));
+                    Some(quote!(pub #prop_ident: #metadata_type_ident,))
                 }
             },
 
             // We should never end up here if the metadata XML is correct...
-            PropertyType::Unqualified => {},
+            PropertyType::Unqualified => None,
         }
-    }
+    });
 
-    out_buffer.extend_from_slice(END_BLOCK);
-    out_buffer
+    quote! {
+        #[allow(dead_code)]
+        pub struct #struct_ident {
+            pub key: Vec<PropertyRef>,
+            #(#fields)*
+        }
+    }
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// Generates the metadata getter functions for each property in the impl of an EntityType
-fn gen_metadata_entity_type_impl(entity: &EntityType, opt_cts: &Option<Vec<ComplexType>>) -> Vec<u8> {
-    let struct_name = format!("{}{METADATA}", to_upper_camel_case(&entity.name));
-    let mut out_buffer: Vec<u8> = gen_impl_start_for(&struct_name);
+fn gen_metadata_entity_type_impl(entity: &EntityType, opt_cts: &Option<Vec<ComplexType>>) -> TokenStream {
+    let struct_ident = gen_ident(&format!("{}{METADATA}", to_upper_camel_case(&entity.name)));
     let keys = &entity.key.property_refs;
+    let mut fns: Vec<TokenStream> = Vec::new();
 
     // Add a get_key function
-    out_buffer.extend_from_slice(&gen_fn_signature(
-        KEY,
-        true,
-        false,
-        None,
-        Some(&gen_vector_of_type(PROPERTYREF)),
-    ));
-    out_buffer.extend_from_slice(OPEN_CURLY);
-    out_buffer.extend_from_slice(LINE_FEED);
-    out_buffer.extend_from_slice(VEC_BANG);
-    out_buffer.extend_from_slice(
-        keys.into_iter()
-            .map(|pr| format!("{pr}"))
-            .collect::<Vec<_>>()
-            .join(",")
-            .as_bytes(),
-    );
-    out_buffer.extend_from_slice(CLOSE_SQR);
-    out_buffer.extend_from_slice(CLOSE_CURLY);
-    out_buffer.extend_from_slice(LINE_FEED);
+    fns.push(quote! {
+        pub fn key() -> Vec<PropertyRef> {
+            vec![#(#keys),*]
+        }
+    });
+
+    // Add a get_unit_pairings function if any amount or quantity fields reference a currency code or unit of measure
+    let unit_pairings = UnitPairing::find_in(&entity.properties);
+
+    if !unit_pairings.is_empty() {
+        fns.push(quote! {
+            pub fn get_unit_pairings() -> Vec<UnitPairing> {
+                vec![#(#unit_pairings),*]
+            }
+        });
+    }
+
+    // Add a get_analytical_properties function if this is an aggregate entity type
+    if entity.is_aggregate() {
+        let analytical_props = AnalyticalProperty::find_in(&entity.properties);
+        fns.push(quote! {
+            pub fn get_analytical_properties() -> Vec<AnalyticalProperty> {
+                vec![#(#analytical_props),*]
+            }
+        });
+    }
+
+    // Add a get_hierarchies function if any properties are annotated with sap:hierarchy-node-for
+    let hierarchies = HierarchyInfo::find_in(&entity.properties);
+
+    if !hierarchies.is_empty() {
+        fns.push(quote! {
+            pub fn get_hierarchies() -> Vec<HierarchyInfo> {
+                vec![#(#hierarchies),*]
+            }
+        });
+    }
+
+    // Add a text_property_for function if any fields have a descriptive text field
+    let text_pairings = TextPairing::find_in(&entity.properties);
+
+    if !text_pairings.is_empty() {
+        fns.push(gen_text_property_for_fn(&text_pairings));
+    }
 
     let mut props: Vec<_> = entity.properties.iter().collect();
     props.sort();
@@ -127,15 +164,13 @@ fn gen_metadata_entity_type_impl(entity: &EntityType, opt_cts: &Option<Vec<Compl
     // One getter function per property
     for prop in props {
         let safe_name = odata_name_to_rust_safe_name(&prop.odata_name);
-        let mut fn_name: Vec<u8> = Vec::new();
-        fn_name.extend_from_slice(PREFIX_SNAKE_GET.as_ref());
-        fn_name.extend_from_slice(safe_name.as_ref());
+        let fn_name = format!("{PREFIX_SNAKE_GET}{}", safe_name.trim_start_matches("r#"));
 
         let mut prop = prop.clone();
         prop.deserializer_fn = gen_custom_deserializer_info(&prop);
 
         match prop.get_property_type() {
-            PropertyType::Edm(_, _) => gen_pub_getter_fn_of_type_into(&mut out_buffer, &fn_name, PROPERTY, &prop),
+            PropertyType::Edm(_, _) => fns.push(gen_pub_getter_fn_of_type(&fn_name, quote!(Property), &prop)),
 
             PropertyType::Complex(cmplx_type) => {
                 let err_msg = format!(
@@ -144,7 +179,7 @@ fn gen_metadata_entity_type_impl(entity: &EntityType, opt_cts: &Option<Vec<Compl
 
                 if let Some(cts) = opt_cts {
                     if let Some(ct) = cts.iter().find(|ct| ct.name.eq(&cmplx_type)) {
-                        gen_pub_getter_fn_of_type_into(&mut out_buffer, &fn_name, COMPLEX_TYPE, ct);
+                        fns.push(gen_pub_getter_fn_of_type(&fn_name, quote!(ComplexType), ct));
                     } else {
                         println!("{err_msg}");
                         println!(
@@ -161,6 +196,36 @@ fn gen_metadata_entity_type_impl(entity: &EntityType, opt_cts: &Option<Vec<Compl
         }
     }
 
-    out_buffer.extend_from_slice(END_BLOCK);
-    out_buffer
+    quote! {
+        impl #struct_ident {
+            #(#fns)*
+        }
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generates a function that maps the name of a field to the OData name of the field holding its descriptive text.
+/// The field can be identified either by its OData name or by its Rust field name
+fn gen_text_property_for_fn(text_pairings: &[TextPairing]) -> TokenStream {
+    let arms = text_pairings.iter().map(|tp| {
+        let safe_name = odata_name_to_rust_safe_name(&tp.value_property);
+        let value_property = &tp.value_property;
+        let text_property = &tp.text_property;
+        let pattern = if safe_name.eq(value_property) {
+            quote!(#value_property)
+        } else {
+            quote!(#value_property | #safe_name)
+        };
+
+        quote!(#pattern => Some(#text_property),)
+    });
+
+    quote! {
+        pub fn text_property_for(field: &str) -> Option<&'static str> {
+            match field {
+                #(#arms)*
+                _ => None,
+            }
+        }
+    }
 }
@@ -2,12 +2,15 @@ mod associations;
 mod complex_types;
 mod entity_types;
 
+use std::path::Path;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
 use crate::{
     edmx::data_services::schema::Schema,
-    parser::generate::{
-        gen_extern_crate_into, gen_module_start_into,
-        syntax_fragments::{gen_use_path_into, *},
-    },
+    parser::generate::{gen_extern_crate, gen_ident, gen_use_path, module_tree::ModuleTree, syntax_fragments::*},
+    utils::odata_name_to_rust_safe_name,
 };
 
 use super::CRATE_PARSE_SAP_ATOM_FEED;
@@ -18,29 +21,86 @@ use entity_types::*;
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// Generate a module containing the metadata structs and their respective instances
-pub fn gen_metadata_module(odata_srv_name: &str, schema: &Schema) -> Vec<u8> {
-    let mod_name = format!("{odata_srv_name}{SUFFIX_SNAKE_METADATA}");
-    let mut out_buffer = Vec::new();
-
-    // Start module definition
-    gen_extern_crate_into(&mut out_buffer, CRATE_PARSE_SAP_ATOM_FEED);
-    gen_module_start_into(&mut out_buffer, &mod_name);
-    gen_use_path_into(&mut out_buffer, PATH_TO_SAP_ODATA_PROPERTIES);
-    gen_use_path_into(&mut out_buffer, PATH_TO_SAP_ANNOTATIONS_PROPERTY);
+pub fn gen_metadata_module(odata_srv_name: &str, schema: &Schema) -> TokenStream {
+    let mod_ident = gen_ident(&format!("{odata_srv_name}{SUFFIX_SNAKE_METADATA}"));
+    let extern_crate = gen_extern_crate(CRATE_PARSE_SAP_ATOM_FEED);
+    let mut mod_body = gen_use_path(PATH_TO_SAP_ODATA_PROPERTIES);
+    mod_body.extend(gen_use_path(PATH_TO_SAP_ANNOTATIONS_PROPERTY));
 
     // Do we need to generate any complex types?
     let skipped_cts = if let Some(cts) = &schema.complex_types {
-        gen_use_path_into(&mut out_buffer, PATH_TO_EDMX_COMPLEX_TYPE);
-        gen_metadata_complex_types_into(&mut out_buffer, cts)
+        mod_body.extend(gen_use_path(PATH_TO_EDMX_COMPLEX_TYPE));
+        gen_metadata_complex_types_into(&mut mod_body, cts)
     } else {
         Vec::new()
     };
 
-    gen_metadata_entity_types_into(&mut out_buffer, &schema, &skipped_cts);
-    gen_metadata_associations_into(&mut out_buffer, odata_srv_name, &schema);
-    gen_metadata_association_sets_into(&mut out_buffer, odata_srv_name, &schema);
+    gen_metadata_entity_types_into(&mut mod_body, schema, &skipped_cts);
+    gen_metadata_associations_into(&mut mod_body, odata_srv_name, schema);
+    gen_metadata_association_sets_into(&mut mod_body, odata_srv_name, schema);
+
+    quote! {
+        #extern_crate
+        pub mod #mod_ident {
+            #mod_body
+        }
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate the metadata module as the file `metadata/mod.rs` below `srv_dir`, with the metadata of each complex type
+/// and entity type in a file of its own.  The files are added to `tree`, and the source code declaring the metadata
+/// module is returned
+pub fn gen_metadata_module_tree(odata_srv_name: &str, schema: &Schema, tree: &mut ModuleTree, srv_dir: &Path) -> TokenStream {
+    let metadata_dir = srv_dir.join(DIR_METADATA);
+    let extern_crate = gen_extern_crate(CRATE_PARSE_SAP_ATOM_FEED);
+    let mut mod_body = gen_use_path(PATH_TO_SAP_ODATA_PROPERTIES);
+    mod_body.extend(gen_use_path(PATH_TO_SAP_ANNOTATIONS_PROPERTY));
+
+    let mut skipped_cts: Vec<String> = Vec::new();
+
+    if let Some(cts) = &schema.complex_types {
+        mod_body.extend(gen_use_path(PATH_TO_EDMX_COMPLEX_TYPE));
+
+        let ct_mods: Vec<(String, TokenStream)> = cts
+            .iter()
+            .filter_map(|ct| match gen_metadata_complex_type_src(ct) {
+                Some(ct_src) => Some((odata_name_to_rust_safe_name(&ct.name), ct_src)),
+                None => {
+                    skipped_cts.push(ct.name.clone());
+                    None
+                },
+            })
+            .collect();
+
+        mod_body.extend(tree.add_types_module(metadata_dir.join(DIR_COMPLEX_TYPES), DIR_COMPLEX_TYPES, ct_mods));
+    }
+
+    let et_mods: Vec<(String, TokenStream)> = schema
+        .entity_types
+        .iter()
+        .map(|entity| {
+            (
+                odata_name_to_rust_safe_name(&entity.name),
+                gen_metadata_entity_type_src(entity, schema, &skipped_cts),
+            )
+        })
+        .collect();
+
+    mod_body.extend(tree.add_types_module(metadata_dir.join(DIR_ENTITY_TYPES), DIR_ENTITY_TYPES, et_mods));
+    mod_body.extend(gen_metadata_entity_type_uses(&schema.entity_types));
+    gen_metadata_associations_into(&mut mod_body, odata_srv_name, schema);
+    gen_metadata_association_sets_into(&mut mod_body, odata_srv_name, schema);
+
+    let mod_decl = tree.add_module(
+        metadata_dir.join(MOD_RS),
+        &format!("{odata_srv_name}{SUFFIX_SNAKE_METADATA}"),
+        true,
+        mod_body,
+    );
 
-    // Close module definition
-    out_buffer.extend_from_slice(END_BLOCK);
-    out_buffer
+    quote! {
+        #extern_crate
+        #mod_decl
+    }
 }
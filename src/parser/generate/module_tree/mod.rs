@@ -0,0 +1,105 @@
+use std::path::{Path, PathBuf};
+
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    edmx::data_services::schema::Schema,
+    parser::{
+        generate::{
+            gen_ident, metadata_doc::gen_metadata_module_tree, srvc_doc::gen_srv_doc_module_tree,
+            syntax_fragments::MOD_RS,
+        },
+        options::GeneratorOptions,
+    },
+};
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// A Rust source file belonging to a module tree
+pub struct ModuleFile {
+    /// The path of the file relative to the directory in which the module tree is written
+    pub path: PathBuf,
+    pub tokens: TokenStream,
+}
+
+/// The Rust source files into which the service document and metadata modules are split
+pub struct ModuleTree {
+    /// The directory in which the module tree is written (normally `$OUT_DIR`)
+    pub out_dir: PathBuf,
+    pub files: Vec<ModuleFile>,
+}
+
+impl ModuleTree {
+    pub fn new(out_dir: &Path) -> ModuleTree {
+        ModuleTree {
+            out_dir: out_dir.to_path_buf(),
+            files: Vec::new(),
+        }
+    }
+
+    /// Add the source file of a module to the tree and return the declaration of that module.
+    ///
+    /// The root of the tree is brought into scope using `include!()`, which resolves the file of a module declaration
+    /// relative to the file that calls `include!()`.  Therefore, the declaration always names the absolute path of the
+    /// module's file
+    pub fn add_module(&mut self, path: PathBuf, mod_name: &str, is_pub: bool, tokens: TokenStream) -> TokenStream {
+        let abs_path = self.out_dir.join(&path).display().to_string();
+        let mod_ident = gen_ident(mod_name);
+        let visibility = if is_pub { quote!(pub) } else { TokenStream::new() };
+
+        self.files.push(ModuleFile { path, tokens });
+
+        quote! {
+            #[path = #abs_path]
+            #visibility mod #mod_ident;
+        }
+    }
+
+    /// Add a module that holds one child module per type, each of which is re-exported
+    ///
+    /// `types` yields the name of each child module together with its content
+    pub fn add_types_module<I>(&mut self, dir: PathBuf, mod_name: &str, types: I) -> TokenStream
+    where
+        I: IntoIterator<Item = (String, TokenStream)>,
+    {
+        let mut mod_body = quote!(use super::*;);
+
+        for (type_mod_name, type_src) in types {
+            let type_mod_ident = gen_ident(&type_mod_name);
+            let type_file = dir.join(format!("{}.rs", type_mod_name.trim_start_matches("r#")));
+
+            mod_body.extend(self.add_module(type_file, &type_mod_name, false, quote!(use super::*; #type_src)));
+            mod_body.extend(quote!(pub use #type_mod_ident::*;));
+        }
+
+        let mod_ident = gen_ident(mod_name);
+        let mut mod_decl = self.add_module(dir.join(MOD_RS), mod_name, false, mod_body);
+        mod_decl.extend(quote!(pub use #mod_ident::*;));
+        mod_decl
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Split the service document and metadata modules into a tree of module files written below `<odata_srv_name>/` in
+/// `out_dir`.
+///
+/// The root of the tree is `<odata_srv_name>/mod.rs`, which declares both the service document module and the metadata
+/// module.  The generated types can be used through the same paths as when each module is written to a single file
+pub fn gen_module_tree(odata_srv_name: &str, schema: &Schema, options: &GeneratorOptions, out_dir: &Path) -> ModuleTree {
+    let mut tree = ModuleTree::new(out_dir);
+    let srv_dir = PathBuf::from(odata_srv_name);
+
+    let mut root_src = gen_srv_doc_module_tree(odata_srv_name, schema, options, &mut tree, &srv_dir);
+    root_src.extend(gen_metadata_module_tree(odata_srv_name, schema, &mut tree, &srv_dir));
+
+    tree.files.push(ModuleFile {
+        path: srv_dir.join(MOD_RS),
+        tokens: root_src,
+    });
+
+    tree
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(test)]
+pub mod unit_tests;
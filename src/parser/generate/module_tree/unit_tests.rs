@@ -0,0 +1,81 @@
+use std::path::PathBuf;
+
+use super::*;
+use crate::test_utils::*;
+
+static OUT_DIR: &str = "/tmp/out";
+
+fn fetch_module_tree() -> Result<ModuleTree, String> {
+    let edmx = fetch_mock_service_edmx()?;
+    let schema = edmx
+        .data_services
+        .fetch_schema("GWSAMPLE_BASIC")
+        .ok_or("Schema GWSAMPLE_BASIC not found")?;
+
+    Ok(gen_module_tree(
+        "gwsample_basic",
+        schema,
+        &GeneratorOptions::default(),
+        Path::new(OUT_DIR),
+    ))
+}
+
+fn fetch_file_src(tree: &ModuleTree, path: &str) -> Result<String, String> {
+    tree.files
+        .iter()
+        .find(|module_file| module_file.path.eq(&PathBuf::from(path)))
+        .map(|module_file| tokens_to_rust_src(module_file.tokens.clone()).join("\n"))
+        .ok_or(format!("Module file {path} not found"))
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_generate_one_file_per_type() -> Result<(), String> {
+    let tree = fetch_module_tree()?;
+
+    for path in [
+        "gwsample_basic/mod.rs",
+        "gwsample_basic/complex_types/mod.rs",
+        "gwsample_basic/complex_types/ct_address.rs",
+        "gwsample_basic/entity_types/mod.rs",
+        "gwsample_basic/entity_types/business_partner.rs",
+        "gwsample_basic/entity_types/product.rs",
+        "gwsample_basic/entity_sets.rs",
+        "gwsample_basic/metadata/mod.rs",
+        "gwsample_basic/metadata/complex_types/ct_address.rs",
+        "gwsample_basic/metadata/entity_types/product.rs",
+    ] {
+        fetch_file_src(&tree, path)?;
+    }
+
+    handle_test_comparison(&tree.files.len(), &13)
+}
+
+#[test]
+pub fn should_declare_modules_with_absolute_paths() -> Result<(), String> {
+    let tree = fetch_module_tree()?;
+    let root_src = fetch_file_src(&tree, "gwsample_basic/mod.rs")?;
+
+    handle_test_bool(root_src.contains("pub mod gwsample_basic {"))?;
+    handle_test_bool(root_src.contains(
+        "#[path = \"/tmp/out/gwsample_basic/entity_types/mod.rs\"]\nmod entity_types;\npub use entity_types::*;",
+    ))?;
+    handle_test_bool(root_src.contains("#[path = \"/tmp/out/gwsample_basic/entity_sets.rs\"]\npub mod entity_sets;"))?;
+    handle_test_bool(
+        root_src.contains("#[path = \"/tmp/out/gwsample_basic/metadata/mod.rs\"]\npub mod gwsample_basic_metadata;"),
+    )
+}
+
+#[test]
+pub fn should_generate_type_in_own_file() -> Result<(), String> {
+    let tree = fetch_module_tree()?;
+    let et_src = fetch_file_src(&tree, "gwsample_basic/entity_types/product.rs")?;
+    let mod_src = fetch_file_src(&tree, "gwsample_basic/entity_types/mod.rs")?;
+    let metadata_src = fetch_file_src(&tree, "gwsample_basic/metadata/entity_types/product.rs")?;
+
+    handle_test_bool(et_src.starts_with("use super::*;"))?;
+    handle_test_bool(et_src.contains("pub struct Product {"))?;
+    handle_test_bool(!et_src.contains("pub struct BusinessPartner {"))?;
+    handle_test_bool(mod_src.contains("mod product;\npub use product::*;"))?;
+    handle_test_bool(metadata_src.contains("pub struct ProductMetadata {"))
+}
@@ -0,0 +1,411 @@
+use serde_json::{json, Map, Value};
+
+use crate::{
+    edmx::data_services::schema::{
+        entity_container::{
+            entity_set::EntitySet,
+            function_import::{parameter::Parameter, FunctionImport},
+        },
+        entity_type::EntityType,
+        Schema,
+    },
+    parser::generate::json_schema::{gen_edm_type_schema, gen_property_schema, gen_type_schemas},
+    property::Property,
+};
+
+static OPENAPI_VERSION: &str = "3.1.0";
+static OPENAPI_SCHEMAS_PREFIX: &str = "#/components/schemas/";
+static MEDIA_TYPE_JSON: &str = "application/json";
+static DEFAULT_SERVICE_ROOT: &str = "/sap/opu/odata/sap/";
+static COLLECTION_PREFIX: &str = "Collection(";
+
+pub static SUFFIX_OPENAPI: &str = ".openapi.json";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Strip the namespace from a qualified name such as `GWSAMPLE_BASIC.Product`
+fn unqualified_name(qualified_name: &str) -> &str {
+    qualified_name.rsplit_once('.').map_or(qualified_name, |(_, name)| name)
+}
+
+fn schema_ref(type_name: &str) -> Value {
+    json!({ "$ref": format!("{OPENAPI_SCHEMAS_PREFIX}{}", unqualified_name(type_name)) })
+}
+
+// OData V2 JSON responses wrap their content in an object called `d`
+fn json_response(description: &str, content_schema: Value) -> Value {
+    json!({
+        "description": description,
+        "content": {
+            MEDIA_TYPE_JSON: {
+                "schema": { "type": "object", "properties": { "d": content_schema } }
+            }
+        }
+    })
+}
+
+fn collection_schema(items_schema: Value) -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "results": { "type": "array", "items": items_schema },
+            "__count": { "type": "string" }
+        }
+    })
+}
+
+fn query_parameter(name: &str, description: &str, required: bool, schema: Value) -> Value {
+    json!({
+        "name": name,
+        "in": "query",
+        "description": description,
+        "required": required,
+        "schema": schema
+    })
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// The query options accepted when reading an entity set, as permitted by the entity set's SAP annotations
+fn gen_query_options(es: &EntitySet, et: &EntityType) -> Vec<Value> {
+    let sap_annos = &es.sap_annotations;
+    let mut options = vec![query_parameter(
+        "$filter",
+        "Filter items by property values",
+        sap_annos.requires_filter,
+        json!({ "type": "string" }),
+    )];
+
+    if sap_annos.is_topable {
+        options.push(query_parameter(
+            "$top",
+            "Show only the first n items",
+            false,
+            json!({ "type": "integer", "minimum": 0 }),
+        ));
+    }
+
+    if sap_annos.is_pageable {
+        options.push(query_parameter(
+            "$skip",
+            "Skip the first n items",
+            false,
+            json!({ "type": "integer", "minimum": 0 }),
+        ));
+    }
+
+    if sap_annos.is_countable {
+        options.push(query_parameter(
+            "$inlinecount",
+            "Include the number of items",
+            false,
+            json!({ "type": "string", "enum": ["allpages", "none"] }),
+        ));
+    }
+
+    if sap_annos.is_searchable {
+        options.push(query_parameter(
+            "search",
+            "Search items by search phrases",
+            false,
+            json!({ "type": "string" }),
+        ));
+    }
+
+    if et.properties.iter().any(|prop| prop.sap_annotations.is_sortable) {
+        options.push(query_parameter(
+            "$orderby",
+            "Order items by property values",
+            false,
+            json!({ "type": "string" }),
+        ));
+    }
+
+    options.push(query_parameter(
+        "$select",
+        "Select properties to be returned",
+        false,
+        json!({ "type": "string" }),
+    ));
+
+    if !et.navigations.is_empty() {
+        options.push(query_parameter(
+            "$expand",
+            "Expand related entities",
+            false,
+            json!({ "type": "string" }),
+        ));
+    }
+
+    options
+}
+
+// The key predicate of an entity set's path template, E.G. `('{ProductID}')` or `(SalesOrderID='{SalesOrderID}',ItemPosition='{ItemPosition}')`
+fn gen_key_predicate(key_props: &[&Property]) -> String {
+    let literal = |prop: &Property| {
+        let name = &prop.odata_name;
+
+        match prop.edm_type.as_str() {
+            "Edm.String" => format!("'{{{name}}}'"),
+            "Edm.Guid" => format!("guid'{{{name}}}'"),
+            "Edm.DateTime" => format!("datetime'{{{name}}}'"),
+            "Edm.DateTimeOffset" => format!("datetimeoffset'{{{name}}}'"),
+            "Edm.Time" => format!("time'{{{name}}}'"),
+            "Edm.Int64" => format!("{{{name}}}L"),
+            "Edm.Decimal" => format!("{{{name}}}M"),
+            _ => format!("{{{name}}}"),
+        }
+    };
+
+    if key_props.len() == 1 {
+        format!("({})", literal(key_props[0]))
+    } else {
+        let pairs: Vec<String> = key_props
+            .iter()
+            .map(|prop| format!("{}={}", prop.odata_name, literal(prop)))
+            .collect();
+        format!("({})", pairs.join(","))
+    }
+}
+
+fn gen_entity_set_paths_into(paths: &mut Map<String, Value>, es: &EntitySet, et: &EntityType) {
+    let sap_annos = &es.sap_annotations;
+    let entity_ref = schema_ref(&es.entity_type);
+    let tag = json!([es.name]);
+
+    // Collection path
+    let mut collection_ops = Map::new();
+
+    if sap_annos.is_addressable {
+        collection_ops.insert(
+            "get".to_owned(),
+            json!({
+                "summary": format!("Get entities from {}", es.name),
+                "tags": tag,
+                "parameters": gen_query_options(es, et),
+                "responses": { "200": json_response("Retrieved entities", collection_schema(entity_ref.clone())) }
+            }),
+        );
+    }
+
+    if sap_annos.is_creatable {
+        collection_ops.insert(
+            "post".to_owned(),
+            json!({
+                "summary": format!("Add new entity to {}", es.name),
+                "tags": tag,
+                "requestBody": {
+                    "required": true,
+                    "content": { MEDIA_TYPE_JSON: { "schema": entity_ref.clone() } }
+                },
+                "responses": { "201": json_response("Created entity", entity_ref.clone()) }
+            }),
+        );
+    }
+
+    if !collection_ops.is_empty() {
+        paths.insert(format!("/{}", es.name), Value::Object(collection_ops));
+    }
+
+    if sap_annos.is_countable {
+        paths.insert(
+            format!("/{}/$count", es.name),
+            json!({
+                "get": {
+                    "summary": format!("Get the number of entities in {}", es.name),
+                    "tags": tag,
+                    "parameters": [query_parameter(
+                        "$filter",
+                        "Filter items by property values",
+                        sap_annos.requires_filter,
+                        json!({ "type": "string" }),
+                    )],
+                    "responses": {
+                        "200": {
+                            "description": "The number of entities",
+                            "content": { "text/plain": { "schema": { "type": "integer", "minimum": 0 } } }
+                        }
+                    }
+                }
+            }),
+        );
+    }
+
+    // Key path
+    let key_props: Vec<&Property> = et
+        .key
+        .property_refs
+        .iter()
+        .filter_map(|key| et.properties.iter().find(|prop| prop.odata_name.eq(&key.name)))
+        .collect();
+
+    if key_props.is_empty() {
+        return;
+    }
+
+    let key_params: Vec<Value> = key_props
+        .iter()
+        .map(|prop| {
+            json!({
+                "name": prop.odata_name,
+                "in": "path",
+                "required": true,
+                "schema": gen_property_schema(prop, OPENAPI_SCHEMAS_PREFIX)
+            })
+        })
+        .collect();
+    let mut entity_ops = Map::new();
+    entity_ops.insert("parameters".to_owned(), json!(key_params));
+
+    entity_ops.insert(
+        "get".to_owned(),
+        json!({
+            "summary": format!("Get entity from {} by key", es.name),
+            "tags": tag,
+            "responses": { "200": json_response("Retrieved entity", entity_ref.clone()) }
+        }),
+    );
+
+    // SAP Gateway accepts PATCH as an alternative to the OData V2 MERGE method
+    if sap_annos.is_updatable {
+        entity_ops.insert(
+            "patch".to_owned(),
+            json!({
+                "summary": format!("Update entity in {}", es.name),
+                "tags": tag,
+                "requestBody": {
+                    "required": true,
+                    "content": { MEDIA_TYPE_JSON: { "schema": entity_ref } }
+                },
+                "responses": { "204": { "description": "Success" } }
+            }),
+        );
+    }
+
+    if sap_annos.is_deletable {
+        entity_ops.insert(
+            "delete".to_owned(),
+            json!({
+                "summary": format!("Delete entity from {}", es.name),
+                "tags": tag,
+                "responses": { "204": { "description": "Success" } }
+            }),
+        );
+    }
+
+    paths.insert(
+        format!("/{}{}", es.name, gen_key_predicate(&key_props)),
+        Value::Object(entity_ops),
+    );
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn gen_type_ref_schema(type_name: &str) -> Value {
+    match type_name.strip_prefix("Edm.") {
+        Some(edm_type) => Value::Object(gen_edm_type_schema(edm_type, None, None, None, false, None)),
+        None => schema_ref(type_name),
+    }
+}
+
+fn gen_function_parameter(param: &Parameter) -> Value {
+    let max_length = param.max_length.as_ref().and_then(|max_length| max_length.parse::<u16>().ok());
+    let edm_type = param.parameter_type.strip_prefix("Edm.").unwrap_or(&param.parameter_type);
+    let mut description = format!("Value of type {}", param.parameter_type);
+
+    // Function import parameters are passed as URI literals, so strings must be enclosed in single quotes
+    if edm_type.eq("String") {
+        description = format!("{description} enclosed in single quotes");
+    }
+
+    let mut parameter = json!({
+        "name": param.parameter_name,
+        "in": "query",
+        "description": description,
+        "required": !param.nullable,
+        "schema": gen_edm_type_schema(edm_type, max_length, None, None, false, None)
+    });
+
+    if let Some(label) = &param.sap_annotations.label {
+        parameter["schema"]["title"] = json!(label);
+    }
+
+    parameter
+}
+
+fn gen_function_import_path_into(paths: &mut Map<String, Value>, fi: &FunctionImport) {
+    let return_schema = match fi
+        .return_type
+        .strip_prefix(COLLECTION_PREFIX)
+        .and_then(|item_type| item_type.strip_suffix(')'))
+    {
+        Some(item_type) => collection_schema(gen_type_ref_schema(item_type)),
+        None => match fi.return_type.strip_prefix("Edm.") {
+            // A primitive value is returned as a property named after the function import
+            Some(_) => {
+                json!({ "type": "object", "properties": { (fi.name.clone()): gen_type_ref_schema(&fi.return_type) } })
+            },
+            None => gen_type_ref_schema(&fi.return_type),
+        },
+    };
+
+    let mut operation = json!({
+        "summary": fi.sap_annotations.label.clone().unwrap_or(format!("Invoke function {}", fi.name)),
+        "tags": [fi.entity_set.clone().unwrap_or("Service Operations".to_owned())],
+        "parameters": fi.parameters.iter().flatten().map(gen_function_parameter).collect::<Vec<Value>>(),
+        "responses": { "200": json_response("Success", return_schema) }
+    });
+
+    if let Some(action_for) = &fi.sap_annotations.action_for {
+        operation["description"] = json!(format!("Action for {}", unqualified_name(action_for)));
+    }
+
+    paths.insert(format!("/{}", fi.name), json!({ (fi.http_method.to_lowercase()): operation }));
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate an OpenAPI 3 document describing the entity sets and function imports of the schema's entity container.
+///
+/// * Each entity set has a collection path, a `$count` path and a key path, with operations and query options that
+///   respect the entity set's SAP annotations
+/// * Each function import has a path whose operation uses the function import's HTTP method
+/// * The JSON Schema of each entity type and complex type is declared in `components/schemas`
+///
+/// The service root is declared as the server variable `serviceRoot` and can be changed in tools such as Swagger UI
+pub fn gen_openapi(odata_srv_name: &str, schema: &Schema) -> Vec<u8> {
+    let mut paths = Map::new();
+
+    if let Some(ent_cont) = &schema.entity_container {
+        for es in &ent_cont.entity_sets {
+            let et_name = unqualified_name(&es.entity_type);
+
+            if let Some(et) = schema.entity_types.iter().find(|et| et.name.eq(et_name)) {
+                gen_entity_set_paths_into(&mut paths, es, et);
+            }
+        }
+
+        for fi in ent_cont.function_imports.iter().flatten() {
+            gen_function_import_path_into(&mut paths, fi);
+        }
+    }
+
+    let document = json!({
+        "openapi": OPENAPI_VERSION,
+        "info": {
+            "title": odata_srv_name,
+            "description": format!("OData V2 service with namespace {}", schema.namespace),
+            "version": schema.sap_annotations.schema_version
+        },
+        "servers": [{
+            "url": "{serviceRoot}",
+            "variables": {
+                "serviceRoot": { "default": format!("{DEFAULT_SERVICE_ROOT}{}", schema.namespace) }
+            }
+        }],
+        "paths": paths,
+        "components": { "schemas": gen_type_schemas(schema, OPENAPI_SCHEMAS_PREFIX) }
+    });
+
+    serde_json::to_vec_pretty(&document).unwrap_or_default()
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(test)]
+pub mod unit_tests;
@@ -0,0 +1,107 @@
+use serde_json::{json, Value};
+
+use super::*;
+use crate::test_utils::*;
+
+fn fetch_openapi() -> Result<Value, String> {
+    let edmx = fetch_mock_service_edmx()?;
+    let schema = edmx
+        .data_services
+        .fetch_schema("GWSAMPLE_BASIC")
+        .ok_or("Schema GWSAMPLE_BASIC not found")?;
+
+    serde_json::from_slice(&gen_openapi("mock_service", schema)).map_err(|err| err.to_string())
+}
+
+fn fetch_node<'a>(openapi: &'a Value, pointer: &str) -> Result<&'a Value, String> {
+    openapi
+        .pointer(pointer)
+        .ok_or(format!("OpenAPI document does not contain {pointer}"))
+}
+
+fn parameter_names(operation: &Value) -> String {
+    operation["parameters"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|param| param["name"].as_str())
+        .collect::<Vec<&str>>()
+        .join(",")
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_generate_entity_set_paths() -> Result<(), String> {
+    let openapi = fetch_openapi()?;
+
+    handle_test_comparison(&openapi["openapi"], &json!(OPENAPI_VERSION))?;
+    handle_test_comparison(
+        &openapi["servers"][0]["variables"]["serviceRoot"]["default"],
+        &json!("/sap/opu/odata/sap/GWSAMPLE_BASIC"),
+    )?;
+
+    let paths = fetch_node(&openapi, "/paths")?.as_object().ok_or("paths is not an object")?;
+    handle_test_comparison(
+        &paths.keys().cloned().collect::<Vec<String>>().join(","),
+        &"/BusinessPartnerSet,/BusinessPartnerSet('{BusinessPartnerID}'),/BusinessPartnerSet/$count,\
+          /FindProducts,/ProductSet,/ProductSet('{ProductID}'),/ProductSet/$count,/RegenerateAllData"
+            .to_owned(),
+    )?;
+
+    // Operations are only offered if the entity set's capabilities permit them
+    let bp_entity = fetch_node(&openapi, "/paths/~1BusinessPartnerSet('{BusinessPartnerID}')")?;
+    handle_test_bool(bp_entity.get("patch").is_some())?;
+    handle_test_bool(bp_entity.get("delete").is_none())?;
+    handle_test_bool(fetch_node(&openapi, "/paths/~1ProductSet('{ProductID}')/delete").is_ok())?;
+
+    handle_test_comparison(
+        &parameter_names(fetch_node(&openapi, "/paths/~1BusinessPartnerSet/get")?),
+        &"$filter,$top,$skip,$inlinecount,search,$orderby,$select,$expand".to_owned(),
+    )?;
+    handle_test_comparison(
+        &parameter_names(fetch_node(&openapi, "/paths/~1ProductSet/get")?),
+        &"$filter,$top,$skip,$inlinecount,$orderby,$select,$expand".to_owned(),
+    )?;
+
+    handle_test_comparison(
+        fetch_node(
+            &openapi,
+            "/paths/~1ProductSet/get/responses/200/content/application~1json/schema/properties/d/properties/results/items",
+        )?,
+        &json!({ "$ref": "#/components/schemas/Product" }),
+    )
+}
+
+#[test]
+pub fn should_generate_function_import_paths() -> Result<(), String> {
+    let openapi = fetch_openapi()?;
+
+    let regenerate = fetch_node(&openapi, "/paths/~1RegenerateAllData")?;
+    handle_test_bool(regenerate.get("get").is_none())?;
+    handle_test_comparison(
+        &regenerate["post"]["parameters"][0],
+        &json!({
+            "name": "NoOfSalesOrders",
+            "in": "query",
+            "description": "Value of type Edm.Int32",
+            "required": false,
+            "schema": { "type": "integer", "minimum": -2_147_483_648i64, "maximum": 2_147_483_647i64 }
+        }),
+    )?;
+
+    let find_products = fetch_node(&openapi, "/paths/~1FindProducts/get")?;
+    handle_test_comparison(&find_products["summary"], &json!("Find Products"))?;
+    handle_test_comparison(&find_products["parameters"][0]["required"], &json!(true))?;
+    handle_test_comparison(&find_products["parameters"][0]["schema"]["maxLength"], &json!(40))?;
+    handle_test_comparison(
+        &find_products["responses"]["200"]["content"]["application/json"]["schema"]["properties"]["d"]["properties"]
+            ["results"]["items"],
+        &json!({ "$ref": "#/components/schemas/Product" }),
+    )?;
+
+    // Entity types and complex types are declared as component schemas
+    handle_test_comparison(
+        fetch_node(&openapi, "/components/schemas/BusinessPartner/properties/Address")?,
+        &json!({ "$ref": "#/components/schemas/CT_Address" }),
+    )
+}
@@ -0,0 +1,234 @@
+use crate::{
+    edmx::data_services::schema::{association::Association, entity_type::EntityType, Schema},
+    property::{metadata::PropertyType, Property},
+    sql::{quote_identifier, SqlDialect},
+    utils::to_snake_case,
+};
+
+static INDENT: &str = "    ";
+// Postgres timestamps and times have at most microsecond precision
+static MAX_FRACTIONAL_SECONDS_PRECISION: u16 = 6;
+
+pub static SUFFIX_SQL: &str = ".sql";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// The name of the table to which an entity type is mapped
+pub fn sql_table_name(et_name: &str) -> String {
+    to_snake_case(et_name)
+}
+
+/// The name of the column to which a property is mapped
+pub fn sql_column_name(odata_name: &str) -> String {
+    to_snake_case(odata_name)
+}
+
+struct SqlColumn {
+    name: String,
+    sql_type: String,
+    nullable: bool,
+}
+
+struct SqlForeignKey {
+    name: String,
+    table: String,
+    columns: Vec<String>,
+    referenced_table: String,
+    referenced_columns: Vec<String>,
+}
+
+fn quoted_list(names: &[String]) -> String {
+    names
+        .iter()
+        .map(|name| quote_identifier(name))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+fn unqualified_name(qualified_name: &str) -> &str {
+    qualified_name.rsplit_once('.').map_or(qualified_name, |(_, name)| name)
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn postgres_type(edm_type: &str, prop: &Property) -> String {
+    let with_fractional_seconds = |sql_type: &str| match prop.precision {
+        Some(precision) => format!("{sql_type}({})", precision.min(MAX_FRACTIONAL_SECONDS_PRECISION)),
+        None => sql_type.to_owned(),
+    };
+
+    match edm_type {
+        "Binary" => "BYTEA".to_owned(),
+        "Boolean" => "BOOLEAN".to_owned(),
+        "Byte" | "Int16" | "SByte" => "SMALLINT".to_owned(),
+        "DateTime" => with_fractional_seconds("TIMESTAMP"),
+        "DateTimeOffset" => with_fractional_seconds("TIMESTAMPTZ"),
+        "Decimal" => match (prop.precision, prop.scale) {
+            // NUMERIC(precision) would fix the scale at zero
+            _ if prop.sap_annotations.has_variable_scale => "NUMERIC".to_owned(),
+            (Some(precision), Some(scale)) => format!("NUMERIC({precision}, {scale})"),
+            (Some(precision), None) => format!("NUMERIC({precision})"),
+            (None, _) => "NUMERIC".to_owned(),
+        },
+        "Double" => "DOUBLE PRECISION".to_owned(),
+        "Guid" => "UUID".to_owned(),
+        "Int32" => "INTEGER".to_owned(),
+        "Int64" => "BIGINT".to_owned(),
+        "Single" => "REAL".to_owned(),
+        "Time" => with_fractional_seconds("TIME"),
+        _ => match prop.max_length {
+            Some(0) | None => "TEXT".to_owned(),
+            Some(max_length) => format!("VARCHAR({max_length})"),
+        },
+    }
+}
+
+// SQLite only distinguishes between storage classes, so decimals are stored as text to keep their exact value
+fn sqlite_type(edm_type: &str) -> String {
+    match edm_type {
+        "Binary" => "BLOB",
+        "Boolean" | "Byte" | "Int16" | "Int32" | "Int64" | "SByte" => "INTEGER",
+        "Double" | "Single" => "REAL",
+        _ => "TEXT",
+    }
+    .to_owned()
+}
+
+// Add the columns for a list of properties, flattening complex types into one column per property of the complex type
+fn columns_into(out: &mut Vec<SqlColumn>, schema: &Schema, props: &[Property], prefix: &str, dialect: SqlDialect) {
+    for prop in props {
+        let column_name = format!("{prefix}{}", sql_column_name(&prop.odata_name));
+
+        match prop.get_property_type() {
+            PropertyType::Edm(edm_type, _) => out.push(SqlColumn {
+                name: column_name,
+                sql_type: match dialect {
+                    SqlDialect::Postgres => postgres_type(&edm_type, prop),
+                    SqlDialect::Sqlite => sqlite_type(&edm_type),
+                },
+                nullable: prop.nullable,
+            }),
+            PropertyType::Complex(ct_name) => {
+                if let Some(ct) = schema.complex_types.iter().flatten().find(|ct| ct.name.eq(&ct_name)) {
+                    columns_into(out, schema, &ct.properties, &format!("{column_name}_"), dialect);
+                }
+            },
+            PropertyType::Unqualified => {},
+        }
+    }
+}
+
+// An association's referential constraint becomes a foreign key only if the principal properties are the principal
+// entity type's key, since a foreign key must reference a primary key or a unique constraint
+fn foreign_key(schema: &Schema, assoc: &Association) -> Option<SqlForeignKey> {
+    let rc = assoc.referential_constraint.as_ref()?;
+    let entity_type_of = |role: &str| -> Option<&EntityType> {
+        let end = assoc.ends.iter().find(|end| end.role.eq(role))?;
+        let type_name = unqualified_name(end.end_type.as_deref()?);
+        schema.entity_types.iter().find(|et| et.name.eq(type_name))
+    };
+    let principal = entity_type_of(&rc.principal.role)?;
+    let dependent = entity_type_of(&rc.dependent.role)?;
+
+    let mut principal_props: Vec<&String> = rc.principal.property_refs.iter().map(|pr| &pr.name).collect();
+    let mut principal_keys: Vec<&String> = principal.key.property_refs.iter().map(|pr| &pr.name).collect();
+    principal_props.sort();
+    principal_keys.sort();
+
+    if principal_props.ne(&principal_keys) {
+        return None;
+    }
+
+    Some(SqlForeignKey {
+        name: format!("fk_{}", to_snake_case(&assoc.name)),
+        table: sql_table_name(&dependent.name),
+        columns: rc.dependent.property_refs.iter().map(|pr| sql_column_name(&pr.name)).collect(),
+        referenced_table: sql_table_name(&principal.name),
+        referenced_columns: rc.principal.property_refs.iter().map(|pr| sql_column_name(&pr.name)).collect(),
+    })
+}
+
+fn foreign_key_clause(fk: &SqlForeignKey) -> String {
+    format!(
+        "FOREIGN KEY ({}) REFERENCES {} ({})",
+        quoted_list(&fk.columns),
+        quote_identifier(&fk.referenced_table),
+        quoted_list(&fk.referenced_columns)
+    )
+}
+
+fn create_table(schema: &Schema, et: &EntityType, fks: &[SqlForeignKey], dialect: SqlDialect) -> String {
+    let table_name = sql_table_name(&et.name);
+    let mut columns = vec![];
+    columns_into(&mut columns, schema, &et.properties, "", dialect);
+
+    let mut lines: Vec<String> = columns
+        .iter()
+        .map(|col| {
+            let not_null = if col.nullable { "" } else { " NOT NULL" };
+            format!("{INDENT}{} {}{not_null}", quote_identifier(&col.name), col.sql_type)
+        })
+        .collect();
+
+    let keys: Vec<String> = et.key.property_refs.iter().map(|pr| sql_column_name(&pr.name)).collect();
+    if !keys.is_empty() {
+        lines.push(format!("{INDENT}PRIMARY KEY ({})", quoted_list(&keys)));
+    }
+
+    // SQLite cannot add constraints to an existing table, so its foreign keys are declared with the table
+    if dialect == SqlDialect::Sqlite {
+        for fk in fks.iter().filter(|fk| fk.table.eq(&table_name)) {
+            lines.push(format!("{INDENT}{}", foreign_key_clause(fk)));
+        }
+    }
+
+    let label = et.sap_label.as_ref().map(|label| format!("-- {label}\n")).unwrap_or_default();
+
+    format!(
+        "{label}CREATE TABLE {} (\n{}\n);\n",
+        quote_identifier(&table_name),
+        lines.join(",\n")
+    )
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate an SQL script that creates a table for each entity type in the schema
+///
+/// * Column types are derived from the `Edm` type together with the `MaxLength`, `Precision` and `Scale` facets
+/// * Columns of non-nullable properties are declared `NOT NULL`
+/// * The entity type's key becomes the table's primary key
+/// * Each association's referential constraint becomes a foreign key
+/// * The properties of a complex type are flattened into columns named after the complex type property and the
+///   property within the complex type, E.G. `address_city`
+///
+/// In Postgres, the foreign keys are added once all the tables have been created, so the tables can be created in any
+/// order
+pub fn gen_sql_ddl(schema: &Schema, dialect: SqlDialect) -> Vec<u8> {
+    let fks: Vec<SqlForeignKey> = schema
+        .associations
+        .iter()
+        .filter_map(|assoc| foreign_key(schema, assoc))
+        .collect();
+
+    let mut out = format!("-- Tables for OData service namespace {}\n", schema.namespace);
+
+    for et in &schema.entity_types {
+        out.push('\n');
+        out.push_str(&create_table(schema, et, &fks, dialect));
+    }
+
+    if dialect == SqlDialect::Postgres {
+        for fk in &fks {
+            out.push_str(&format!(
+                "\nALTER TABLE {}\n{INDENT}ADD CONSTRAINT {} {};\n",
+                quote_identifier(&fk.table),
+                quote_identifier(&fk.name),
+                foreign_key_clause(fk)
+            ));
+        }
+    }
+
+    out.into_bytes()
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(test)]
+pub mod unit_tests;
@@ -0,0 +1,80 @@
+use super::*;
+use crate::test_utils::*;
+
+fn table_ddl(ddl: &str, table_name: &str) -> Result<String, String> {
+    let start = format!("CREATE TABLE \"{table_name}\" (\n");
+    let from = ddl.find(&start).ok_or(format!("Table {table_name} not found"))?;
+    let to = ddl[from..].find(");\n").ok_or("Unterminated CREATE TABLE statement")?;
+
+    Ok(ddl[from..from + to + 3].to_owned())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_generate_postgres_ddl() -> Result<(), String> {
+    let edmx = fetch_mock_service_edmx()?;
+    let schema = edmx
+        .data_services
+        .fetch_schema("GWSAMPLE_BASIC")
+        .ok_or("Schema GWSAMPLE_BASIC not found")?;
+    let ddl = String::from_utf8(gen_sql_ddl(schema, SqlDialect::Postgres)).map_err(|err| err.to_string())?;
+
+    handle_test_comparison(
+        &table_ddl(&ddl, "business_partner")?,
+        &r#"CREATE TABLE "business_partner" (
+    "address_city" VARCHAR(40),
+    "address_postal_code" VARCHAR(10),
+    "address_country" VARCHAR(3),
+    "business_partner_id" VARCHAR(10) NOT NULL,
+    "company_name" VARCHAR(80) NOT NULL,
+    "email_address" VARCHAR(255),
+    PRIMARY KEY ("business_partner_id")
+);
+"#
+        .to_owned(),
+    )?;
+
+    let product = table_ddl(&ddl, "product")?;
+    handle_test_bool(product.contains(r#""supplier_id" VARCHAR(10) NOT NULL,"#))?;
+    handle_test_bool(product.contains(r#""price" NUMERIC(16, 3),"#))?;
+    handle_test_bool(product.contains(r#""changed_at" TIMESTAMP(6),"#))?;
+
+    // Foreign keys are added once all tables exist
+    handle_test_bool(ddl.ends_with(
+        r#"ALTER TABLE "product"
+    ADD CONSTRAINT "fk_assoc_business_partner_products" FOREIGN KEY ("supplier_id") REFERENCES "business_partner" ("business_partner_id");
+"#,
+    ))
+}
+
+#[test]
+pub fn should_generate_sqlite_ddl() -> Result<(), String> {
+    let edmx = fetch_mock_service_edmx()?;
+    let schema = edmx
+        .data_services
+        .fetch_schema("GWSAMPLE_BASIC")
+        .ok_or("Schema GWSAMPLE_BASIC not found")?;
+    let ddl = String::from_utf8(gen_sql_ddl(schema, SqlDialect::Sqlite)).map_err(|err| err.to_string())?;
+    let product = table_ddl(&ddl, "product")?;
+
+    handle_test_bool(product.contains(r#""supplier_id" TEXT NOT NULL,"#))?;
+    handle_test_bool(product.contains(r#""price" TEXT,"#))?;
+    handle_test_bool(product.contains(r#""changed_at" TEXT,"#))?;
+    handle_test_bool(product.ends_with(
+        r#"    PRIMARY KEY ("product_id"),
+    FOREIGN KEY ("supplier_id") REFERENCES "business_partner" ("business_partner_id")
+);
+"#,
+    ))?;
+    handle_test_bool(!ddl.contains("ALTER TABLE"))
+}
+
+#[test]
+pub fn should_map_variable_scale_decimal_to_unconstrained_numeric() -> Result<(), String> {
+    let prop: Property = quick_xml::de::from_str(
+        r#"<Property Name="Quantity" Type="Edm.Decimal" Precision="16" Scale="3" sap:variable-scale="true"/>"#,
+    )
+    .map_err(|err| err.to_string())?;
+
+    handle_test_comparison(&postgres_type("Decimal", &prop), &"NUMERIC".to_owned())
+}
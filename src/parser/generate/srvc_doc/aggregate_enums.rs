@@ -0,0 +1,165 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    analytics::{AnalyticalKind, AnalyticalProperty},
+    edmx::data_services::schema::entity_type::EntityType,
+    parser::generate::{gen_ident, syntax_fragments::derive_traits::*},
+    utils::to_upper_camel_case,
+};
+
+static SUFFIX_DIMENSION: &str = "Dimension";
+static SUFFIX_MEASURE: &str = "Measure";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate the dimension and measure enums for an entity type annotated with `sap:semantics="aggregate"`.
+///
+/// For the entity type `SalesOrderQueryResult`, two enums are generated:
+/// * `SalesOrderQueryResultDimension` containing a variant for each dimension and dimension attribute, implementing
+///   `parse_sap_odata::analytics::AggregateDimension`
+/// * `SalesOrderQueryResultMeasure` containing a variant for each measure, implementing
+///   `parse_sap_odata::analytics::AggregateMeasure`
+///
+/// If the entity type is not an aggregate, nothing is generated
+pub fn gen_aggregate_enums(entity: &EntityType) -> TokenStream {
+    if !entity.is_aggregate() {
+        return TokenStream::new();
+    }
+
+    let type_name = to_upper_camel_case(&entity.name);
+    let analytical_props = AnalyticalProperty::find_in(&entity.properties);
+    let (measures, dimensions): (Vec<_>, Vec<_>) =
+        analytical_props.iter().partition(|ap| ap.kind == AnalyticalKind::Measure);
+
+    let dimension_enum = gen_dimension_enum(&format!("{type_name}{SUFFIX_DIMENSION}"), &dimensions);
+    let measure_enum = gen_measure_enum(&format!("{type_name}{SUFFIX_MEASURE}"), &measures);
+
+    quote!(#dimension_enum #measure_enum)
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Declare a field-less enum whose variants are the UpperCamelCase versions of `variant_names`
+pub(super) fn gen_field_enum_decl<'a>(enum_name: &str, variant_names: impl Iterator<Item = &'a str>) -> TokenStream {
+    let derive = gen_derive(&[
+        DeriveTraits::CLONE,
+        DeriveTraits::COPY,
+        DeriveTraits::DEBUG,
+        DeriveTraits::PARTIALEQ,
+        DeriveTraits::EQ,
+    ]);
+    let enum_ident = gen_ident(enum_name);
+    let variants = variant_names.map(|name| gen_ident(&to_upper_camel_case(name)));
+
+    quote! {
+        #derive
+        pub enum #enum_ident {
+            #(#variants),*
+        }
+    }
+}
+
+// Generates a trait function that matches on self and returns the value of each arm
+//   fn <fn_name>(&self) -> <return_type> { match *self { Self::A => <a>, _ => <default>, } }
+pub(super) fn gen_match_self_fn(
+    fn_name: &str,
+    return_type: TokenStream,
+    arms: &[(String, TokenStream)],
+    default: Option<TokenStream>,
+) -> TokenStream {
+    let fn_ident = gen_ident(fn_name);
+
+    // A match containing only the default arm would trigger a lint, so return the default value directly
+    let body = match (arms.is_empty(), default) {
+        (true, Some(default_value)) => default_value,
+        (_, default) => {
+            let match_arms = arms.iter().map(|(variant, value)| {
+                let variant_ident = gen_ident(variant);
+                quote!(Self::#variant_ident => #value,)
+            });
+            let default_arm = default.map(|default_value| quote!(_ => #default_value,));
+
+            quote! {
+                match *self {
+                    #(#match_arms)*
+                    #default_arm
+                }
+            }
+        },
+    };
+
+    quote! {
+        fn #fn_ident(&self) -> #return_type {
+            #body
+        }
+    }
+}
+
+fn odata_name_arms(props: &[&AnalyticalProperty]) -> Vec<(String, TokenStream)> {
+    props
+        .iter()
+        .map(|ap| {
+            let odata_name = &ap.property_name;
+            (to_upper_camel_case(odata_name), quote!(#odata_name))
+        })
+        .collect()
+}
+
+// Only relationships to other members of the dimension enum can be expressed as variants
+fn related_dimension_arms(
+    props: &[&AnalyticalProperty],
+    related: fn(&AnalyticalProperty) -> &Option<String>,
+) -> Vec<(String, TokenStream)> {
+    props
+        .iter()
+        .filter_map(|ap| {
+            related(ap)
+                .as_ref()
+                .filter(|other| props.iter().any(|p| p.property_name.eq(*other)))
+                .map(|other| {
+                    let other_ident = gen_ident(&to_upper_camel_case(other));
+                    (to_upper_camel_case(&ap.property_name), quote!(Some(Self::#other_ident)))
+                })
+        })
+        .collect()
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn gen_dimension_enum(enum_name: &str, dimensions: &[&AnalyticalProperty]) -> TokenStream {
+    let enum_decl = gen_field_enum_decl(enum_name, dimensions.iter().map(|ap| ap.property_name.as_str()));
+    let enum_ident = gen_ident(enum_name);
+    let fn_odata_name = gen_match_self_fn("odata_name", quote!(&'static str), &odata_name_arms(dimensions), None);
+    let fn_attribute_for = gen_match_self_fn(
+        "attribute_for",
+        quote!(Option<Self>),
+        &related_dimension_arms(dimensions, |ap| &ap.attribute_for),
+        Some(quote!(None)),
+    );
+    let fn_super_ordinate = gen_match_self_fn(
+        "super_ordinate",
+        quote!(Option<Self>),
+        &related_dimension_arms(dimensions, |ap| &ap.super_ordinate),
+        Some(quote!(None)),
+    );
+
+    quote! {
+        #enum_decl
+        impl parse_sap_odata::analytics::AggregateDimension for #enum_ident {
+            #fn_odata_name
+            #fn_attribute_for
+            #fn_super_ordinate
+        }
+    }
+}
+
+fn gen_measure_enum(enum_name: &str, measures: &[&AnalyticalProperty]) -> TokenStream {
+    let enum_decl = gen_field_enum_decl(enum_name, measures.iter().map(|ap| ap.property_name.as_str()));
+    let enum_ident = gen_ident(enum_name);
+    let fn_odata_name = gen_match_self_fn("odata_name", quote!(&'static str), &odata_name_arms(measures), None);
+
+    quote! {
+        #enum_decl
+        impl parse_sap_odata::analytics::AggregateMeasure for #enum_ident {
+            #fn_odata_name
+        }
+    }
+}
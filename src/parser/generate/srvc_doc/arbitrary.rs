@@ -0,0 +1,208 @@
+use proc_macro2::{Literal, TokenStream};
+use quote::quote;
+
+use crate::{
+    edmx::data_services::schema::entity_type::EntityType,
+    parser::generate::{gen_ident, gen_path},
+    property::{metadata::PropertyType, Property},
+    sap_annotations::display_format::SAPDisplayFormatProperty,
+    utils::{odata_name_to_rust_safe_name, to_snake_case, to_upper_camel_case},
+};
+
+static PATH_TO_CHECK_ROUND_TRIP: &str = "parse_sap_odata::round_trip::check_round_trip";
+
+// Proptest implements `Strategy` for tuples of up to 12 elements, so larger structs use nested tuples
+static MAX_TUPLE_ARITY: usize = 10;
+// Strings are kept short enough for tests to run quickly, even if the MaxLength would permit more
+static MAX_STRING_LENGTH: u16 = 64;
+static DEFAULT_STRING_LENGTH: u16 = 20;
+static MAX_BINARY_LENGTH: u16 = 64;
+static MAX_DECIMAL_DIGITS: u16 = 18;
+
+// Timestamps range from 1970-01-01T00:00:00 to 9999-12-31T23:59:59
+static MAX_SECONDS: i64 = 253_402_300_800;
+static SECONDS_PER_DAY: i64 = 86_400;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Strategy for a value of the property's type, ignoring any Option wrapper
+fn gen_inner_strategy(prop: &Property) -> TokenStream {
+    let edm_type = match prop.get_property_type() {
+        PropertyType::Edm(edm_type, _) => edm_type,
+        PropertyType::Complex(_) | PropertyType::Unqualified => {
+            let rust_type = prop.to_rust_type();
+            return quote!(proptest::arbitrary::any::<#rust_type>());
+        },
+    };
+    let is_date_only = prop.sap_annotations.display_format == Some(SAPDisplayFormatProperty::Date);
+    let is_upper_case = prop.sap_annotations.display_format == Some(SAPDisplayFormatProperty::UpperCase);
+
+    match edm_type.as_str() {
+        "Binary" => {
+            let max_length = Literal::u16_unsuffixed(prop.max_length.unwrap_or(MAX_BINARY_LENGTH).min(MAX_BINARY_LENGTH));
+            quote!(proptest::collection::vec(proptest::arbitrary::any::<u8>(), 0..=#max_length))
+        },
+        "Boolean" => quote!(proptest::arbitrary::any::<bool>()),
+        "Byte" => quote!(proptest::arbitrary::any::<u8>()),
+        "DateTime" | "DateTimeOffset" if is_date_only => {
+            let max_days = MAX_SECONDS / SECONDS_PER_DAY;
+            quote! {
+                (0i64..#max_days).prop_map(|days| {
+                    chrono::DateTime::from_timestamp(days * #SECONDS_PER_DAY, 0).unwrap_or_default().naive_utc()
+                })
+            }
+        },
+        "DateTime" | "DateTimeOffset" => quote! {
+            (0i64..#MAX_SECONDS).prop_map(|secs| chrono::DateTime::from_timestamp(secs, 0).unwrap_or_default().naive_utc())
+        },
+        "Decimal" => {
+            // The deserializer rejects values with more fractional digits than the scale permits
+            let scale = prop.scale.unwrap_or(0);
+            let digits = prop
+                .precision
+                .unwrap_or(MAX_DECIMAL_DIGITS)
+                .max(scale.max(1))
+                .min(MAX_DECIMAL_DIGITS);
+            let max_mantissa = 10i64.pow(digits as u32) - 1;
+            let scale = Literal::u16_unsuffixed(scale);
+
+            quote!((-#max_mantissa..=#max_mantissa).prop_map(|mantissa| rust_decimal::Decimal::new(mantissa, #scale)))
+        },
+        "Double" => quote!(-1.0e12f64..1.0e12f64),
+        "Guid" => quote!(proptest::arbitrary::any::<u128>().prop_map(uuid::Uuid::from_u128)),
+        "Int16" => quote!(proptest::arbitrary::any::<i16>()),
+        "Int32" => quote!(proptest::arbitrary::any::<i32>()),
+        "Int64" => quote!(proptest::arbitrary::any::<i64>()),
+        "Null" => quote!(proptest::strategy::Just(())),
+        "SByte" => quote!(proptest::arbitrary::any::<i8>()),
+        "Single" => quote!(-1.0e6f32..1.0e6f32),
+        "Time" => {
+            let seconds_per_day = SECONDS_PER_DAY as u64;
+            quote!((0u64..#seconds_per_day).prop_map(|secs| std::time::UNIX_EPOCH + std::time::Duration::from_secs(secs)))
+        },
+        // Empty strings are excluded because they cannot be distinguished from null in an Atom feed
+        _ => {
+            let max_length = match prop.max_length {
+                Some(0) | None => DEFAULT_STRING_LENGTH,
+                Some(max_length) => max_length.min(MAX_STRING_LENGTH),
+            };
+            let char_class = if is_upper_case { "A-Z0-9" } else { "A-Za-z0-9" };
+            let regex = format!("[{char_class}]{{1,{max_length}}}");
+
+            quote!(#regex)
+        },
+    }
+}
+
+/// Strategy for the values of a property, constrained by the property's metadata
+pub fn gen_strategy(prop: &Property) -> TokenStream {
+    let inner = gen_inner_strategy(prop);
+
+    if prop.is_option_type() {
+        quote!(proptest::option::of(#inner))
+    } else {
+        inner
+    }
+}
+
+// Combine (pattern, strategy) pairs into a tuple strategy, nesting the tuples if there are too many pairs
+fn gen_tuple(items: Vec<(TokenStream, TokenStream)>) -> (TokenStream, TokenStream) {
+    if items.len() > MAX_TUPLE_ARITY {
+        let chunks: Vec<(TokenStream, TokenStream)> =
+            items.chunks(MAX_TUPLE_ARITY).map(|chunk| gen_tuple(chunk.to_vec())).collect();
+
+        return gen_tuple(chunks);
+    }
+
+    let (patterns, strategies): (Vec<TokenStream>, Vec<TokenStream>) = items.into_iter().unzip();
+
+    // The trailing comma ensures that a single item is still a tuple
+    (quote!((#(#patterns,)*)), quote!((#(#strategies,)*)))
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate an implementation of `proptest::arbitrary::Arbitrary` whose strategy builds instances of the struct using
+/// values that respect the metadata of each property:
+/// * Strings are no longer than `MaxLength`
+/// * Decimals fit within `Precision` and have `Scale` fractional digits
+/// * Nullable properties are sometimes `None`
+///
+/// The implementation is only compiled when the crate's `proptest` feature is enabled
+pub fn gen_arbitrary_impl(struct_name: &str, props: &[Property]) -> TokenStream {
+    let struct_ident = gen_ident(struct_name);
+    let field_idents: Vec<_> = props
+        .iter()
+        .map(|prop| gen_ident(&odata_name_to_rust_safe_name(&prop.odata_name)))
+        .collect();
+    let items: Vec<(TokenStream, TokenStream)> = field_idents
+        .iter()
+        .zip(props)
+        .map(|(field_ident, prop)| (quote!(#field_ident), gen_strategy(prop)))
+        .collect();
+
+    let strategy = if items.is_empty() {
+        quote!(proptest::strategy::Just(#struct_ident::default()))
+    } else {
+        let (pattern, tuple) = gen_tuple(items);
+        quote!(#tuple.prop_map(|#pattern| #struct_ident { #(#field_idents),* }))
+    };
+
+    quote! {
+        #[cfg(feature = "proptest")]
+        impl proptest::arbitrary::Arbitrary for #struct_ident {
+            type Parameters = ();
+            type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+            fn arbitrary_with(_args: Self::Parameters) -> Self::Strategy {
+                use proptest::strategy::Strategy;
+                #strategy.boxed()
+            }
+        }
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate a module containing a property-based test for each entity type that checks an arbitrary instance survives
+/// being serialized into an Atom `<m:properties>` block and deserialized again.
+///
+/// Entity types containing `Edm.Binary` or `Edm.Time` properties are skipped because the Rust types of these
+/// properties are not serialized in the format used by Atom feeds.
+///
+/// The module is only compiled for tests when the crate's `proptest` feature is enabled
+pub fn gen_round_trip_tests(ets: &[EntityType]) -> TokenStream {
+    let check_round_trip = gen_path(PATH_TO_CHECK_ROUND_TRIP);
+    let tests: Vec<TokenStream> = ets
+        .iter()
+        .filter(|et| {
+            !et.properties
+                .iter()
+                .any(|prop| prop.edm_type.eq("Edm.Binary") || prop.edm_type.eq("Edm.Time"))
+        })
+        .map(|et| {
+            let test_ident = gen_ident(&format!("{}_round_trip", to_snake_case(&et.name)));
+            let struct_ident = gen_ident(&to_upper_camel_case(&et.name));
+
+            quote! {
+                #[test]
+                fn #test_ident(value in proptest::arbitrary::any::<#struct_ident>()) {
+                    let result = #check_round_trip(&value);
+                    proptest::prop_assert!(result.is_ok(), "{}", result.unwrap_err());
+                }
+            }
+        })
+        .collect();
+
+    if tests.is_empty() {
+        return TokenStream::new();
+    }
+
+    quote! {
+        #[cfg(all(test, feature = "proptest"))]
+        mod round_trip_tests {
+            use super::*;
+
+            proptest::proptest! {
+                #(#tests)*
+            }
+        }
+    }
+}
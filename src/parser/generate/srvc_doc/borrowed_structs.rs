@@ -0,0 +1,108 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    parser::{
+        generate::{
+            gen_ident,
+            syntax_fragments::{
+                derive_traits::gen_derive,
+                serde_fragments::{gen_serde_rename, DESERIALIZABLE_STRUCT_DERIVES, NULLABLE_COW_STR_DESERIALIZER_FN},
+                SUFFIX_BORROWED,
+            },
+        },
+        AsRustSrc,
+    },
+    property::{metadata::PropertyType, Property},
+    utils::{odata_name_to_rust_safe_name, to_pascal_case, to_upper_camel_case},
+};
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate the borrowed counterpart `<struct_name>Borrowed<'a>` of an entity type or complex type struct.
+///
+/// String fields become `Cow<'a, str>` and complex type fields use the borrowed struct of that complex type.  All other
+/// fields are the same as in the owned struct.  The borrowed struct is given two functions:
+/// * `pub fn from_xml(xml: &'a str) -> Result<Self, quick_xml::DeError>`
+/// * `pub fn into_owned(self) -> <struct_name>`
+pub fn gen_borrowed_struct(struct_name: &str, props: &[Property]) -> TokenStream {
+    let owned_ident = gen_ident(struct_name);
+    let borrowed_ident = gen_ident(&format!("{struct_name}{SUFFIX_BORROWED}"));
+    let derive = gen_derive(&DESERIALIZABLE_STRUCT_DERIVES);
+
+    let mut props: Vec<&Property> = props.iter().collect();
+    props.sort();
+
+    let mut fields = TokenStream::new();
+    let mut owned_values: Vec<TokenStream> = Vec::new();
+    let mut uses_lifetime = false;
+
+    for prop in props {
+        let field_ident = gen_ident(&odata_name_to_rust_safe_name(&prop.odata_name));
+
+        match prop.get_property_type() {
+            PropertyType::Edm(_, _) if prop.is_string_type() => {
+                uses_lifetime = true;
+                let rename = (!to_pascal_case(&prop.odata_name).eq(&prop.odata_name))
+                    .then(|| gen_serde_rename(&prop.odata_name));
+
+                if prop.nullable {
+                    fields.extend(quote! {
+                        #rename
+                        #[serde(default, borrow, deserialize_with = #NULLABLE_COW_STR_DESERIALIZER_FN)]
+                        pub #field_ident: Option<std::borrow::Cow<'a, str>>,
+                    });
+                    owned_values.push(quote!(#field_ident: self.#field_ident.map(std::borrow::Cow::into_owned)));
+                } else {
+                    fields.extend(quote! {
+                        #rename
+                        #[serde(borrow)]
+                        pub #field_ident: std::borrow::Cow<'a, str>,
+                    });
+                    owned_values.push(quote!(#field_ident: self.#field_ident.into_owned()));
+                }
+            },
+            PropertyType::Complex(ct_name) => {
+                uses_lifetime = true;
+                let ct_ident = gen_ident(&format!("{}{SUFFIX_BORROWED}", to_upper_camel_case(&ct_name)));
+
+                fields.extend(quote! {
+                    #[serde(borrow)]
+                    pub #field_ident: #ct_ident<'a>,
+                });
+                owned_values.push(quote!(#field_ident: self.#field_ident.into_owned()));
+            },
+            _ => {
+                fields.extend(prop.to_rust().0);
+                owned_values.push(quote!(#field_ident: self.#field_ident));
+            },
+        }
+    }
+
+    // A struct without string or complex type fields must still use its lifetime
+    if !uses_lifetime {
+        fields.extend(quote! {
+            #[serde(skip)]
+            _lifetime: std::marker::PhantomData<&'a ()>,
+        });
+    }
+
+    quote! {
+        #derive
+        #[serde(rename_all = "PascalCase")]
+        pub struct #borrowed_ident<'a> {
+            #fields
+        }
+
+        impl<'a> #borrowed_ident<'a> {
+            pub fn from_xml(xml: &'a str) -> Result<Self, quick_xml::DeError> {
+                quick_xml::de::from_str(xml)
+            }
+
+            pub fn into_owned(self) -> #owned_ident {
+                #owned_ident {
+                    #(#owned_values,)*
+                }
+            }
+        }
+    }
+}
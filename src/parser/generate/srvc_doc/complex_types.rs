@@ -1,51 +1,62 @@
 use check_keyword::CheckKeyword;
+use proc_macro2::TokenStream;
 
 use crate::{
     edmx::data_services::schema::complex_type::ComplexType,
     parser::{
         generate::{
-            gen_comment_separator_for, gen_impl_from_str_for,
-            syntax_fragments::{serde_fragments::*, COMPLEX_TYPES, END_BLOCK, SEPARATOR},
+            gen_impl_from_str_for,
+            syntax_fragments::serde_fragments::*,
+            type_attributes::{gen_type_attributes, ComplexTypeDerives},
         },
-        AsRustSrc,
+        options::GeneratorOptions,
     },
     utils::to_upper_camel_case,
 };
 
+use super::{
+    arbitrary::gen_arbitrary_impl, borrowed_structs::gen_borrowed_struct, sql_rows::gen_sql_columns_impl,
+    text_accessors::gen_text_accessors, unit_accessors::gen_unit_accessors,
+};
+
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// Generate complex type structs, writing output into `out` and returning crate references
-pub fn gen_complex_types_into(out: &mut Vec<u8>, cts: &[ComplexType]) -> Vec<String> {
-    let (mut src, crs) = gen_complex_types(cts);
-    out.append(&mut src);
+pub fn gen_complex_types_into(
+    out: &mut TokenStream,
+    cts: &[ComplexType],
+    ct_derives: &ComplexTypeDerives,
+    options: &GeneratorOptions,
+) -> Vec<String> {
+    let (src, crs) = gen_complex_types(cts, ct_derives, options);
+    out.extend(src);
     crs
 }
 
-pub fn gen_complex_types(cts: &[ComplexType]) -> (Vec<u8>, Vec<String>) {
-    let mut ignored_cts: usize = 0;
-
-    cts.into_iter().enumerate().fold(
-        // Accumulator's initial value is a comment separator
-        (gen_comment_separator_for(COMPLEX_TYPES), vec![]),
-        |(mut acc_src, mut acc_crate_refs), (idx, ct)| {
-            if idx > 0 && idx + ignored_cts + 1 < cts.len() {
-                acc_src.extend_from_slice(SEPARATOR);
-            }
-
-            if let (Some(mut ct_src), mut crs) = gen_complex_type_src(ct) {
+pub fn gen_complex_types(
+    cts: &[ComplexType],
+    ct_derives: &ComplexTypeDerives,
+    options: &GeneratorOptions,
+) -> (TokenStream, Vec<String>) {
+    cts.iter()
+        .fold((TokenStream::new(), vec![]), |(mut acc_src, mut acc_crate_refs), ct| {
+            if let (Some(ct_src), mut crs) = gen_complex_type_src(ct, ct_derives, options) {
                 acc_crate_refs.append(&mut crs);
-                acc_src.append(&mut ct_src);
-            } else {
-                ignored_cts += 1;
+                acc_src.extend(ct_src);
             }
 
             (acc_src, acc_crate_refs)
-        },
-    )
+        })
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// EDM Complex Type Instance -> Rust declaration
-fn gen_complex_type_src(ct: &ComplexType) -> (Option<Vec<u8>>, Vec<String>) {
+///
+/// `ct_derives` holds the traits derived by each complex type, as resolved by `resolve_complex_type_derives`
+pub(super) fn gen_complex_type_src(
+    ct: &ComplexType,
+    ct_derives: &ComplexTypeDerives,
+    options: &GeneratorOptions,
+) -> (Option<TokenStream>, Vec<String>) {
     let ct_name = to_upper_camel_case(&ct.name);
 
     // If the complex type contains only one field and that field's name suffix is a basic Rust type, then this complex
@@ -58,22 +69,46 @@ fn gen_complex_type_src(ct: &ComplexType) -> (Option<Vec<u8>>, Vec<String>) {
         let mut props: Vec<_> = ct.properties.iter().collect();
         props.sort();
 
-        let mut out_buffer: Vec<u8> = props.into_iter().fold(
-            // The accumulator's initial value is the derive and serde attributes, plus the struct declaration
-            gen_deserializable_struct(&ct_name),
-            |mut acc, prop| {
-                let (mut src, cr) = prop.to_rust();
+        let fields: TokenStream = props
+            .into_iter()
+            .map(|prop| {
+                let (src, cr) = prop.to_rust_field(options.gen_arbitrary_impls);
                 if !cr.is_empty() {
                     crate_refs.push(cr);
                 }
 
-                acc.append(&mut src);
-                acc
-            },
-        );
+                src
+            })
+            .collect();
+
+        // The derive and serde attributes, plus the struct declaration and its from_str implementation
+        let derives = ct_derives
+            .get(&ct.name)
+            .map_or(&DESERIALIZABLE_STRUCT_DERIVES[..], Vec::as_slice);
+        let type_attrs = gen_type_attributes(&ct.name, derives, &options.type_attributes.complex_types);
+        let mut out_buffer = gen_deserializable_struct(&ct_name, type_attrs, fields);
+        out_buffer.extend(gen_impl_from_str_for(&ct_name, options.sanitise_xml.as_ref()));
+
+        if options.gen_borrowed_structs {
+            out_buffer.extend(gen_borrowed_struct(&ct_name, &ct.properties));
+        }
+
+        if options.gen_unit_accessors {
+            out_buffer.extend(gen_unit_accessors(&ct_name, &ct.properties));
+        }
+
+        if options.gen_text_accessors {
+            out_buffer.extend(gen_text_accessors(&ct_name, &ct.properties));
+        }
+
+        if options.gen_arbitrary_impls {
+            out_buffer.extend(gen_arbitrary_impl(&ct_name, &ct.properties));
+        }
+
+        if options.gen_sql_ddl {
+            out_buffer.extend(gen_sql_columns_impl(&ct_name, &ct.properties));
+        }
 
-        out_buffer.extend_from_slice(END_BLOCK);
-        out_buffer.append(&mut gen_impl_from_str_for(&ct_name));
         (Some(out_buffer), crate_refs)
     } else {
         // This is just a simple type with a complex
@@ -1,67 +1,121 @@
+use proc_macro2::TokenStream;
+
 use crate::{
     edmx::data_services::schema::entity_type::EntityType,
     parser::{
         generate::{
-            gen_comment_separator_for, gen_impl_from_str_for,
-            syntax_fragments::{serde_fragments::*, END_BLOCK, ENTITY_TYPES, SEPARATOR},
+            gen_impl_from_str_for,
+            syntax_fragments::serde_fragments::*,
+            type_attributes::{gen_type_attributes, resolve_struct_derives, ComplexTypeDerives},
         },
-        AsRustSrc,
+        options::GeneratorOptions,
     },
     utils::to_upper_camel_case,
 };
 
+use super::{
+    aggregate_enums::gen_aggregate_enums,
+    arbitrary::gen_arbitrary_impl,
+    borrowed_structs::gen_borrowed_struct,
+    filter_fields::gen_filter_field_enum,
+    hierarchy_node::gen_hierarchy_node_impl,
+    sql_rows::{gen_sql_columns_impl, gen_sql_row_impl},
+    text_accessors::gen_text_accessors,
+    unit_accessors::gen_unit_accessors,
+};
+
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// Generate entity type structs, writing output into `out` and returning crate references
-pub fn gen_entity_types_into(out: &mut Vec<u8>, ets: &[EntityType]) -> Vec<String> {
-    let (mut src, crs) = gen_entity_types(ets);
-    out.append(&mut src);
+pub fn gen_entity_types_into(
+    out: &mut TokenStream,
+    ets: &[EntityType],
+    ct_derives: &ComplexTypeDerives,
+    options: &GeneratorOptions,
+) -> Vec<String> {
+    let (src, crs) = gen_entity_types(ets, ct_derives, options);
+    out.extend(src);
     crs
 }
 
-pub fn gen_entity_types(ets: &[EntityType]) -> (Vec<u8>, Vec<String>) {
-    ets.into_iter().enumerate().fold(
-        // Accumulator's initial value is a comment separator
-        (gen_comment_separator_for(ENTITY_TYPES), vec![]),
-        |(mut acc_src, mut acc_crs), (idx, entity)| {
-            if idx > 0 {
-                acc_src.extend_from_slice(SEPARATOR);
-            }
-
-            let (mut et_src, mut crs) = gen_entity_type(entity);
+pub fn gen_entity_types(
+    ets: &[EntityType],
+    ct_derives: &ComplexTypeDerives,
+    options: &GeneratorOptions,
+) -> (TokenStream, Vec<String>) {
+    ets.iter()
+        .fold((TokenStream::new(), vec![]), |(mut acc_src, mut acc_crs), entity| {
+            let (et_src, mut crs) = gen_entity_type(entity, ct_derives, options);
             if !crs.is_empty() {
                 acc_crs.append(&mut crs)
             }
-            acc_src.append(&mut et_src);
+            acc_src.extend(et_src);
             (acc_src, acc_crs)
-        },
-    )
+        })
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// EDM EntityType Instance -> Rust declaration
-fn gen_entity_type(entity: &EntityType) -> (Vec<u8>, Vec<String>) {
+///
+/// `ct_derives` holds the traits derived by each complex type, which limits the traits an entity type using that
+/// complex type can derive
+pub(super) fn gen_entity_type(
+    entity: &EntityType,
+    ct_derives: &ComplexTypeDerives,
+    options: &GeneratorOptions,
+) -> (TokenStream, Vec<String>) {
     let struct_name = to_upper_camel_case(&entity.name);
     let mut crate_refs: Vec<String> = vec![];
     let mut props: Vec<_> = entity.properties.iter().collect();
     props.sort();
 
-    let mut out_buffer: Vec<u8> = props.into_iter().fold(
-        // Accumulator's initial value is the derive and serde attributes plus the struct declaration
-        gen_deserializable_struct(&struct_name),
-        |mut acc, prop| {
-            let (mut prop_src, cr) = prop.to_rust();
+    let fields: TokenStream = props
+        .into_iter()
+        .map(|prop| {
+            let (prop_src, cr) = prop.to_rust_field(options.gen_arbitrary_impls);
             if !cr.is_empty() {
                 crate_refs.push(cr)
             }
 
-            acc.append(&mut prop_src);
-            acc
-        },
-    );
+            prop_src
+        })
+        .collect();
+
+    // The derive and serde attributes plus the struct declaration, then the from_str implementation
+    let rules = &options.type_attributes.entity_types;
+    let derives = resolve_struct_derives(&entity.name, &entity.properties, rules, ct_derives);
+    let type_attrs = gen_type_attributes(&entity.name, &derives, rules);
+    let mut out_buffer = gen_deserializable_struct(&struct_name, type_attrs, fields);
+    out_buffer.extend(gen_impl_from_str_for(&struct_name, options.sanitise_xml.as_ref()));
+
+    if options.gen_borrowed_structs {
+        out_buffer.extend(gen_borrowed_struct(&struct_name, &entity.properties));
+    }
+
+    // Aggregate entity types also need enums listing their dimensions and measures
+    out_buffer.extend(gen_aggregate_enums(entity));
+
+    // Entity types with filter annotations need an enum of the fields that can be used in a filter
+    out_buffer.extend(gen_filter_field_enum(entity));
+
+    // Entity types that define a hierarchy can be assembled into a tree
+    out_buffer.extend(gen_hierarchy_node_impl(&struct_name, &entity.properties));
+
+    if options.gen_unit_accessors {
+        out_buffer.extend(gen_unit_accessors(&struct_name, &entity.properties));
+    }
+
+    if options.gen_text_accessors {
+        out_buffer.extend(gen_text_accessors(&struct_name, &entity.properties));
+    }
+
+    if options.gen_arbitrary_impls {
+        out_buffer.extend(gen_arbitrary_impl(&struct_name, &entity.properties));
+    }
 
-    // End the struct declaration then generate from_str implementation
-    out_buffer.extend_from_slice(END_BLOCK);
-    out_buffer.append(&mut gen_impl_from_str_for(&struct_name));
+    if options.gen_sql_ddl {
+        out_buffer.extend(gen_sql_columns_impl(&struct_name, &entity.properties));
+        out_buffer.extend(gen_sql_row_impl(&struct_name, &entity.name));
+    }
 
     (out_buffer, crate_refs)
 }
@@ -0,0 +1,89 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    edmx::data_services::schema::entity_type::EntityType,
+    parser::generate::gen_ident,
+    property::{metadata::PropertyType, Property},
+    utils::to_upper_camel_case,
+};
+
+use super::aggregate_enums::{gen_field_enum_decl, gen_match_self_fn};
+
+static SUFFIX_FILTER_FIELD: &str = "FilterField";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn has_filter_restrictions(prop: &Property) -> bool {
+    prop.sap_annotations.is_required_in_filter || prop.sap_annotations.filter_restriction.is_some()
+}
+
+/// Generate an enum of the filterable properties of an entity type that has at least one property annotated with
+/// `sap:required-in-filter` or `sap:filter-restriction`.
+///
+/// For the entity type `SalesOrderQueryResult`, the enum `SalesOrderQueryResultFilterField` is generated, implementing
+/// `parse_sap_odata::query::filter::FilterField`, so that `FilterBuilder` can reject filters that SAP Gateway would
+/// refuse.
+/// Properties annotated with `sap:filterable="false"` and properties of a complex type are not filter fields
+pub fn gen_filter_field_enum(entity: &EntityType) -> TokenStream {
+    if !entity.properties.iter().any(has_filter_restrictions) {
+        return TokenStream::new();
+    }
+
+    let enum_name = format!("{}{SUFFIX_FILTER_FIELD}", to_upper_camel_case(&entity.name));
+    let enum_ident = gen_ident(&enum_name);
+    let fields: Vec<&Property> = entity
+        .properties
+        .iter()
+        .filter(|p| p.sap_annotations.is_filterable && matches!(p.get_property_type(), PropertyType::Edm(_, _)))
+        .collect();
+
+    let enum_decl = gen_field_enum_decl(&enum_name, fields.iter().map(|p| p.odata_name.as_str()));
+
+    let odata_name_arms: Vec<(String, TokenStream)> = fields
+        .iter()
+        .map(|p| {
+            let odata_name = &p.odata_name;
+            (to_upper_camel_case(odata_name), quote!(#odata_name))
+        })
+        .collect();
+    let required: Vec<String> = fields
+        .iter()
+        .filter(|p| p.sap_annotations.is_required_in_filter)
+        .map(|p| to_upper_camel_case(&p.odata_name))
+        .collect();
+    let required_arms: Vec<(String, TokenStream)> = required.iter().map(|v| (v.clone(), quote!(true))).collect();
+    let restriction_arms: Vec<(String, TokenStream)> = fields
+        .iter()
+        .filter_map(|p| {
+            p.sap_annotations.filter_restriction.map(|fr| {
+                (
+                    to_upper_camel_case(&p.odata_name),
+                    quote!(Some(parse_sap_odata::sap_annotations::filter_restriction::#fr)),
+                )
+            })
+        })
+        .collect();
+    let required_fields = required.iter().map(|v| gen_ident(v));
+
+    let fn_odata_name = gen_match_self_fn("odata_name", quote!(&'static str), &odata_name_arms, None);
+    let fn_is_required_in_filter =
+        gen_match_self_fn("is_required_in_filter", quote!(bool), &required_arms, Some(quote!(false)));
+    let fn_filter_restriction = gen_match_self_fn(
+        "filter_restriction",
+        quote!(Option<parse_sap_odata::sap_annotations::filter_restriction::SAPFilterRestrictionProperty>),
+        &restriction_arms,
+        Some(quote!(None)),
+    );
+
+    quote! {
+        #enum_decl
+        impl parse_sap_odata::query::filter::FilterField for #enum_ident {
+            #fn_odata_name
+            #fn_is_required_in_filter
+            #fn_filter_restriction
+            fn required_fields() -> &'static [Self] {
+                &[#(Self::#required_fields),*]
+            }
+        }
+    }
+}
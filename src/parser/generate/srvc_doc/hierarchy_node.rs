@@ -0,0 +1,95 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    hierarchy::HierarchyInfo,
+    parser::generate::gen_ident,
+    property::{metadata::PropertyType, Property},
+    utils::odata_name_to_rust_safe_name,
+};
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Expression returning the field value as a String
+fn gen_string_expr(prop: &Property) -> TokenStream {
+    let field = gen_ident(&odata_name_to_rust_safe_name(&prop.odata_name));
+
+    match (prop.is_option_type(), prop.is_string_type()) {
+        (false, true) => quote!(self.#field.clone()),
+        (true, true) => quote!(self.#field.clone().unwrap_or_default()),
+        (false, false) => quote!(self.#field.to_string()),
+        (true, false) => quote!(self.#field.as_ref().map(|v| v.to_string()).unwrap_or_default()),
+    }
+}
+
+// Expression returning the field value as an Option<String>
+fn gen_opt_string_expr(prop: &Property) -> TokenStream {
+    let field = gen_ident(&odata_name_to_rust_safe_name(&prop.odata_name));
+
+    match (prop.is_option_type(), prop.is_string_type()) {
+        (false, true) => quote!(Some(self.#field.clone())),
+        (true, true) => quote!(self.#field.clone()),
+        (false, false) => quote!(Some(self.#field.to_string())),
+        (true, false) => quote!(self.#field.as_ref().map(|v| v.to_string())),
+    }
+}
+
+// Expression returning the field value as an Option<i64>, or None if the field's type cannot be converted
+fn gen_rank_expr(prop: &Property) -> Option<TokenStream> {
+    let field = gen_ident(&odata_name_to_rust_safe_name(&prop.odata_name));
+    let is_integer = match prop.get_property_type() {
+        PropertyType::Edm(edm_type, _) => matches!(edm_type.as_str(), "Byte" | "Int16" | "Int32" | "Int64" | "SByte"),
+        _ => false,
+    };
+
+    match (prop.is_option_type(), is_integer, prop.is_string_type()) {
+        (false, true, _) => Some(quote!(Some(i64::from(self.#field)))),
+        (true, true, _) => Some(quote!(self.#field.map(i64::from))),
+        (false, false, true) => Some(quote!(self.#field.parse().ok())),
+        (true, false, true) => Some(quote!(self.#field.as_deref().and_then(|v| v.parse().ok()))),
+        _ => None,
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate an implementation of `parse_sap_odata::hierarchy::HierarchyNode` for an entity type that defines a
+/// hierarchy.
+///
+/// If the entity type defines more than one hierarchy, the first one that has a parent node property is used.
+/// If the entity type does not define a hierarchy with a parent node property, nothing is generated
+pub fn gen_hierarchy_node_impl(struct_name: &str, props: &[Property]) -> TokenStream {
+    let find_prop = |name: &str| props.iter().find(|p| p.odata_name.eq(name));
+
+    let hierarchy = HierarchyInfo::find_in(props)
+        .into_iter()
+        .find(|hi| hi.parent_node_property.is_some());
+
+    let Some(hierarchy) = hierarchy else {
+        return TokenStream::new();
+    };
+
+    let node = find_prop(&hierarchy.node_property).unwrap();
+    let parent = find_prop(hierarchy.parent_node_property.as_ref().unwrap()).unwrap();
+    let fn_rank = hierarchy
+        .preorder_rank_property
+        .as_ref()
+        .or(hierarchy.sibling_rank_property.as_ref())
+        .and_then(|rank_name| find_prop(rank_name))
+        .and_then(gen_rank_expr)
+        .map(|rank_expr| quote!(fn rank(&self) -> Option<i64> { #rank_expr }));
+
+    let struct_ident = gen_ident(struct_name);
+    let node_expr = gen_string_expr(node);
+    let parent_expr = gen_opt_string_expr(parent);
+
+    quote! {
+        impl parse_sap_odata::hierarchy::HierarchyNode for #struct_ident {
+            fn node_id(&self) -> String {
+                #node_expr
+            }
+            fn parent_node_id(&self) -> Option<String> {
+                #parent_expr
+            }
+            #fn_rank
+        }
+    }
+}
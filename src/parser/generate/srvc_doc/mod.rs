@@ -1,54 +1,160 @@
+mod aggregate_enums;
+mod arbitrary;
+mod borrowed_structs;
 mod complex_types;
 mod entity_types;
+mod filter_fields;
+mod hierarchy_node;
+mod parameter_sets;
+mod sql_rows;
+mod text_accessors;
+mod unit_accessors;
 
-use complex_types::gen_complex_types_into;
-use entity_types::gen_entity_types_into;
+use arbitrary::gen_round_trip_tests;
+use complex_types::{gen_complex_type_src, gen_complex_types_into};
+use entity_types::{gen_entity_type, gen_entity_types_into};
+use parameter_sets::gen_parameter_sets;
 
-use std::collections::BTreeSet;
+use std::{collections::BTreeSet, path::Path};
+
+use proc_macro2::TokenStream;
+use quote::quote;
 
 use crate::{
     edmx::data_services::schema::Schema,
-    parser::generate::{
-        gen_comment_separator_for_into, gen_extern_crate_into, gen_module_start_into,
-        syntax_fragments::{
-            gen_use_path_into, CRATE_QUICK_XML, CRATE_SERDE, END_BLOCK, PATH_TO_SERDE_SERIALIZE_DESERIALIZE,
+    parser::{
+        generate::{
+            gen_extern_crate, gen_ident, gen_use_path,
+            module_tree::ModuleTree,
+            type_attributes::resolve_complex_type_derives,
+            syntax_fragments::{
+                CRATE_QUICK_XML, CRATE_SERDE, DIR_COMPLEX_TYPES, DIR_ENTITY_TYPES, FILE_ENTITY_SETS,
+                PATH_TO_SERDE_SERIALIZE_DESERIALIZE,
+            },
         },
+        options::GeneratorOptions,
     },
+    utils::odata_name_to_rust_safe_name,
 };
 
 // ---------------------------------------------------------------------------------------------------------------------
-pub fn gen_srv_doc_module(odata_srv_name: &str, schema: &Schema) -> Vec<u8> {
-    let mut crate_refs: BTreeSet<String> = BTreeSet::new();
-    let mut out_buffer = Vec::new();
+pub fn gen_srv_doc_module(odata_srv_name: &str, schema: &Schema) -> TokenStream {
+    gen_srv_doc_module_with_options(odata_srv_name, schema, &GeneratorOptions::default())
+}
 
-    // External crate dependencies on serde and quick_xml are always required
-    gen_extern_crate_into(&mut out_buffer, CRATE_QUICK_XML);
-    gen_extern_crate_into(&mut out_buffer, CRATE_SERDE);
-    gen_module_start_into(&mut out_buffer, odata_srv_name);
-    gen_use_path_into(&mut out_buffer, PATH_TO_SERDE_SERIALIZE_DESERIALIZE);
+pub fn gen_srv_doc_module_with_options(odata_srv_name: &str, schema: &Schema, options: &GeneratorOptions) -> TokenStream {
+    let mut crate_refs: BTreeSet<String> = BTreeSet::new();
+    let mut mod_body = gen_use_path(PATH_TO_SERDE_SERIALIZE_DESERIALIZE);
+    let ct_derives = resolve_complex_type_derives(
+        schema.complex_types.as_deref().unwrap_or_default(),
+        &options.type_attributes.complex_types,
+    );
 
     if let Some(cts) = &schema.complex_types {
-        crate_refs.extend(gen_complex_types_into(&mut out_buffer, cts));
+        crate_refs.extend(gen_complex_types_into(&mut mod_body, cts, &ct_derives, options));
     }
 
-    crate_refs.extend(gen_entity_types_into(&mut out_buffer, &schema.entity_types));
+    crate_refs.extend(gen_entity_types_into(&mut mod_body, &schema.entity_types, &ct_derives, options));
+
+    // Entity sets whose entity type holds input parameters need a parameter struct to build the URL path
+    mod_body.extend(gen_parameter_sets(schema));
 
     // Create enum + impl for the entity container element
     // This enum acts as a proxy for the list of Collections in the service document
     if let Some(ent_cont) = &schema.entity_container {
-        gen_comment_separator_for_into(&mut out_buffer, "ENTITY SETS ENUM");
-        ent_cont.to_enum_with_impl_into(&mut out_buffer);
+        mod_body.extend(ent_cont.to_enum_with_options(options));
+
+        // Marker types used to check an entity set's capabilities at compile time
+        mod_body.extend(ent_cont.to_entity_sets_module());
     }
 
-    // End module definition
-    out_buffer.extend_from_slice(END_BLOCK);
+    if options.gen_arbitrary_impls {
+        mod_body.extend(gen_round_trip_tests(&schema.entity_types));
+    }
+
+    gen_srv_doc_root(odata_srv_name, mod_body, &crate_refs)
+}
+
+// ---------------------------------------------------------------------------------------------------------------------
+/// Generate the service document module with each complex type and entity type in a file of its own, and the marker
+/// types of the entity sets in the file `entity_sets.rs`.  The files are added to `tree` below `srv_dir`, and the source
+/// code declaring the service document module is returned
+pub fn gen_srv_doc_module_tree(
+    odata_srv_name: &str,
+    schema: &Schema,
+    options: &GeneratorOptions,
+    tree: &mut ModuleTree,
+    srv_dir: &Path,
+) -> TokenStream {
+    let mut crate_refs: BTreeSet<String> = BTreeSet::new();
+    let mut mod_body = gen_use_path(PATH_TO_SERDE_SERIALIZE_DESERIALIZE);
+    let ct_derives = resolve_complex_type_derives(
+        schema.complex_types.as_deref().unwrap_or_default(),
+        &options.type_attributes.complex_types,
+    );
+
+    if let Some(cts) = &schema.complex_types {
+        let ct_mods: Vec<(String, TokenStream)> = cts
+            .iter()
+            .filter_map(|ct| {
+                let (ct_src, crs) = gen_complex_type_src(ct, &ct_derives, options);
+                crate_refs.extend(crs);
+                ct_src.map(|src| (odata_name_to_rust_safe_name(&ct.name), src))
+            })
+            .collect();
 
-    // Add any external crate references
-    for cr in crate_refs {
-        gen_extern_crate_into(&mut out_buffer, &cr);
+        mod_body.extend(tree.add_types_module(srv_dir.join(DIR_COMPLEX_TYPES), DIR_COMPLEX_TYPES, ct_mods));
     }
 
-    out_buffer
+    let et_mods: Vec<(String, TokenStream)> = schema
+        .entity_types
+        .iter()
+        .map(|entity| {
+            let (et_src, crs) = gen_entity_type(entity, &ct_derives, options);
+            crate_refs.extend(crs);
+            (odata_name_to_rust_safe_name(&entity.name), et_src)
+        })
+        .collect();
+
+    mod_body.extend(tree.add_types_module(srv_dir.join(DIR_ENTITY_TYPES), DIR_ENTITY_TYPES, et_mods));
+    mod_body.extend(gen_parameter_sets(schema));
+
+    if let Some(ent_cont) = &schema.entity_container {
+        mod_body.extend(ent_cont.to_enum_with_options(options));
+        mod_body.extend(tree.add_module(
+            srv_dir.join(FILE_ENTITY_SETS),
+            "entity_sets",
+            true,
+            ent_cont.to_entity_sets_items(),
+        ));
+    }
+
+    if options.gen_arbitrary_impls {
+        mod_body.extend(gen_round_trip_tests(&schema.entity_types));
+    }
+
+    gen_srv_doc_root(odata_srv_name, mod_body, &crate_refs)
+}
+
+// ---------------------------------------------------------------------------------------------------------------------
+/// Wrap the body of the service document module in its module declaration, together with the external crates it needs
+fn gen_srv_doc_root(odata_srv_name: &str, mod_body: TokenStream, crate_refs: &BTreeSet<String>) -> TokenStream {
+    // External crate dependencies on serde and quick_xml are always required
+    let extern_crates = gen_extern_crate(CRATE_QUICK_XML);
+    let serde_crate = gen_extern_crate(CRATE_SERDE);
+    let mod_ident = gen_ident(odata_srv_name);
+
+    // Add any external crate references after the module definition
+    let crate_ref_decls = crate_refs.iter().map(|cr| gen_extern_crate(cr));
+
+    quote! {
+        #extern_crates
+        #serde_crate
+        pub mod #mod_ident {
+            #mod_body
+        }
+        #(#crate_ref_decls)*
+    }
 }
 
 // ---------------------------------------------------------------------------------------------------------------------
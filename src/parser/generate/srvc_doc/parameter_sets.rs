@@ -0,0 +1,148 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    edmx::data_services::schema::{entity_type::EntityType, Schema},
+    parser::generate::{gen_ident, syntax_fragments::derive_traits::*},
+    property::Property,
+    sap_annotations::parameter::SAPParameterProperty,
+    utils::{odata_name_to_rust_safe_name, to_upper_camel_case},
+};
+
+static SUFFIX_PARAMS: &str = "Params";
+static NAV_PROP_RESULTS: &str = "Results";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate a parameter struct for each entity set whose entity type is annotated with `sap:semantics="parameters"`.
+///
+/// For the entity set `ZQuery`, the struct `ZQueryParams` is generated containing one field per parameter.
+/// Mandatory parameters must be passed to `ZQueryParams::new()`, optional parameters are `Option` fields that default to
+/// `None`.
+///
+/// The struct's `to_path()` function returns the entity set name followed by the key predicate containing the
+/// parameter values, E.G. `ZQuery(P_Year=2024,P_Plant='1000')`.
+/// If the entity type has a navigation property to its results, `to_results_path()` returns the path to the results,
+/// E.G. `ZQuery(P_Year=2024,P_Plant='1000')/Results`
+pub fn gen_parameter_sets(schema: &Schema) -> TokenStream {
+    let Some(ent_cont) = &schema.entity_container else {
+        return TokenStream::new();
+    };
+
+    ent_cont
+        .entity_sets
+        .iter()
+        .filter_map(|ent_set| {
+            let et_name = ent_set.entity_type.rsplit('.').next().unwrap_or_default();
+
+            schema
+                .entity_types
+                .iter()
+                .find(|et| et.name.eq(et_name) && et.is_parameters())
+                .map(|et| gen_parameter_set(&ent_set.name, et))
+        })
+        .collect()
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn is_mandatory(prop: &Property) -> bool {
+    // Parameters without an sap:parameter annotation are treated as mandatory because they are part of the key
+    !matches!(prop.sap_annotations.parameter, Some(SAPParameterProperty::Optional))
+}
+
+// The type of the parameter without any Option wrapper
+fn param_type(prop: &Property) -> TokenStream {
+    Property {
+        nullable: false,
+        ..prop.clone()
+    }
+    .to_rust_type()
+}
+
+pub fn gen_parameter_set(ent_set_name: &str, entity: &EntityType) -> TokenStream {
+    let struct_ident = gen_ident(&format!("{}{SUFFIX_PARAMS}", to_upper_camel_case(ent_set_name)));
+    let derive = gen_derive(&[DeriveTraits::CLONE, DeriveTraits::DEBUG, DeriveTraits::PARTIALEQ]);
+    let params: Vec<&Property> = entity.properties.iter().collect();
+    let (mandatory, optional): (Vec<&Property>, Vec<&Property>) = params.iter().partition(|p| is_mandatory(p));
+    let field_ident = |prop: &Property| gen_ident(&odata_name_to_rust_safe_name(&prop.odata_name));
+
+    // Struct declaration
+    let fields = params.iter().map(|prop| {
+        let field = field_ident(prop);
+        let field_type = param_type(prop);
+
+        if is_mandatory(prop) {
+            quote!(pub #field: #field_type,)
+        } else {
+            quote!(pub #field: Option<#field_type>,)
+        }
+    });
+
+    // Constructor taking only the mandatory parameters
+    let args = mandatory.iter().map(|prop| {
+        let field = field_ident(prop);
+        let field_type = param_type(prop);
+        quote!(#field: #field_type)
+    });
+    let mandatory_fields = mandatory.iter().map(|prop| field_ident(prop));
+    let optional_fields = optional.iter().map(|prop| field_ident(prop));
+
+    // Key predicate containing the parameter values in the order they are declared in the metadata
+    let push_stmts = params.iter().map(|prop| {
+        let field = field_ident(prop);
+        let name_value_fmt = format!("{}={{}}", prop.odata_name);
+
+        if is_mandatory(prop) {
+            let literal = prop.gen_odata_literal_expr(quote!(&self.#field));
+            quote!(params.push(format!(#name_value_fmt, #literal));)
+        } else {
+            let literal = prop.gen_odata_literal_expr(quote!(value));
+            quote! {
+                if let Some(value) = &self.#field {
+                    params.push(format!(#name_value_fmt, #literal));
+                }
+            }
+        }
+    });
+
+    // Path to the parameterised entity
+    let path_fmt = format!("{ent_set_name}{{}}");
+
+    // Path to the results, preferring a navigation property called "Results"
+    let fn_to_results_path = entity
+        .navigations
+        .iter()
+        .find(|nav| nav.name.eq(NAV_PROP_RESULTS))
+        .or(entity.navigations.first())
+        .map(|nav| {
+            let results_path_fmt = format!("{{}}/{}", nav.name);
+            quote! {
+                pub fn to_results_path(&self) -> String {
+                    format!(#results_path_fmt, self.to_path())
+                }
+            }
+        });
+
+    quote! {
+        #derive
+        pub struct #struct_ident {
+            #(#fields)*
+        }
+        impl #struct_ident {
+            pub fn new(#(#args),*) -> Self {
+                #struct_ident {
+                    #(#mandatory_fields,)*
+                    #(#optional_fields: None,)*
+                }
+            }
+            pub fn key_predicate(&self) -> String {
+                let mut params: Vec<String> = Vec::new();
+                #(#push_stmts)*
+                format!("({})", params.join(","))
+            }
+            pub fn to_path(&self) -> String {
+                format!(#path_fmt, self.key_predicate())
+            }
+            #fn_to_results_path
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    parser::generate::{
+        gen_ident, gen_path,
+        sql_ddl::{sql_column_name, sql_table_name},
+        syntax_fragments::PATH_TO_SQL_VALUE,
+    },
+    property::{metadata::PropertyType, Property},
+    utils::{odata_name_to_rust_safe_name, to_upper_camel_case},
+};
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate an implementation of `parse_sap_odata::sql::SqlColumns` that maps each field to a column of the table
+/// generated by [`gen_sql_ddl`](crate::parser::generate::sql_ddl::gen_sql_ddl).
+///
+/// Complex type fields are flattened by delegating to the complex type's own implementation, using the field's column
+/// name followed by an underscore as the prefix of the complex type's column names
+pub fn gen_sql_columns_impl(struct_name: &str, props: &[Property]) -> TokenStream {
+    let sql_value = gen_path(PATH_TO_SQL_VALUE);
+    let mut names: Vec<TokenStream> = Vec::new();
+    let mut values: Vec<TokenStream> = Vec::new();
+
+    for prop in props {
+        let field = gen_ident(&odata_name_to_rust_safe_name(&prop.odata_name));
+        let column_name = sql_column_name(&prop.odata_name);
+
+        if let PropertyType::Complex(ct_name) = prop.get_property_type() {
+            let ct_struct_ident = gen_ident(&to_upper_camel_case(&ct_name));
+            let prefix_fmt = format!("{{prefix}}{column_name}_");
+            names.push(quote! {
+                names.extend(<#ct_struct_ident as parse_sap_odata::sql::SqlColumns>::sql_column_names(&format!(#prefix_fmt)));
+            });
+            values.push(quote!(values.extend(parse_sap_odata::sql::SqlColumns::to_sql_values(&self.#field));));
+        } else {
+            let column_fmt = format!("{{prefix}}{column_name}");
+            names.push(quote!(names.push(format!(#column_fmt));));
+
+            let value_expr = if prop.is_option_type() {
+                let some_expr = prop.gen_sql_value_expr(quote!(value));
+                quote! {
+                    match &self.#field {
+                        Some(value) => #some_expr,
+                        None => #sql_value::Null,
+                    }
+                }
+            } else {
+                prop.gen_sql_value_expr(quote!(&self.#field))
+            };
+            values.push(quote!(values.push(#value_expr);));
+        }
+    }
+
+    let struct_ident = gen_ident(struct_name);
+
+    quote! {
+        impl parse_sap_odata::sql::SqlColumns for #struct_ident {
+            fn sql_column_names(prefix: &str) -> Vec<String> {
+                let mut names = Vec::new();
+                #(#names)*
+                names
+            }
+
+            fn to_sql_values(&self) -> Vec<#sql_value> {
+                let mut values = Vec::new();
+                #(#values)*
+                values
+            }
+        }
+    }
+}
+
+/// Generate an implementation of `parse_sap_odata::sql::SqlRow` naming the table to which an entity type is mapped
+pub fn gen_sql_row_impl(struct_name: &str, et_name: &str) -> TokenStream {
+    let struct_ident = gen_ident(struct_name);
+    let table_name = sql_table_name(et_name);
+
+    quote! {
+        impl parse_sap_odata::sql::SqlRow for #struct_ident {
+            const SQL_TABLE_NAME: &'static str = #table_name;
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    parser::generate::gen_ident,
+    property::{metadata::PropertyType, Property},
+    texts::TextPairing,
+    utils::odata_name_to_rust_safe_name,
+};
+
+static FN_SUFFIX_WITH_TEXT: &str = "_with_text";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Does the Rust type generated for this property implement `Display`?
+fn is_displayable(prop: &Property) -> bool {
+    match prop.get_property_type() {
+        PropertyType::Edm(edm_type, _) => !matches!(edm_type.as_str(), "Binary" | "Null" | "Time"),
+        _ => false,
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate an impl block containing the display helpers that pair each field having a descriptive text field with
+/// that text.
+///
+/// For a field `category` whose `sap:text` annotation refers to the field `category_name`, the following function is
+/// generated:
+/// * `pub fn category_with_text(&self) -> String`
+///
+/// The function returns `"<category> (<category_name>)"`, or just `"<category>"` if the text is empty or missing.
+///
+/// If the struct contains no such fields, no impl block is generated
+pub fn gen_text_accessors(struct_name: &str, props: &[Property]) -> TokenStream {
+    let mut accessor_fns: Vec<TokenStream> = Vec::new();
+
+    for pairing in TextPairing::find_in(props) {
+        let value = props.iter().find(|p| p.odata_name.eq(&pairing.value_property)).unwrap();
+        let text = props.iter().find(|p| p.odata_name.eq(&pairing.text_property)).unwrap();
+
+        // The text must be a plain string field and the value must be printable
+        if !text.is_string_type() || !is_displayable(value) {
+            continue;
+        }
+
+        let value_field_name = odata_name_to_rust_safe_name(&value.odata_name);
+        let value_field = gen_ident(&value_field_name);
+        let text_field = gen_ident(&odata_name_to_rust_safe_name(&text.odata_name));
+
+        let value_expr = if !value.is_option_type() {
+            quote!(&self.#value_field)
+        } else if value.is_string_type() {
+            quote!(self.#value_field.as_deref().unwrap_or_default())
+        } else {
+            quote!(self.#value_field.as_ref().map(|v| v.to_string()).unwrap_or_default())
+        };
+        let text_expr = if text.nullable {
+            quote!(self.#text_field.as_deref())
+        } else {
+            quote!(Some(self.#text_field.as_str()))
+        };
+
+        // pub fn <value>_with_text(&self) -> String { value_with_text(<value>, <text>) }
+        let fn_ident = gen_ident(&format!("{}{FN_SUFFIX_WITH_TEXT}", value_field_name.trim_start_matches("r#")));
+        accessor_fns.push(quote! {
+            pub fn #fn_ident(&self) -> String {
+                parse_sap_odata::texts::value_with_text(#value_expr, #text_expr)
+            }
+        });
+    }
+
+    if accessor_fns.is_empty() {
+        TokenStream::new()
+    } else {
+        let struct_ident = gen_ident(struct_name);
+        quote! {
+            impl #struct_ident {
+                #(#accessor_fns)*
+            }
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::{
+    amounts::{UnitKind, UnitPairing},
+    parser::generate::gen_ident,
+    property::Property,
+    utils::odata_name_to_rust_safe_name,
+};
+
+static FN_SUFFIX_WITH_CURRENCY: &str = "_with_currency";
+static FN_SUFFIX_WITH_UNIT: &str = "_with_unit";
+static FN_SUFFIX_AS_MONEY: &str = "_as_money";
+static FN_SUFFIX_AS_QUANTITY: &str = "_as_quantity";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate an impl block containing the accessor functions that pair each amount or quantity field with its currency
+/// code or unit of measure.
+///
+/// For an amount field `price` whose `sap:unit` annotation refers to a currency code, two functions are generated:
+/// * `pub fn price_with_currency(&self) -> (rust_decimal::Decimal, &str)`
+/// * `pub fn price_as_money(&self) -> parse_sap_odata::amounts::Money<rust_decimal::Decimal>`
+///
+/// Quantity fields receive `_with_unit` and `_as_quantity` functions instead.
+///
+/// If the struct contains no such fields, no impl block is generated
+pub fn gen_unit_accessors(struct_name: &str, props: &[Property]) -> TokenStream {
+    let mut accessor_fns: Vec<TokenStream> = Vec::new();
+
+    for pairing in UnitPairing::find_in(props) {
+        let amount = props.iter().find(|p| p.odata_name.eq(&pairing.amount_property)).unwrap();
+        let unit = props.iter().find(|p| p.odata_name.eq(&pairing.unit_property)).unwrap();
+
+        // The unit must be a plain string field
+        if !unit.is_string_type() {
+            continue;
+        }
+
+        let amount_field_name = odata_name_to_rust_safe_name(&amount.odata_name);
+        let amount_field = gen_ident(&amount_field_name);
+        let unit_field = gen_ident(&odata_name_to_rust_safe_name(&unit.odata_name));
+
+        let (with_suffix, as_suffix, value_path) = if pairing.kind == UnitKind::Currency {
+            (FN_SUFFIX_WITH_CURRENCY, FN_SUFFIX_AS_MONEY, quote!(parse_sap_odata::amounts::Money))
+        } else {
+            (FN_SUFFIX_WITH_UNIT, FN_SUFFIX_AS_QUANTITY, quote!(parse_sap_odata::amounts::Quantity))
+        };
+
+        let amount_expr = if amount.is_copy_type() {
+            quote!(self.#amount_field)
+        } else {
+            quote!(self.#amount_field.clone())
+        };
+        let unit_expr = if unit.nullable {
+            quote!(self.#unit_field.as_deref().unwrap_or_default())
+        } else {
+            quote!(&self.#unit_field)
+        };
+
+        // The type of the amount field with and without a possible Option wrapper
+        let amount_type = amount.to_rust_type();
+        let inner_amount_type = Property {
+            nullable: false,
+            ..amount.clone()
+        }
+        .to_rust_type();
+        let value_type = quote!(#value_path<#inner_amount_type>);
+
+        // pub fn <amount>_with_<currency|unit>(&self) -> (<amount_type>, &str) { (<amount>, <unit>) }
+        let fn_name_root = amount_field_name.trim_start_matches("r#");
+        let with_fn_ident = gen_ident(&format!("{fn_name_root}{with_suffix}"));
+        accessor_fns.push(quote! {
+            pub fn #with_fn_ident(&self) -> (#amount_type, &str) {
+                (#amount_expr, #unit_expr)
+            }
+        });
+
+        // pub fn <amount>_as_<money|quantity>(&self) -> [Option<]<Money|Quantity><..>[>]
+        let as_fn_ident = gen_ident(&format!("{fn_name_root}{as_suffix}"));
+        let (as_return_type, as_body) = if amount.is_option_type() {
+            (
+                quote!(Option<#value_type>),
+                quote!(#amount_expr.map(|amount| #value_path::new(amount, #unit_expr))),
+            )
+        } else {
+            (value_type, quote!(#value_path::new(#amount_expr, #unit_expr)))
+        };
+        accessor_fns.push(quote! {
+            pub fn #as_fn_ident(&self) -> #as_return_type {
+                #as_body
+            }
+        });
+    }
+
+    if accessor_fns.is_empty() {
+        TokenStream::new()
+    } else {
+        let struct_ident = gen_ident(struct_name);
+        quote! {
+            impl #struct_ident {
+                #(#accessor_fns)*
+            }
+        }
+    }
+}
@@ -1,12 +1,32 @@
 use std::collections::BTreeSet;
 
 use crate::{
-    edmx::data_services::schema::complex_type::ComplexType,
-    parser::generate::srvc_doc::complex_types::gen_complex_types, property::metadata::PropertyType,
-    test_utils::{handle_test_comparison, handle_test_bool},
+    analytics::unit_tests::fetch_entity_type_sales_order_query,
+    hierarchy::unit_tests::fetch_entity_type_cost_center_hierarchy,
+    edmx::data_services::schema::{complex_type::ComplexType, entity_type::EntityType},
+    parser::{
+        generate::{
+            srvc_doc::{
+            aggregate_enums::gen_aggregate_enums,
+            arbitrary::{gen_arbitrary_impl, gen_round_trip_tests},
+            borrowed_structs::gen_borrowed_struct,
+            complex_types::gen_complex_types, entity_types::gen_entity_type, filter_fields::gen_filter_field_enum,
+            hierarchy_node::gen_hierarchy_node_impl, parameter_sets::gen_parameter_set,
+            sql_rows::{gen_sql_columns_impl, gen_sql_row_impl}, text_accessors::gen_text_accessors,
+            unit_accessors::gen_unit_accessors,
+            },
+            type_attributes::resolve_complex_type_derives,
+        },
+        options::GeneratorOptions,
+    },
+    property::metadata::PropertyType,
+    test_utils::{fetch_mock_service_edmx, handle_test_bool, handle_test_comparison, tokens_to_rust_src},
+    texts::unit_tests::fetch_entity_type_product_with_texts,
 };
 
 use chrono;
+use proc_macro2::TokenStream;
+use quote::quote;
 use rust_decimal;
 use serde::Deserialize;
 use std::{
@@ -18,8 +38,17 @@ use std::{
 
 static PATH_TO_COMPLEX_TYPE_METADATA: &str = "./test_data/complex_type_metadata.xml";
 static PATH_TO_COMPLEX_TYPE: &str = "./test_data/complex_type_pallet.xml";
+static PATH_TO_ENTITY_TYPE_PRODUCT: &str = "./test_data/entity_type_product.xml";
+static PATH_TO_ENTITY_TYPE_QUERY_PARAMETERS: &str = "./test_data/entity_type_query_parameters.xml";
+// Compiled into the feed_entries unit tests, so must be identical to the source generated today
+static PATH_TO_GENERATED_PRODUCT: &str = "./test_data/generated_product.rs";
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Pretty print generated source code with indentation removed
+fn to_src(tokens: TokenStream) -> String {
+    tokens_to_rust_src(tokens).join("\n")
+}
+
 impl FromStr for ComplexType {
     type Err = quick_xml::DeError;
 
@@ -122,7 +151,9 @@ fn should_generate_extern_crate_refs() -> Result<(), String> {
     match String::from_utf8(xml_buffer) {
         Ok(xml) => {
             let result = ComplexType::from_str(&xml).unwrap();
-            let (_src_code, crate_refs) = gen_complex_types(&vec![result]);
+            let cts = [result];
+            let ct_derives = resolve_complex_type_derives(&cts, &[]);
+            let (_src_code, crate_refs) = gen_complex_types(&cts, &ct_derives, &GeneratorOptions::default());
             let crs: BTreeSet<String> = crate_refs.into_iter().collect();
 
             handle_test_comparison(&crs.len(), &2)?;
@@ -132,3 +163,279 @@ fn should_generate_extern_crate_refs() -> Result<(), String> {
         Err(err) => Err(format!("XML test data was not in UTF8 format: {err}")),
     }
 }
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_generate_unit_accessors() -> Result<(), String> {
+    let mut xml_buffer: Vec<u8> = Vec::new();
+    let test_data = File::open(Path::new(PATH_TO_ENTITY_TYPE_PRODUCT)).unwrap();
+    let _file_size = BufReader::new(test_data).read_to_end(&mut xml_buffer);
+
+    match String::from_utf8(xml_buffer) {
+        Ok(xml) => {
+            let product = EntityType::from_str(&xml).unwrap();
+            let src = to_src(gen_unit_accessors("Product", &product.properties));
+
+            handle_test_bool(src.contains("impl Product {"))?;
+            handle_test_bool(src.contains(
+                "pub fn price_with_currency(&self) -> (Option<rust_decimal::Decimal>, &str) {\n(self.price, &self.currency_code)\n}",
+            ))?;
+            handle_test_bool(src.contains(
+                ") -> Option<parse_sap_odata::amounts::Money<rust_decimal::Decimal>> {\nself.price\n.map(|amount| parse_sap_odata::amounts::Money::new(\namount,\n&self.currency_code,\n))",
+            ))?;
+            handle_test_bool(src.contains(
+                "pub fn weight_measure_with_unit(&self) -> (Option<rust_decimal::Decimal>, &str) {\n(self.weight_measure, self.weight_unit.as_deref().unwrap_or_default())\n}",
+            ))?;
+            handle_test_bool(src.contains("pub fn width_as_quantity("))
+        },
+        Err(err) => Err(format!("XML test data was not in UTF8 format: {err}")),
+    }
+}
+
+#[test]
+fn should_not_generate_unit_accessors_without_pairings() -> Result<(), String> {
+    let mut xml_buffer: Vec<u8> = Vec::new();
+    let test_data = File::open(Path::new(PATH_TO_COMPLEX_TYPE_METADATA)).unwrap();
+    let _file_size = BufReader::new(test_data).read_to_end(&mut xml_buffer);
+
+    match String::from_utf8(xml_buffer) {
+        Ok(xml) => {
+            let result = ComplexType::from_str(&xml).unwrap();
+            handle_test_bool(gen_unit_accessors("CtPallet", &result.properties).is_empty())
+        },
+        Err(err) => Err(format!("XML test data was not in UTF8 format: {err}")),
+    }
+}
+
+#[test]
+fn should_generate_text_accessors() -> Result<(), String> {
+    let product = fetch_entity_type_product_with_texts()?;
+    let src = to_src(gen_text_accessors("Product", &product.properties));
+
+    handle_test_bool(src.contains("impl Product {"))?;
+    handle_test_bool(src.contains(
+        "pub fn product_id_with_text(&self) -> String {\nparse_sap_odata::texts::value_with_text(\n&self.product_id,\nSome(self.name.as_str()),\n)",
+    ))?;
+    handle_test_bool(src.contains(
+        "pub fn supplier_id_with_text(&self) -> String {\nparse_sap_odata::texts::value_with_text(\n&self.supplier_id,\nself.supplier_name.as_deref(),\n)",
+    ))
+}
+
+#[test]
+fn should_not_generate_text_accessors_without_pairings() -> Result<(), String> {
+    let mut xml_buffer: Vec<u8> = Vec::new();
+    let test_data = File::open(Path::new(PATH_TO_ENTITY_TYPE_PRODUCT)).unwrap();
+    let _file_size = BufReader::new(test_data).read_to_end(&mut xml_buffer);
+
+    match String::from_utf8(xml_buffer) {
+        Ok(xml) => {
+            let product = EntityType::from_str(&xml).unwrap();
+            handle_test_bool(gen_text_accessors("Product", &product.properties).is_empty())
+        },
+        Err(err) => Err(format!("XML test data was not in UTF8 format: {err}")),
+    }
+}
+
+#[test]
+fn should_generate_borrowed_struct() -> Result<(), String> {
+    let product = fetch_entity_type_product_with_texts()?;
+    let src = to_src(gen_borrowed_struct("Product", &product.properties));
+
+    handle_test_bool(src.contains("pub struct ProductBorrowed<'a> {"))?;
+    handle_test_bool(src.contains("#[serde(borrow)]\npub name: std::borrow::Cow<'a, str>,"))?;
+    handle_test_bool(src.contains("pub supplier_name: Option<std::borrow::Cow<'a, str>>,"))?;
+    handle_test_bool(src.contains("parse_sap_odata::deserializers::to_nullable_cow_str"))?;
+    handle_test_bool(src.contains("pub tax_tarif_code: u8,"))?;
+    handle_test_bool(src.contains("pub fn into_owned(self) -> Product {"))
+}
+
+#[test]
+fn should_match_compiled_borrowed_struct_fixture() -> Result<(), String> {
+    let edmx = fetch_mock_service_edmx()?;
+    let product = edmx
+        .data_services
+        .fetch_schema("GWSAMPLE_BASIC")
+        .and_then(|schema| schema.entity_types.iter().find(|et| et.name.eq("Product")))
+        .ok_or("Entity type Product not found")?;
+    let options = GeneratorOptions {
+        gen_borrowed_structs: true,
+        ..Default::default()
+    };
+    let (tokens, _) = gen_entity_type(product, &Default::default(), &options);
+    let src = prettyplease::unparse(&syn::parse2::<syn::File>(tokens).map_err(|err| err.to_string())?);
+
+    let mut fixture = String::new();
+    let fixture_file = File::open(Path::new(PATH_TO_GENERATED_PRODUCT)).map_err(|err| err.to_string())?;
+    let _file_size = BufReader::new(fixture_file).read_to_string(&mut fixture);
+
+    handle_test_comparison(&fixture, &src)
+}
+
+#[test]
+fn should_generate_aggregate_enums() -> Result<(), String> {
+    let query_result = fetch_entity_type_sales_order_query()?;
+    let src = to_src(gen_aggregate_enums(&query_result));
+
+    handle_test_bool(src.contains("pub enum SalesOrderQueryResultDimension {\nCompanyCode,\nCustomer,\nCustomerCountry,\n}"))?;
+    handle_test_bool(src.contains("pub enum SalesOrderQueryResultMeasure {\nNetAmount,\nOrderCount,\n}"))?;
+    handle_test_bool(src.contains(
+        "impl parse_sap_odata::analytics::AggregateDimension for SalesOrderQueryResultDimension {",
+    ))?;
+    handle_test_bool(src.contains("Self::CustomerCountry => Some(Self::Customer),"))?;
+    handle_test_bool(src.contains("Self::Customer => Some(Self::CompanyCode),"))?;
+    handle_test_bool(src.contains("impl parse_sap_odata::analytics::AggregateMeasure for SalesOrderQueryResultMeasure {"))
+}
+
+#[test]
+fn should_not_generate_aggregate_enums_for_ordinary_entity_type() -> Result<(), String> {
+    let product = fetch_entity_type_product_with_texts()?;
+    handle_test_bool(gen_aggregate_enums(&product).is_empty())
+}
+
+#[test]
+fn should_generate_filter_field_enum() -> Result<(), String> {
+    let query_result = fetch_entity_type_sales_order_query()?;
+    let src = to_src(gen_filter_field_enum(&query_result));
+
+    // Properties annotated with sap:filterable="false" are not filter fields
+    handle_test_bool(src.contains(
+        "pub enum SalesOrderQueryResultFilterField {\nCompanyCode,\nCompanyCodeName,\nCustomer,\nCustomerCountry,\nPostingDate,\nCurrency,\n}",
+    ))?;
+    handle_test_bool(src.contains("impl parse_sap_odata::query::filter::FilterField for SalesOrderQueryResultFilterField {"))?;
+    handle_test_bool(src.contains("Self::CompanyCode => true,"))?;
+    handle_test_bool(src.contains(
+        "Self::PostingDate => {\nSome(\nparse_sap_odata::sap_annotations::filter_restriction::SAPFilterRestrictionProperty::Interval,\n)",
+    ))?;
+    handle_test_bool(src.contains("fn required_fields() -> &'static [Self] {\n&[Self::CompanyCode]\n}"))
+}
+
+#[test]
+fn should_not_generate_filter_field_enum_without_filter_annotations() -> Result<(), String> {
+    let product = fetch_entity_type_product_with_texts()?;
+    handle_test_bool(gen_filter_field_enum(&product).is_empty())
+}
+
+#[test]
+fn should_generate_hierarchy_node_impl() -> Result<(), String> {
+    let cost_centers = fetch_entity_type_cost_center_hierarchy()?;
+    let src = to_src(gen_hierarchy_node_impl("CostCenterHierarchyNode", &cost_centers.properties));
+
+    handle_test_bool(src.contains("impl parse_sap_odata::hierarchy::HierarchyNode for CostCenterHierarchyNode {"))?;
+    handle_test_bool(src.contains("fn node_id(&self) -> String {\nself.hierarchy_node.clone()\n}"))?;
+    handle_test_bool(src.contains("fn parent_node_id(&self) -> Option<String> {\nself.parent_node.clone()\n}"))?;
+    handle_test_bool(src.contains("fn rank(&self) -> Option<i64> {\nself.preorder_rank.map(i64::from)\n}"))
+}
+
+#[test]
+fn should_generate_arbitrary_impl() -> Result<(), String> {
+    let mut xml_buffer: Vec<u8> = Vec::new();
+    let test_data = File::open(Path::new(PATH_TO_ENTITY_TYPE_PRODUCT)).unwrap();
+    let _file_size = BufReader::new(test_data).read_to_end(&mut xml_buffer);
+
+    match String::from_utf8(xml_buffer) {
+        Ok(xml) => {
+            let product = EntityType::from_str(&xml).unwrap();
+            let src = to_src(gen_arbitrary_impl("Product", &product.properties));
+
+            handle_test_bool(src.starts_with(
+                "#[cfg(feature = \"proptest\")]\nimpl proptest::arbitrary::Arbitrary for Product {",
+            ))?;
+            // Strings respect MaxLength, and nullable properties are optional
+            handle_test_bool(src.contains("\"[A-Za-z0-9]{1,10}\",\n\"[A-Za-z0-9]{1,2}\","))?;
+            handle_test_bool(src.contains("proptest::option::of(\"[A-Za-z0-9]{1,64}\")"))?;
+            // Decimals respect Precision and Scale
+            handle_test_bool(src.contains(
+                "proptest::option::of(\n(-9999999999999999i64..=9999999999999999i64)\n.prop_map(|mantissa| rust_decimal::Decimal::new(mantissa, 3)),\n)",
+            ))?;
+            // Product has more properties than can be held in a single tuple
+            handle_test_bool(src.contains(".prop_map(|\n(\n(\nproduct_id,\ntype_code,\ncategory,\nname,\nname_language,"))?;
+            handle_test_bool(src.contains("dim_unit,\ncreated_at,\n),\n(changed_at,),\n)|\nProduct {\nproduct_id,\ntype_code,"))
+        },
+        Err(err) => Err(format!("XML test data was not in UTF8 format: {err}")),
+    }
+}
+
+#[test]
+fn should_limit_decimal_digits_when_scale_exceeds_maximum() -> Result<(), String> {
+    let et = EntityType::from_str(
+        r#"<EntityType Name="Rate"><Key><PropertyRef Name="ID"/></Key><Property Name="ID" Type="Edm.Int32" Nullable="false"/><Property Name="Rate" Type="Edm.Decimal" Nullable="false" Precision="22" Scale="20"/></EntityType>"#,
+    )
+    .map_err(|err| err.to_string())?;
+    let src = to_src(gen_arbitrary_impl("Rate", &et.properties));
+
+    handle_test_bool(src.contains(
+        "(-999999999999999999i64..=999999999999999999i64)\n.prop_map(|mantissa| rust_decimal::Decimal::new(mantissa, 20))",
+    ))
+}
+
+#[test]
+fn should_generate_round_trip_tests() -> Result<(), String> {
+    let product = fetch_entity_type_product_with_texts()?;
+    let cost_centers = fetch_entity_type_cost_center_hierarchy()?;
+    let tokens = gen_round_trip_tests(&[product, cost_centers]);
+    let src = to_src(tokens.clone());
+
+    handle_test_bool(src.starts_with("#[cfg(all(test, feature = \"proptest\"))]\nmod round_trip_tests {"))?;
+
+    // The body of the proptest! macro is printed verbatim, so compare token streams instead
+    let src = tokens.to_string();
+    handle_test_bool(src.contains(&quote!(fn product_round_trip(value in proptest::arbitrary::any::<Product>())).to_string()))?;
+    handle_test_bool(src.contains(
+        &quote!(fn cost_center_hierarchy_node_round_trip(value in proptest::arbitrary::any::<CostCenterHierarchyNode>()))
+            .to_string(),
+    ))?;
+    handle_test_bool(src.contains(&quote!(let result = parse_sap_odata::round_trip::check_round_trip(&value);).to_string()))
+}
+
+#[test]
+fn should_generate_parameter_set() -> Result<(), String> {
+    let mut xml_buffer: Vec<u8> = Vec::new();
+    let test_data = File::open(Path::new(PATH_TO_ENTITY_TYPE_QUERY_PARAMETERS)).unwrap();
+    let _file_size = BufReader::new(test_data).read_to_end(&mut xml_buffer);
+
+    match String::from_utf8(xml_buffer) {
+        Ok(xml) => {
+            let query_params = EntityType::from_str(&xml).unwrap();
+            handle_test_bool(query_params.is_parameters())?;
+
+            let src = to_src(gen_parameter_set("SalesOrderQuery", &query_params));
+
+            handle_test_bool(src.contains("pub struct SalesOrderQueryParams {"))?;
+            handle_test_bool(src.contains("pub p_display_currency: Option<String>,"))?;
+            handle_test_bool(src.contains(
+                "pub fn new(p_year: i32, p_plant: String) -> Self {\nSalesOrderQueryParams {\np_year,\np_plant,\np_display_currency: None,\n}",
+            ))?;
+            handle_test_bool(src.contains("params.push(format!(\"P_Year={}\", (&self.p_year).to_string()));"))?;
+            handle_test_bool(src.contains(
+                "if let Some(value) = &self.p_display_currency {\nparams\n.push(\nformat!(\n\"P_DisplayCurrency={}\",\nparse_sap_odata::query::string_literal(value),\n),\n);",
+            ))?;
+            handle_test_bool(src.contains(
+                "pub fn to_path(&self) -> String {\nformat!(\"SalesOrderQuery{}\", self.key_predicate())\n}",
+            ))?;
+            handle_test_bool(src.contains(
+                "pub fn to_results_path(&self) -> String {\nformat!(\"{}/Results\", self.to_path())\n}",
+            ))
+        },
+        Err(err) => Err(format!("XML test data was not in UTF8 format: {err}")),
+    }
+}
+
+#[test]
+fn should_generate_sql_row_impls() -> Result<(), String> {
+    let product = fetch_entity_type_product_with_texts()?;
+    let src = to_src(gen_sql_columns_impl("Product", &product.properties));
+
+    handle_test_bool(src.contains("impl parse_sap_odata::sql::SqlColumns for Product {"))?;
+    handle_test_bool(src.contains("names.push(format!(\"{prefix}product_id\"));"))?;
+    handle_test_bool(src.contains("values.push(parse_sap_odata::sql::SqlValue::Text((&self.product_id).clone()));"))?;
+    handle_test_bool(src.contains(
+        "parse_sap_odata::sql::SqlValue::Integer(i64::from(*&self.tax_tarif_code)),",
+    ))?;
+    // Nullable properties become NULL
+    handle_test_bool(src.contains(
+        "match &self.price {\nSome(value) => {\nparse_sap_odata::sql::SqlValue::Text((value).to_string())\n}\nNone => parse_sap_odata::sql::SqlValue::Null,\n},",
+    ))?;
+
+    let src = to_src(gen_sql_row_impl("Product", "Product"));
+    handle_test_bool(src.contains("const SQL_TABLE_NAME: &'static str = \"product\";"))
+}
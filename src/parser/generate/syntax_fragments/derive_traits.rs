@@ -1,9 +1,13 @@
-use super::{CLOSE_PAREN, CLOSE_SQR, COMMA, DERIVE_START, LINE_FEED};
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use crate::parser::generate::gen_ident;
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// Derivable traits.
 ///
 /// The Serde traits `Serialize` and `Deserialize` are also included
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum DeriveTraits {
     CLONE,
     COPY,
@@ -21,37 +25,35 @@ pub enum DeriveTraits {
 
 impl DeriveTraits {
     /// Transform Enum variant name to the trait name added to the source code
-    pub fn value(&self) -> &'static [u8] {
+    pub fn value(&self) -> &'static str {
         match *self {
-            DeriveTraits::CLONE => b"Clone",
-            DeriveTraits::COPY => b"Copy",
-            DeriveTraits::DEBUG => b"Debug",
-            DeriveTraits::DEFAULT => b"Default",
-            DeriveTraits::DESERIALIZE => b"Deserialize",
-            DeriveTraits::EQ => b"Eq",
-            DeriveTraits::HASH => b"Hash",
-            DeriveTraits::ITERATOR => b"Iterator",
-            DeriveTraits::ORD => b"Ord",
-            DeriveTraits::PARTIALEQ => b"PartialEq",
-            DeriveTraits::PARTIALORD => b"PartialOrd",
-            DeriveTraits::SERIALIZE => b"Serialize",
+            DeriveTraits::CLONE => "Clone",
+            DeriveTraits::COPY => "Copy",
+            DeriveTraits::DEBUG => "Debug",
+            DeriveTraits::DEFAULT => "Default",
+            DeriveTraits::DESERIALIZE => "Deserialize",
+            DeriveTraits::EQ => "Eq",
+            DeriveTraits::HASH => "Hash",
+            DeriveTraits::ITERATOR => "Iterator",
+            DeriveTraits::ORD => "Ord",
+            DeriveTraits::PARTIALEQ => "PartialEq",
+            DeriveTraits::PARTIALORD => "PartialOrd",
+            DeriveTraits::SERIALIZE => "Serialize",
         }
     }
-}
 
-pub fn gen_derive_str(traits: &[DeriveTraits]) -> Vec<u8> {
-    let mut out_buffer = traits.iter().enumerate().fold(DERIVE_START.to_vec(), |mut acc, (idx, d)| {
-        acc.extend_from_slice(d.value());
-
-        if idx < traits.len() - 1 {
-            acc.extend_from_slice(COMMA);
+    /// The traits that must also be derived before this trait can be derived
+    pub fn required_traits(&self) -> &'static [DeriveTraits] {
+        match *self {
+            DeriveTraits::COPY => &[DeriveTraits::CLONE],
+            DeriveTraits::EQ | DeriveTraits::PARTIALORD => &[DeriveTraits::PARTIALEQ],
+            DeriveTraits::ORD => &[DeriveTraits::EQ, DeriveTraits::PARTIALORD],
+            _ => &[],
         }
+    }
+}
 
-        acc
-    });
-
-    out_buffer.extend_from_slice(CLOSE_PAREN);
-    out_buffer.extend_from_slice(CLOSE_SQR);
-    out_buffer.extend_from_slice(LINE_FEED);
-    out_buffer
+pub fn gen_derive(traits: &[DeriveTraits]) -> TokenStream {
+    let trait_names = traits.iter().map(|d| gen_ident(d.value()));
+    quote!(#[derive(#(#trait_names),*)])
 }
@@ -1,9 +1,9 @@
-use crate::parser::generate::gen_start_struct;
+use proc_macro2::TokenStream;
+use quote::quote;
 
-use super::{
-    derive_traits::{gen_derive_str, DeriveTraits},
-    CLOSE_PAREN, CLOSE_SQR, DOUBLE_QUOTE, LINE_FEED,
-};
+use crate::parser::generate::gen_ident;
+
+use super::derive_traits::DeriveTraits;
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 // Reference the custom DateTime deserializer functions that exist in the parse-odata-atom-feed crate
@@ -24,46 +24,54 @@ pub fn gen_decimal_deserializer_ref(is_nullable: bool, scale: Option<u16>) -> St
     )
 }
 
+// Reference the wrapper in this crate around the scale-aware decimal deserializer that maps `m:null="true"` to `None`
+pub fn gen_nullable_decimal_deserializer_ref(scale: Option<u16>) -> String {
+    format!(
+        "parse_sap_odata::deserializers::edm_decimal::to_nullable_rust_decimal_{}dp",
+        scale.unwrap_or(0)
+    )
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Reference the deserializer for nullable properties whose value may be marked as `m:null="true"`
+pub static NULLABLE_DESERIALIZER_FN: &str = "parse_sap_odata::deserializers::to_nullable";
+
+// Reference the deserializer for the nullable string properties of borrowed structs
+pub static NULLABLE_COW_STR_DESERIALIZER_FN: &str = "parse_sap_odata::deserializers::to_nullable_cow_str";
+
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-pub fn gen_deserialize_with(fn_name: &str) -> Vec<u8> {
-    [
-        "#[serde(deserialize_with = ".as_bytes(),
-        DOUBLE_QUOTE,
-        fn_name.as_bytes(),
-        DOUBLE_QUOTE,
-        CLOSE_PAREN,
-        CLOSE_SQR,
-        LINE_FEED,
-    ]
-    .concat()
+pub fn gen_deserialize_with(fn_name: &str) -> TokenStream {
+    quote!(#[serde(deserialize_with = #fn_name)])
+}
+
+// A missing element must still deserialize as `None` when an `Option` field has a custom deserializer
+pub fn gen_deserialize_with_default(fn_name: &str) -> TokenStream {
+    quote!(#[serde(default, deserialize_with = #fn_name)])
 }
 
-pub fn gen_serde_rename(odata_name: &str) -> Vec<u8> {
-    [
-        "#[serde(rename = ".as_bytes(),
-        DOUBLE_QUOTE,
-        odata_name.as_bytes(),
-        DOUBLE_QUOTE,
-        CLOSE_PAREN,
-        CLOSE_SQR,
-        LINE_FEED,
-    ]
-    .concat()
+pub fn gen_serde_rename(odata_name: &str) -> TokenStream {
+    quote!(#[serde(rename = #odata_name)])
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-pub fn gen_deserializable_struct(struct_name: &str) -> Vec<u8> {
-    [
-        &*gen_derive_str(&[
-            DeriveTraits::CLONE,
-            DeriveTraits::DEBUG,
-            DeriveTraits::DEFAULT,
-            DeriveTraits::SERIALIZE,
-            DeriveTraits::DESERIALIZE,
-        ]),
-        "#[serde(rename_all = \"PascalCase\")]".as_bytes(),
-        LINE_FEED,
-        &*gen_start_struct(struct_name),
-    ]
-    .concat()
+/// The traits always derived by the structs generated for entity types and complex types
+pub static DESERIALIZABLE_STRUCT_DERIVES: [DeriveTraits; 5] = [
+    DeriveTraits::CLONE,
+    DeriveTraits::DEBUG,
+    DeriveTraits::DEFAULT,
+    DeriveTraits::SERIALIZE,
+    DeriveTraits::DESERIALIZE,
+];
+
+/// `type_attrs` holds the derive attribute followed by any other outer attributes of the struct
+pub fn gen_deserializable_struct(struct_name: &str, type_attrs: TokenStream, fields: TokenStream) -> TokenStream {
+    let struct_ident = gen_ident(struct_name);
+
+    quote! {
+        #type_attrs
+        #[serde(rename_all = "PascalCase")]
+        pub struct #struct_ident {
+            #fields
+        }
+    }
 }
@@ -0,0 +1,231 @@
+use std::collections::HashMap;
+
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse::Parser, Attribute};
+
+use crate::{
+    edmx::data_services::schema::complex_type::ComplexType,
+    parser::{
+        generate::syntax_fragments::{
+            derive_traits::{gen_derive, DeriveTraits},
+            serde_fragments::DESERIALIZABLE_STRUCT_DERIVES,
+        },
+        options::TypeAttributeRule,
+    },
+    property::{metadata::PropertyType, Property},
+};
+
+/// The traits derived by each complex type struct, keyed by the complex type's EDM name
+pub type ComplexTypeDerives = HashMap<String, Vec<DeriveTraits>>;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Does `name` match `pattern`, in which `*` matches any sequence of characters?  An empty pattern matches every name
+pub fn matches_name_pattern(pattern: &str, name: &str) -> bool {
+    if pattern.is_empty() {
+        return true;
+    }
+
+    let mut parts = pattern.split('*');
+    let Some(mut rest) = name.strip_prefix(parts.next().unwrap_or_default()) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+
+    match parts.split_last() {
+        // The pattern contains no wildcards
+        None => rest.is_empty(),
+        Some((last, middle)) => {
+            for part in middle {
+                match rest.find(part) {
+                    Some(idx) => rest = &rest[idx + part.len()..],
+                    None => return false,
+                }
+            }
+
+            rest.ends_with(last)
+        },
+    }
+}
+
+fn matching_rules<'a>(
+    type_name: &'a str,
+    rules: &'a [TypeAttributeRule],
+) -> impl Iterator<Item = &'a TypeAttributeRule> {
+    rules
+        .iter()
+        .filter(move |rule| matches_name_pattern(&rule.name_pattern, type_name))
+}
+
+fn warn_not_derivable(type_name: &str, derive: DeriveTraits, reason: &str) {
+    println!(
+        "cargo:warning=Cannot derive `{}` for `{}` because {}",
+        derive.value(),
+        type_name,
+        reason
+    );
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Resolve the traits derived by a generated type: the generator's `base` derives, followed by the extra derives of
+/// every rule matching `type_name`.
+///
+/// An extra derive is skipped with a build warning if `check_derivable` rejects it, or if it depends on another trait
+/// that is not derived
+pub fn resolve_derives<F>(
+    type_name: &str,
+    base: &[DeriveTraits],
+    rules: &[TypeAttributeRule],
+    check_derivable: F,
+) -> Vec<DeriveTraits>
+where
+    F: Fn(DeriveTraits) -> Result<(), String>,
+{
+    let mut derives: Vec<DeriveTraits> = base.to_vec();
+
+    for derive in matching_rules(type_name, rules).flat_map(|rule| rule.derives.iter().copied()) {
+        if derives.contains(&derive) {
+            continue;
+        }
+
+        let checked = if derive == DeriveTraits::ITERATOR {
+            Err("`Iterator` is not a derivable trait".to_owned())
+        } else {
+            check_derivable(derive)
+        };
+
+        match checked {
+            Ok(()) => derives.push(derive),
+            Err(reason) => warn_not_derivable(type_name, derive, &reason),
+        }
+    }
+
+    // Removing one derive can leave another without the traits it depends on, so repeat until nothing more is removed
+    while let Some(idx) = derives
+        .iter()
+        .position(|derive| derive.required_traits().iter().any(|req| !derives.contains(req)))
+    {
+        let derive = derives.remove(idx);
+        let required: Vec<&str> = derive.required_traits().iter().map(DeriveTraits::value).collect();
+        warn_not_derivable(
+            type_name,
+            derive,
+            &format!("it also requires `{}` to be derived", required.join("` and `")),
+        );
+    }
+
+    derives
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Does the Rust type of a struct field generated for this property implement `derive`?
+fn is_field_derivable(prop: &Property, derive: DeriveTraits, ct_derives: &ComplexTypeDerives) -> bool {
+    match prop.get_property_type() {
+        PropertyType::Edm(edm_type, _) => match derive {
+            // Binary properties become `Vec<u8>` and unknown EDM types become `String`
+            DeriveTraits::COPY => matches!(
+                edm_type.as_str(),
+                "Boolean"
+                    | "Byte"
+                    | "DateTime"
+                    | "DateTimeOffset"
+                    | "Decimal"
+                    | "Double"
+                    | "Guid"
+                    | "Int16"
+                    | "Int32"
+                    | "Int64"
+                    | "Null"
+                    | "SByte"
+                    | "Single"
+                    | "Time"
+            ),
+            // Floating point types only implement partial equality and ordering
+            DeriveTraits::EQ | DeriveTraits::HASH | DeriveTraits::ORD => {
+                !matches!(edm_type.as_str(), "Double" | "Single")
+            },
+            _ => true,
+        },
+        PropertyType::Complex(ct_name) => ct_derives.get(&ct_name).is_some_and(|derives| derives.contains(&derive)),
+        PropertyType::Unqualified => false,
+    }
+}
+
+/// Resolve the traits derived by the struct generated for an entity type or complex type with the given properties.
+/// An extra derive is only accepted if it is implemented by the type of every field
+pub fn resolve_struct_derives(
+    type_name: &str,
+    props: &[Property],
+    rules: &[TypeAttributeRule],
+    ct_derives: &ComplexTypeDerives,
+) -> Vec<DeriveTraits> {
+    resolve_derives(type_name, &DESERIALIZABLE_STRUCT_DERIVES, rules, |derive| {
+        match props.iter().find(|prop| !is_field_derivable(prop, derive, ct_derives)) {
+            Some(prop) => Err(format!("the type of property `{}` does not implement it", prop.odata_name)),
+            None => Ok(()),
+        }
+    })
+}
+
+/// Resolve the traits derived by every complex type struct.  Since a complex type can only derive a trait if the complex
+/// types it uses also derive that trait, those complex types are resolved first
+pub fn resolve_complex_type_derives(cts: &[ComplexType], rules: &[TypeAttributeRule]) -> ComplexTypeDerives {
+    let mut ct_derives = ComplexTypeDerives::new();
+
+    for ct in cts {
+        resolve_complex_type(ct, cts, rules, &mut ct_derives);
+    }
+
+    ct_derives
+}
+
+fn resolve_complex_type(
+    ct: &ComplexType,
+    cts: &[ComplexType],
+    rules: &[TypeAttributeRule],
+    ct_derives: &mut ComplexTypeDerives,
+) {
+    if ct_derives.contains_key(&ct.name) {
+        return;
+    }
+
+    // Guard against complex types that (incorrectly) use each other
+    ct_derives.insert(ct.name.clone(), DESERIALIZABLE_STRUCT_DERIVES.to_vec());
+
+    for prop in &ct.properties {
+        if let PropertyType::Complex(used_name) = prop.get_property_type() {
+            if let Some(used_ct) = cts.iter().find(|used_ct| used_ct.name.eq(&used_name)) {
+                resolve_complex_type(used_ct, cts, rules, ct_derives);
+            }
+        }
+    }
+
+    let derives = resolve_struct_derives(&ct.name, &ct.properties, rules, ct_derives);
+    ct_derives.insert(ct.name.clone(), derives);
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate the derive attribute of a type, followed by the extra outer attributes of every rule matching `type_name`.
+/// Extra attributes that cannot be parsed are skipped with a build warning
+pub fn gen_type_attributes(type_name: &str, derives: &[DeriveTraits], rules: &[TypeAttributeRule]) -> TokenStream {
+    let derive = gen_derive(derives);
+    let extra_attrs = matching_rules(type_name, rules)
+        .flat_map(|rule| rule.attributes.iter())
+        .filter_map(|attr_src| match Attribute::parse_outer.parse_str(attr_src) {
+            Ok(attrs) => Some(attrs),
+            Err(err) => {
+                println!("cargo:warning=Ignoring attribute `{}` for `{}`: {}", attr_src, type_name, err);
+                None
+            },
+        })
+        .flatten();
+
+    quote! {
+        #derive
+        #(#extra_attrs)*
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(test)]
+pub mod unit_tests;
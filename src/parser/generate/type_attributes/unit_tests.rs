@@ -0,0 +1,154 @@
+use std::str::FromStr;
+
+use super::*;
+use crate::{
+    edmx::data_services::schema::{entity_container::EntityContainer, entity_type::EntityType},
+    parser::options::{GeneratorOptions, TypeAttributes},
+    test_utils::*,
+};
+
+static XML_ENTITY_TYPE_MEASUREMENT: &str = r#"<EntityType Name="Measurement">
+    <Key><PropertyRef Name="MeasurementID"/></Key>
+    <Property Name="MeasurementID" Type="Edm.String" Nullable="false" MaxLength="10"/>
+    <Property Name="Reading" Type="Edm.Double" Nullable="false"/>
+    <Property Name="Location" Type="ZMEASURE.CT_Location" Nullable="false"/>
+</EntityType>"#;
+
+static XML_COMPLEX_TYPE_LOCATION: &str = r#"<ComplexType Name="CT_Location">
+    <Property Name="Plant" Type="Edm.String" MaxLength="4"/>
+    <Property Name="Bin" Type="Edm.Int32"/>
+</ComplexType>"#;
+
+fn rule(name_pattern: &str, derives: &[DeriveTraits], attributes: &[&str]) -> TypeAttributeRule {
+    TypeAttributeRule {
+        name_pattern: name_pattern.to_owned(),
+        derives: derives.to_vec(),
+        attributes: attributes.iter().map(|attr| attr.to_string()).collect(),
+    }
+}
+
+fn derive_names(derives: &[DeriveTraits]) -> String {
+    derives.iter().map(DeriveTraits::value).collect::<Vec<&str>>().join(",")
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_match_name_patterns() -> Result<(), String> {
+    handle_test_bool(matches_name_pattern("", "BusinessPartner"))?;
+    handle_test_bool(matches_name_pattern("*", "BusinessPartner"))?;
+    handle_test_bool(matches_name_pattern("BusinessPartner", "BusinessPartner"))?;
+    handle_test_bool(matches_name_pattern("Business*", "BusinessPartner"))?;
+    handle_test_bool(matches_name_pattern("*Partner", "BusinessPartner"))?;
+    handle_test_bool(matches_name_pattern("B*s*r", "BusinessPartner"))?;
+    handle_test_bool(!matches_name_pattern("Business", "BusinessPartner"))?;
+    handle_test_bool(!matches_name_pattern("*Product", "BusinessPartner"))?;
+    handle_test_bool(!matches_name_pattern("Partner*Partner", "Partner"))
+}
+
+#[test]
+pub fn should_refuse_derives_not_implemented_by_fields() -> Result<(), String> {
+    let et = EntityType::from_str(XML_ENTITY_TYPE_MEASUREMENT).map_err(|err| err.to_string())?;
+    let ct = ComplexType::from_str(XML_COMPLEX_TYPE_LOCATION).map_err(|err| err.to_string())?;
+    let cts = [ct];
+
+    // The complex type can derive `Eq` and `Hash`, but not `Copy` because it contains a `String`
+    let ct_derives = resolve_complex_type_derives(
+        &cts,
+        &[rule(
+            "CT_*",
+            &[
+                DeriveTraits::PARTIALEQ,
+                DeriveTraits::EQ,
+                DeriveTraits::HASH,
+                DeriveTraits::COPY,
+            ],
+            &[],
+        )],
+    );
+    handle_test_comparison(
+        &derive_names(&ct_derives["CT_Location"]),
+        &"Clone,Debug,Default,Serialize,Deserialize,PartialEq,Eq,Hash".to_owned(),
+    )?;
+
+    // The entity type cannot derive `Eq` or `Hash` because of its `f64` field, and cannot derive `PartialOrd` because
+    // the complex type does not
+    let et_derives = resolve_struct_derives(
+        &et.name,
+        &et.properties,
+        &[rule(
+            "",
+            &[
+                DeriveTraits::PARTIALEQ,
+                DeriveTraits::EQ,
+                DeriveTraits::HASH,
+                DeriveTraits::PARTIALORD,
+            ],
+            &[],
+        )],
+        &ct_derives,
+    );
+    handle_test_comparison(
+        &derive_names(&et_derives),
+        &"Clone,Debug,Default,Serialize,Deserialize,PartialEq".to_owned(),
+    )
+}
+
+#[test]
+pub fn should_refuse_derives_missing_required_traits() -> Result<(), String> {
+    let rules = [
+        rule("*", &[DeriveTraits::ORD, DeriveTraits::PARTIALORD], &[]),
+        rule("Other", &[DeriveTraits::PARTIALEQ], &[]),
+    ];
+    let derives = resolve_derives("Measurement", &[DeriveTraits::CLONE], &rules, |_| Ok(()));
+
+    // `PartialOrd` requires `PartialEq`, which is only added to types called "Other", and `Ord` also requires `Eq`
+    handle_test_comparison(&derive_names(&derives), &"Clone".to_owned())
+}
+
+#[test]
+pub fn should_generate_extra_attributes() -> Result<(), String> {
+    let rules = [
+        rule("Measurement", &[], &["#[non_exhaustive]", "#[derive(schemars::JsonSchema)]"]),
+        rule("Other", &[], &["#[must_use]"]),
+        rule("*", &[], &["not an attribute"]),
+    ];
+    let tokens = gen_type_attributes("Measurement", &[DeriveTraits::CLONE], &rules);
+
+    handle_test_comparison(
+        &tokens.to_string(),
+        &quote!(#[derive(Clone)] #[non_exhaustive] #[derive(schemars::JsonSchema)]).to_string(),
+    )
+}
+
+#[test]
+pub fn should_add_derives_to_entity_container_enum() -> Result<(), String> {
+    let ent_cont = EntityContainer::from_str(
+        r#"<EntityContainer Name="ZMEASURE_Entities"><EntitySet Name="MeasurementSet" EntityType="ZMEASURE.Measurement"/></EntityContainer>"#,
+    )
+    .map_err(|err| err.to_string())?;
+    let options = GeneratorOptions {
+        type_attributes: TypeAttributes {
+            entity_container_enum: vec![rule(
+                "",
+                &[
+                    DeriveTraits::PARTIALEQ,
+                    DeriveTraits::EQ,
+                    DeriveTraits::HASH,
+                    DeriveTraits::DEFAULT,
+                ],
+                &["#[non_exhaustive]"],
+            )],
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let enum_src = ent_cont.to_enum_with_options(&options).to_string();
+    let expected = quote! {
+        #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+        #[non_exhaustive]
+        #[allow(dead_code)]
+        pub enum ZmeasureEntities
+    };
+
+    handle_test_bool(enum_src.starts_with(&expected.to_string()))
+}
@@ -0,0 +1,155 @@
+use crate::{
+    edmx::data_services::schema::Schema,
+    property::{metadata::PropertyType, Property},
+    utils::to_upper_camel_case,
+};
+
+static INDENT: &str = "  ";
+static SUFFIX_LABELS: &str = "Labels";
+static ENTITY_SET_NAME_TYPE: &str = "EntitySetName";
+
+pub static SUFFIX_TYPESCRIPT: &str = ".ts";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// Quote a string as a TypeScript string literal
+fn string_literal(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// The TypeScript type of a property's value in the OData V2 JSON format
+///
+/// `Edm.Int64` and `Edm.Decimal` values are transferred as strings to avoid losing precision, and date, time and
+/// binary values are also transferred as strings
+pub fn ts_type(prop: &Property) -> String {
+    let ts_type = match prop.get_property_type() {
+        PropertyType::Edm(edm_type, _) => match edm_type.as_str() {
+            "Boolean" => "boolean",
+            "Byte" | "Double" | "Int16" | "Int32" | "SByte" | "Single" => "number",
+            "Null" => "null",
+            _ => "string",
+        }
+        .to_owned(),
+        PropertyType::Complex(ct_name) => to_upper_camel_case(&ct_name),
+        PropertyType::Unqualified => "unknown".to_owned(),
+    };
+
+    if prop.nullable && !matches!(prop.get_property_type(), PropertyType::Complex(_)) {
+        format!("{ts_type} | null")
+    } else {
+        ts_type
+    }
+}
+
+// A JSDoc comment built from the property's label and quick info
+fn gen_doc_comment(prop: &Property) -> String {
+    let sap_annos = &prop.sap_annotations;
+    let lines: Vec<&String> = sap_annos.label.iter().chain(sap_annos.quick_info.iter()).collect();
+
+    match lines.as_slice() {
+        [] => String::new(),
+        [line] => format!("{INDENT}/** {} */\n", line.replace("*/", "* /")),
+        _ => format!(
+            "{INDENT}/**\n{}{INDENT} */\n",
+            lines
+                .iter()
+                .map(|line| format!("{INDENT} * {}\n", line.replace("*/", "* /")))
+                .collect::<String>()
+        ),
+    }
+}
+
+fn gen_interface(type_name: &str, props: &[Property]) -> String {
+    let fields: String = props
+        .iter()
+        .map(|prop| format!("{}{INDENT}{}: {};\n", gen_doc_comment(prop), prop.odata_name, ts_type(prop)))
+        .collect();
+
+    format!("export interface {type_name} {{\n{fields}}}\n")
+}
+
+// An object mapping each property name to its SAP label, or nothing if no property has a label
+fn gen_labels(type_name: &str, props: &[Property]) -> String {
+    let labels: String = props
+        .iter()
+        .filter_map(|prop| {
+            let label = prop.sap_annotations.label.as_ref()?;
+            Some(format!("{INDENT}{}: {},\n", prop.odata_name, string_literal(label)))
+        })
+        .collect();
+
+    if labels.is_empty() {
+        String::new()
+    } else {
+        format!("\nexport const {type_name}{SUFFIX_LABELS} = {{\n{labels}}} as const;\n")
+    }
+}
+
+fn gen_entity_sets(schema: &Schema) -> String {
+    let Some(ent_cont) = &schema.entity_container else {
+        return String::new();
+    };
+
+    if ent_cont.entity_sets.is_empty() {
+        return String::new();
+    }
+
+    let names: Vec<String> = ent_cont.entity_sets.iter().map(|es| string_literal(&es.name)).collect();
+    let entity_set_types: String = ent_cont
+        .entity_sets
+        .iter()
+        .map(|es| {
+            let et_name = es
+                .entity_type
+                .rsplit_once('.')
+                .map_or(es.entity_type.as_str(), |(_, name)| name);
+            format!("{INDENT}{}: {};\n", es.name, to_upper_camel_case(et_name))
+        })
+        .collect();
+
+    format!(
+        "
+export const {ENTITY_SET_NAME_TYPE}s = [{}] as const;
+
+export type {ENTITY_SET_NAME_TYPE} = (typeof {ENTITY_SET_NAME_TYPE}s)[number];
+
+/** The entity type of each entity set */
+export interface EntitySetTypes {{
+{entity_set_types}}}
+",
+        names.join(", ")
+    )
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate a TypeScript module from the same schema used to generate the service document:
+/// * An interface for each complex type and entity type, describing the type in the OData V2 JSON format
+/// * A constant `<TypeName>Labels` object for each complex type and entity type, mapping property names to SAP labels
+/// * A constant array `EntitySetNames` and a union type `EntitySetName` listing the entity set names
+/// * An interface `EntitySetTypes` mapping each entity set name to its entity type
+pub fn gen_typescript_module(odata_srv_name: &str, schema: &Schema) -> Vec<u8> {
+    let mut out = format!(
+        "// Generated from the metadata of OData service {odata_srv_name} (namespace {})\n",
+        schema.namespace
+    );
+
+    for ct in schema.complex_types.iter().flatten() {
+        let type_name = to_upper_camel_case(&ct.name);
+        out.push('\n');
+        out.push_str(&gen_interface(&type_name, &ct.properties));
+        out.push_str(&gen_labels(&type_name, &ct.properties));
+    }
+
+    for et in &schema.entity_types {
+        let type_name = to_upper_camel_case(&et.name);
+        out.push('\n');
+        out.push_str(&gen_interface(&type_name, &et.properties));
+        out.push_str(&gen_labels(&type_name, &et.properties));
+    }
+
+    out.push_str(&gen_entity_sets(schema));
+    out.into_bytes()
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(test)]
+pub mod unit_tests;
@@ -0,0 +1,52 @@
+use super::*;
+use crate::test_utils::*;
+
+fn fetch_typescript() -> Result<String, String> {
+    let edmx = fetch_mock_service_edmx()?;
+    let schema = edmx
+        .data_services
+        .fetch_schema("GWSAMPLE_BASIC")
+        .ok_or("Schema GWSAMPLE_BASIC not found")?;
+
+    String::from_utf8(gen_typescript_module("gwsample_basic", schema)).map_err(|err| err.to_string())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_generate_typescript_interfaces() -> Result<(), String> {
+    let ts = fetch_typescript()?;
+
+    handle_test_bool(ts.contains(
+        "export interface BusinessPartner {
+  Address: CtAddress;
+  /** Bus. Part. ID */
+  BusinessPartnerID: string;
+  /** Company Name */
+  CompanyName: string;
+  /** E-Mail */
+  EmailAddress: string | null;
+}
+",
+    ))?;
+    handle_test_bool(ts.contains("export interface CtAddress {"))?;
+
+    // Decimals are transferred as strings
+    handle_test_bool(ts.contains("  /** Unit Price */\n  Price: string | null;\n"))
+}
+
+#[test]
+pub fn should_generate_typescript_entity_sets_and_labels() -> Result<(), String> {
+    let ts = fetch_typescript()?;
+
+    handle_test_bool(ts.contains("export const EntitySetNames = [\"BusinessPartnerSet\", \"ProductSet\"] as const;"))?;
+    handle_test_bool(ts.contains("export type EntitySetName = (typeof EntitySetNames)[number];"))?;
+    handle_test_bool(ts.contains("  ProductSet: Product;\n"))?;
+    handle_test_bool(ts.contains(
+        "export const CtAddressLabels = {
+  City: \"City\",
+  PostalCode: \"Postal Code\",
+  Country: \"Country\",
+} as const;
+",
+    ))
+}
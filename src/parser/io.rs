@@ -1,30 +1,57 @@
 use anyhow::Result;
-use std::{env, fs::OpenOptions, io::Write, path::Path, str::FromStr};
+use std::{
+    env,
+    fs::{self, File, OpenOptions},
+    io::{self, BufRead, BufReader, Write},
+    path::Path,
+};
 
-use crate::edmx::Edmx;
+use crate::{edmx::Edmx, parser::options::GeneratorOptions, sanitise::sanitise_xml};
 
 pub static DEFAULT_INPUT_DIR: &str = "./odata";
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-fn fetch_xml_as_string(filename: &str) -> Result<String> {
+fn open_xml_file(filename: &str) -> Result<BufReader<File>> {
     let xml_input_pathname = format!("{}/{}.xml", DEFAULT_INPUT_DIR, filename);
-    Ok(std::fs::read_to_string(&xml_input_pathname)?)
+    Ok(BufReader::new(File::open(&xml_input_pathname)?))
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// Write buffer to $OUT_DIR
 pub fn write_buffer_to_file(filename: &str, buf: &[u8]) -> Result<()> {
     let out_dir = env::var("OUT_DIR")?;
-    let mut output_file = OpenOptions::new()
-        .create(true)
-        .write(true)
-        .truncate(true)
-        .open(Path::new(&out_dir).join(filename))?;
+    write_buffer_if_changed(&Path::new(&out_dir).join(filename), buf)?;
 
-    output_file.write_all(buf)?;
     Ok(())
 }
 
+/// Write buffer to the given path, unless the file already has exactly this content.
+///
+/// Leaving an unchanged file untouched preserves its modification time, so code that includes it is not recompiled.
+/// Returns whether the file was written
+pub fn write_buffer_if_changed(path: &Path, buf: &[u8]) -> Result<bool> {
+    if has_content(path, buf) {
+        return Ok(false);
+    }
+
+    if let Some(parent_dir) = path.parent() {
+        fs::create_dir_all(parent_dir)?;
+    }
+
+    let mut output_file = OpenOptions::new().create(true).write(true).truncate(true).open(path)?;
+
+    output_file.write_all(buf)?;
+    Ok(true)
+}
+
+// Only read the existing file if its size shows that it could have the same content
+fn has_content(path: &Path, buf: &[u8]) -> bool {
+    match fs::metadata(path) {
+        Ok(file_meta) if file_meta.len() == buf.len() as u64 => fs::read(path).is_ok_and(|existing| existing == buf),
+        _ => false,
+    }
+}
+
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// Deserialize an SAP OData metadata document
 ///
@@ -33,9 +60,33 @@ pub fn write_buffer_to_file(filename: &str, buf: &[u8]) -> Result<()> {
 ///
 /// `odata/`<br>
 /// `└── gwsample_basic.xml`
-pub fn deserialize_sap_metadata(metadata_file_name: &str) -> Result<Edmx> {
-    let xml = fetch_xml_as_string(metadata_file_name)?;
-    let edmx = Edmx::from_str(&xml)?;
+///
+/// The file is deserialized as it is read, unless it must first be sanitised.  If the `skip_annotations` option is set,
+/// all `<Annotations>` elements are skipped
+pub fn deserialize_sap_metadata(metadata_file_name: &str, options: &GeneratorOptions) -> Result<Edmx> {
+    let reader = open_xml_file(metadata_file_name)?;
+
+    let edmx = match &options.sanitise_xml {
+        Some(sanitise_options) => {
+            let xml = io::read_to_string(reader)?;
+            let sanitised = sanitise_xml(&xml, sanitise_options);
+
+            for change in &sanitised.changes {
+                println!("cargo:warning=Sanitised metadata document {}: {}", metadata_file_name, change);
+            }
+
+            read_edmx(sanitised.xml.as_bytes(), options.skip_annotations)?
+        },
+        None => read_edmx(reader, options.skip_annotations)?,
+    };
 
     Ok(edmx)
 }
+
+fn read_edmx<R: BufRead>(reader: R, skip_annotations: bool) -> Result<Edmx, quick_xml::DeError> {
+    if skip_annotations {
+        Edmx::from_reader_skipping_annotations(reader)
+    } else {
+        Edmx::from_reader(reader)
+    }
+}
@@ -1,53 +1,184 @@
 pub mod generate;
+pub mod options;
+pub mod schema_filter;
 
 mod io;
 
-use crate::utils::rust_tools::run_rustfmt;
-use generate::{metadata_doc::*, srvc_doc::*, syntax_fragments::SUFFIX_SNAKE_METADATA};
+use crate::{edmx::data_services::schema::Schema, utils::rust_tools::format_tokens};
+use generate::{
+    csdl_v4::{gen_csdl_v4_json, gen_csdl_v4_xml, SUFFIX_CSDL_V4_JSON, SUFFIX_CSDL_V4_XML},
+    er_diagram::{gen_graphviz_er_diagram, gen_mermaid_er_diagram, SUFFIX_GRAPHVIZ, SUFFIX_MERMAID},
+    json_schema::{gen_json_schema, SUFFIX_JSON_SCHEMA},
+    metadata_doc::*,
+    module_tree::gen_module_tree,
+    openapi::{gen_openapi, SUFFIX_OPENAPI},
+    sql_ddl::{gen_sql_ddl, SUFFIX_SQL},
+    srvc_doc::*,
+    syntax_fragments::SUFFIX_SNAKE_METADATA,
+    typescript::{gen_typescript_module, SUFFIX_TYPESCRIPT},
+};
 use io::*;
+use options::{Formatter, GeneratorOptions};
+use schema_filter::{filter_schema, unknown_names};
+use proc_macro2::TokenStream;
+use std::{env, path::Path};
+
+static RUSTFMT_CONFIG_FILES: [&str; 2] = ["rustfmt.toml", ".rustfmt.toml"];
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 pub trait AsRustSrc {
     type CrateRef;
-    fn to_rust(&self) -> (Vec<u8>, Self::CrateRef);
+    fn to_rust(&self) -> (TokenStream, Self::CrateRef);
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-/// Format `buf` with rustfmt and write the result to `mod_name` in `$OUT_DIR`
-fn emit_module(mod_name: &str, buf: &[u8]) {
-    match run_rustfmt(buf, mod_name) {
+/// Format `tokens` using the given formatter and write the result to `mod_name` in `$OUT_DIR`
+fn emit_module(mod_name: &str, tokens: TokenStream, formatter: &Formatter) {
+    match format_tokens(tokens, mod_name, formatter) {
         Ok(formatted_bytes) => {
             if let Err(err) = write_buffer_to_file(mod_name, &formatted_bytes) {
                 println!("Error: writing module '{}' failed: {}", mod_name, err);
             }
         },
-        Err(err) => println!("Error: rustfmt for module '{}' ended with {}", mod_name, err),
+        Err(err) => println!("Error: formatting module '{}' ended with {}", mod_name, err),
+    }
+}
+
+/// Write each file of the module tree to its location below `$OUT_DIR`
+fn emit_module_tree(odata_srv_name: &str, schema: &Schema, options: &GeneratorOptions) {
+    match env::var("OUT_DIR") {
+        Ok(out_dir) => {
+            for module_file in gen_module_tree(odata_srv_name, schema, options, Path::new(&out_dir)).files {
+                emit_module(&module_file.path.display().to_string(), module_file.tokens, &options.formatter);
+            }
+        },
+        Err(err) => println!("Error: the module tree for '{}' cannot be written: {}", odata_srv_name, err),
+    }
+}
+
+/// Tell cargo to rerun the build script only when one of the inputs to the generated code changes
+fn emit_rerun_directives(odata_srv_name: &str, options: &GeneratorOptions) {
+    println!("cargo:rerun-if-changed={}/{}.xml", DEFAULT_INPUT_DIR, odata_srv_name);
+
+    if let Formatter::Rustfmt(rustfmt_opts) = &options.formatter {
+        match &rustfmt_opts.path {
+            Some(rustfmt_path) => println!("cargo:rerun-if-changed={}", rustfmt_path.display()),
+            None => println!("cargo:rerun-if-env-changed=PATH"),
+        }
+
+        match &rustfmt_opts.config_path {
+            Some(config_path) => println!("cargo:rerun-if-changed={}", config_path.display()),
+            // Without an explicit configuration file, rustfmt uses the one found in the current directory (if any)
+            None => {
+                for config_file in RUSTFMT_CONFIG_FILES.iter().filter(|f| Path::new(f).is_file()) {
+                    println!("cargo:rerun-if-changed={}", config_file);
+                }
+            },
+        }
+    }
+}
+
+/// Write a document other than a Rust module to `file_name` in `$OUT_DIR`
+fn emit_document(file_name: &str, buf: &[u8]) {
+    if let Err(err) = write_buffer_to_file(file_name, buf) {
+        println!("Error: writing document '{}' failed: {}", file_name, err);
     }
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// Generate the service document and metadata modules
 pub fn gen_src(odata_srv_name: &str, namespace: &str) {
-    match deserialize_sap_metadata(odata_srv_name) {
+    gen_src_with_options(odata_srv_name, namespace, &GeneratorOptions::default())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Generate the service document and metadata modules using the supplied generator options
+pub fn gen_src_with_options(odata_srv_name: &str, namespace: &str, options: &GeneratorOptions) {
+    match deserialize_sap_metadata(odata_srv_name, options) {
         // Deserialization can fail sometimes!
         // This can happen for example, when a quoted XML attribute value contains an unescaped double quote character
         //
         // The Atom `<feed>` document returned from the entity sets of certain SAP OData services has been known to
         // contain `<entry>` elements whose `m:etag` attribute contains such an incorrectly quoted value
         Err(err) => println!("Error: {}", err),
-        Ok(edmx) => {
-            // Write cargo build script directive as soon as the input path is known to be valid
-            println!(
-                "cargo:rerun-if-changed={}",
-                format!("{}/{}.xml", DEFAULT_INPUT_DIR, odata_srv_name)
-            );
-
-            if let Some(schema) = edmx.data_services.fetch_schema(namespace) {
-                emit_module(&format!("{}.rs", odata_srv_name), &gen_srv_doc_module(odata_srv_name, &schema));
-                emit_module(
-                    &format!("{odata_srv_name}{SUFFIX_SNAKE_METADATA}.rs"),
-                    &gen_metadata_module(odata_srv_name, &schema),
-                );
+        Ok(mut edmx) => {
+            // Write cargo build script directives as soon as the input path is known to be valid
+            emit_rerun_directives(odata_srv_name, options);
+
+            if let Some(schema) = edmx.data_services.fetch_schema_mut(namespace) {
+                for name in unknown_names(schema, &options.schema_filter) {
+                    println!(
+                        "cargo:warning=Entity set or function import '{}' named in the schema filter does not exist",
+                        name
+                    );
+                }
+
+                filter_schema(schema, &options.schema_filter);
+
+                if options.split_modules {
+                    emit_module_tree(odata_srv_name, schema, options);
+                } else {
+                    emit_module(
+                        &format!("{}.rs", odata_srv_name),
+                        gen_srv_doc_module_with_options(odata_srv_name, schema, options),
+                        &options.formatter,
+                    );
+                    emit_module(
+                        &format!("{odata_srv_name}{SUFFIX_SNAKE_METADATA}.rs"),
+                        gen_metadata_module(odata_srv_name, schema),
+                        &options.formatter,
+                    );
+                }
+
+                if options.gen_json_schema {
+                    emit_document(
+                        &format!("{odata_srv_name}{SUFFIX_JSON_SCHEMA}"),
+                        &gen_json_schema(odata_srv_name, schema),
+                    );
+                }
+
+                if options.gen_openapi {
+                    emit_document(
+                        &format!("{odata_srv_name}{SUFFIX_OPENAPI}"),
+                        &gen_openapi(odata_srv_name, schema),
+                    );
+                }
+
+                if options.gen_csdl_v4 {
+                    emit_document(
+                        &format!("{odata_srv_name}{SUFFIX_CSDL_V4_XML}"),
+                        &gen_csdl_v4_xml(schema),
+                    );
+                    emit_document(
+                        &format!("{odata_srv_name}{SUFFIX_CSDL_V4_JSON}"),
+                        &gen_csdl_v4_json(schema),
+                    );
+                }
+
+                if options.gen_er_diagrams {
+                    emit_document(
+                        &format!("{odata_srv_name}{SUFFIX_MERMAID}"),
+                        &gen_mermaid_er_diagram(schema, &options.er_diagram_entity_sets),
+                    );
+                    emit_document(
+                        &format!("{odata_srv_name}{SUFFIX_GRAPHVIZ}"),
+                        &gen_graphviz_er_diagram(schema, &options.er_diagram_entity_sets),
+                    );
+                }
+
+                if options.gen_sql_ddl {
+                    emit_document(
+                        &format!("{odata_srv_name}{SUFFIX_SQL}"),
+                        &gen_sql_ddl(schema, options.sql_dialect),
+                    );
+                }
+
+                if options.gen_typescript {
+                    emit_document(
+                        &format!("{odata_srv_name}{SUFFIX_TYPESCRIPT}"),
+                        &gen_typescript_module(odata_srv_name, schema),
+                    );
+                }
             } else {
                 println!(
                     "Error: OData schema for namespace '{}' cannot be found or this is not OData V2 XML",
@@ -57,3 +188,7 @@ pub fn gen_src(odata_srv_name: &str, namespace: &str) {
         },
     }
 }
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(test)]
+pub mod unit_tests;
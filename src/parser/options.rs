@@ -0,0 +1,192 @@
+use std::path::PathBuf;
+
+use crate::{
+    parser::generate::syntax_fragments::derive_traits::DeriveTraits, sanitise::SanitiseOptions, sql::SqlDialect,
+};
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Options controlling which optional parts of the service document and metadata modules are generated
+///
+/// All options default to the behaviour of [`gen_src`](crate::parser::gen_src), so only the options that differ need to
+/// be specified:
+///
+/// ```rust,ignore
+/// use parse_sap_odata::parser::{gen_src_with_options, options::GeneratorOptions};
+///
+/// gen_src_with_options(
+///     "gwsample_basic",
+///     "GWSAMPLE_BASIC",
+///     &GeneratorOptions {
+///         gen_unit_accessors: true,
+///         ..Default::default()
+///     },
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct GeneratorOptions {
+    /// For each amount or quantity field annotated with `sap:unit`, generate accessor methods that pair the value with
+    /// its currency code or unit of measure
+    pub gen_unit_accessors: bool,
+
+    /// For each field annotated with `sap:text` (or referenced by another field's `sap:text-for` annotation), generate a
+    /// display helper that returns the field's value together with its descriptive text
+    pub gen_text_accessors: bool,
+
+    /// For each entity type and complex type, generate an implementation of `proptest::arbitrary::Arbitrary` whose
+    /// values respect the property metadata, plus a property-based test per entity type that checks values survive a
+    /// round trip through an Atom `<m:properties>` block.
+    ///
+    /// The generated code is only compiled when the crate that includes the service document has a `proptest` feature
+    /// that is enabled, and that crate then needs a dependency on `proptest`.
+    ///
+    /// So that `None` values survive the round trip, nullable Boolean, Decimal, integer and String fields are also
+    /// given a deserializer that maps elements marked as `m:null="true"` to `None`
+    pub gen_arbitrary_impls: bool,
+
+    /// For each entity type and complex type, also generate a borrowed struct `<Name>Borrowed<'a>` whose string fields are
+    /// `Cow<'a, str>` values that borrow from the XML being deserialized wherever possible.  Use these together with
+    /// `parse_sap_odata::feed_entries::deserialize_feed_entries` to reduce the memory needed by large feeds
+    pub gen_borrowed_structs: bool,
+
+    /// In addition to the Rust modules, write a JSON Schema document describing each entity type and complex type
+    pub gen_json_schema: bool,
+
+    /// In addition to the Rust modules, write an OpenAPI 3 document describing the entity sets and function imports
+    pub gen_openapi: bool,
+
+    /// In addition to the Rust modules, write the service's metadata converted to OData V4 CSDL, both as an XML document
+    /// and as a JSON document.  SAP V2 annotations are expressed using the equivalent V4 vocabulary terms
+    pub gen_csdl_v4: bool,
+
+    /// In addition to the Rust modules, write entity-relationship diagrams of the entity types and their associations,
+    /// both in Mermaid and in Graphviz DOT format
+    pub gen_er_diagrams: bool,
+
+    /// The names of the entity sets to be shown in the entity-relationship diagrams.  If empty, all entity types are shown
+    pub er_diagram_entity_sets: Vec<String>,
+
+    /// In addition to the Rust modules, write an SQL script that creates a table for each entity type, and implement
+    /// `parse_sap_odata::sql::SqlRow` for each entity type struct so that its values can be inserted into that table
+    pub gen_sql_ddl: bool,
+
+    /// The SQL dialect used by the script written when `gen_sql_ddl` is switched on
+    pub sql_dialect: SqlDialect,
+
+    /// In addition to the Rust modules, write a TypeScript module containing interfaces for the entity types and complex
+    /// types, the entity set names and the SAP labels of each property
+    pub gen_typescript: bool,
+
+    /// How the generated Rust modules are formatted before being written to `$OUT_DIR`
+    pub formatter: Formatter,
+
+    /// Instead of writing the service document and metadata modules to one file each, split them into a tree of module
+    /// files below the directory `$OUT_DIR/<metadata_file_name>/`, with each complex type and entity type in a file of
+    /// its own.  This speeds up incremental compilation for services with many entity types.
+    ///
+    /// Both modules are then brought into scope using `include_mod_tree!()` instead of `include_mod!()`
+    pub split_modules: bool,
+
+    /// Restrict the generated code to a subset of the service's entity sets and function imports.  The entity types,
+    /// complex types and associations needed by that subset are kept, and everything else is discarded before any code
+    /// or document is generated
+    pub schema_filter: SchemaFilter,
+
+    /// Skip the `<Annotations>` elements of the metadata document while it is being read.  None of the generated code
+    /// uses these elements, so for metadata documents with large annotation sections, this reduces the time and memory
+    /// needed by the build script
+    pub skip_annotations: bool,
+
+    /// Repair the known quirks of invalid XML returned by SAP OData services.  When set, the metadata document is
+    /// sanitised before it is deserialized, with a build warning for each repair, and the generated `FromStr`
+    /// implementations sanitise their input using the same options.
+    ///
+    /// To find out what was repaired in a particular response, call `parse_sap_odata::sanitise::from_str_sanitised`
+    pub sanitise_xml: Option<SanitiseOptions>,
+
+    /// Additional derives and outer attributes for the entity type structs, complex type structs and the entity
+    /// container enum generated in the service document module
+    pub type_attributes: TypeAttributes,
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// The formatters that can be applied to the generated Rust modules
+///
+/// Whichever formatter is used, the generated source code is first checked for syntax errors.  If any are found, the
+/// source code is written to `$OUT_DIR` with `failed_` prefixed to the file name
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub enum Formatter {
+    /// Write the generated source code without any formatting
+    Unformatted,
+
+    /// Pretty-print the generated source code in-process using `prettyplease`.  No external tools are needed
+    #[default]
+    Prettyplease,
+
+    /// Pretty-print the generated source code using `prettyplease`, then pass it through `rustfmt`.
+    ///
+    /// If the `rustfmt` binary cannot be found, a build warning is issued and the output of `prettyplease` is used instead
+    Rustfmt(RustfmtOptions),
+}
+
+/// Options passed to `rustfmt`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RustfmtOptions {
+    /// The path to the `rustfmt` binary.  If not specified, `rustfmt` is searched for in the `PATH`
+    pub path: Option<PathBuf>,
+
+    /// The Rust edition passed to `rustfmt --edition`, E.G. `"2021"`
+    pub edition: Option<String>,
+
+    /// The path to a `rustfmt.toml` file passed to `rustfmt --config-path`
+    pub config_path: Option<PathBuf>,
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Allow and deny lists naming the entity sets and function imports for which code is generated
+///
+/// An empty allow list allows every name, and a name appearing in both lists is denied.  A function import bound to an
+/// entity set (via its `EntitySet` attribute) is only kept if that entity set is also kept
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SchemaFilter {
+    pub allow_entity_sets: Vec<String>,
+    pub deny_entity_sets: Vec<String>,
+    pub allow_function_imports: Vec<String>,
+    pub deny_function_imports: Vec<String>,
+}
+
+impl SchemaFilter {
+    /// Does this filter keep the entire schema?
+    pub fn is_empty(&self) -> bool {
+        self.allow_entity_sets.is_empty()
+            && self.deny_entity_sets.is_empty()
+            && self.allow_function_imports.is_empty()
+            && self.deny_function_imports.is_empty()
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// The rules adding derives and outer attributes to each kind of generated type.  Every rule whose name pattern
+/// matches a type is applied to that type
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypeAttributes {
+    pub entity_types: Vec<TypeAttributeRule>,
+    pub complex_types: Vec<TypeAttributeRule>,
+    pub entity_container_enum: Vec<TypeAttributeRule>,
+}
+
+/// Derives and outer attributes added to the generated types whose EDM name matches `name_pattern`
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TypeAttributeRule {
+    /// The EDM name of the types to which this rule applies, in which `*` matches any sequence of characters.  An empty
+    /// pattern matches every type
+    pub name_pattern: String,
+
+    /// Traits derived in addition to those the generator always derives.  A trait that cannot be derived for a type
+    /// (E.G. `Eq` for a struct with an `f64` field, or `Ord` without `Eq` and `PartialOrd`) is skipped with a build
+    /// warning
+    pub derives: Vec<DeriveTraits>,
+
+    /// Outer attributes added to the type declaration, E.G. `"#[non_exhaustive]"` or
+    /// `"#[derive(schemars::JsonSchema)]"`.  These are not checked against the type's fields
+    pub attributes: Vec<String>,
+}
@@ -0,0 +1,156 @@
+use std::collections::HashSet;
+
+use crate::{edmx::data_services::schema::Schema, parser::options::SchemaFilter, property::Property};
+
+static COLLECTION_PREFIX: &str = "Collection(";
+static EDM_PREFIX: &str = "Edm.";
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn unqualified_name(qualified_name: &str) -> &str {
+    qualified_name.rsplit_once('.').map_or(qualified_name, |(_, name)| name)
+}
+
+// The unqualified name of the type returned by a function import, without any `Collection()` wrapper
+fn return_type_name(return_type: &str) -> &str {
+    let type_name = return_type
+        .strip_prefix(COLLECTION_PREFIX)
+        .and_then(|inner| inner.strip_suffix(')'))
+        .unwrap_or(return_type);
+
+    unqualified_name(type_name)
+}
+
+// The unqualified names of the non-EDM types used by a list of properties
+fn property_type_names(props: &[Property]) -> impl Iterator<Item = String> + '_ {
+    props
+        .iter()
+        .filter(|prop| !prop.edm_type.starts_with(EDM_PREFIX))
+        .map(|prop| unqualified_name(&prop.edm_type).to_owned())
+}
+
+fn is_allowed(name: &str, allow: &[String], deny: &[String]) -> bool {
+    (allow.is_empty() || allow.iter().any(|allowed| allowed.eq(name))) && !deny.iter().any(|denied| denied.eq(name))
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// The names in the filter's lists that match neither an entity set nor a function import in the schema
+pub fn unknown_names<'a>(schema: &Schema, filter: &'a SchemaFilter) -> Vec<&'a str> {
+    let (ent_set_names, fn_import_names): (Vec<&str>, Vec<&str>) = match &schema.entity_container {
+        Some(container) => (
+            container.entity_sets.iter().map(|es| es.name.as_str()).collect(),
+            container.function_imports.iter().flatten().map(|fi| fi.name.as_str()).collect(),
+        ),
+        None => (vec![], vec![]),
+    };
+
+    let unknown_ent_sets = filter
+        .allow_entity_sets
+        .iter()
+        .chain(filter.deny_entity_sets.iter())
+        .filter(|name| !ent_set_names.contains(&name.as_str()));
+    let unknown_fn_imports = filter
+        .allow_function_imports
+        .iter()
+        .chain(filter.deny_function_imports.iter())
+        .filter(|name| !fn_import_names.contains(&name.as_str()));
+
+    unknown_ent_sets.chain(unknown_fn_imports).map(String::as_str).collect()
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Remove from the schema every entity set and function import rejected by the filter, then remove every type no
+/// longer needed by what remains:
+///
+/// * Entity types are kept if they are used by a remaining entity set or returned by a remaining function import
+/// * Complex types are kept if they are used (directly or via another complex type) by a remaining entity type, or
+///   returned by a remaining function import
+/// * Associations are kept if both their ends are remaining entity types, and association sets are kept if their
+///   association and both their entity sets remain.  Navigation properties whose association has been removed are
+///   also removed
+pub fn filter_schema(schema: &mut Schema, filter: &SchemaFilter) {
+    if filter.is_empty() {
+        return;
+    }
+
+    let Some(container) = schema.entity_container.as_mut() else {
+        return;
+    };
+
+    container
+        .entity_sets
+        .retain(|es| is_allowed(&es.name, &filter.allow_entity_sets, &filter.deny_entity_sets));
+    let kept_ent_sets: HashSet<String> = container.entity_sets.iter().map(|es| es.name.clone()).collect();
+
+    if let Some(fn_imports) = container.function_imports.as_mut() {
+        fn_imports.retain(|fi| {
+            is_allowed(&fi.name, &filter.allow_function_imports, &filter.deny_function_imports)
+                && fi.entity_set.as_ref().is_none_or(|es_name| kept_ent_sets.contains(es_name))
+        });
+    }
+
+    let fn_return_types: Vec<String> = container
+        .function_imports
+        .iter()
+        .flatten()
+        .map(|fi| return_type_name(&fi.return_type).to_owned())
+        .collect();
+
+    // Entity types
+    let mut kept_ets: HashSet<String> = container
+        .entity_sets
+        .iter()
+        .map(|es| unqualified_name(&es.entity_type).to_owned())
+        .collect();
+    kept_ets.extend(fn_return_types.iter().cloned());
+    schema.entity_types.retain(|et| kept_ets.contains(&et.name));
+
+    // Complex types, including those only used by other complex types
+    if let Some(cts) = schema.complex_types.as_mut() {
+        let mut kept_cts: HashSet<String> = HashSet::new();
+        let mut pending: Vec<String> = schema
+            .entity_types
+            .iter()
+            .flat_map(|et| property_type_names(&et.properties))
+            .chain(fn_return_types)
+            .collect();
+
+        while let Some(type_name) = pending.pop() {
+            if let Some(ct) = cts.iter().find(|ct| ct.name.eq(&type_name)) {
+                if kept_cts.insert(type_name) {
+                    pending.extend(property_type_names(&ct.properties));
+                }
+            }
+        }
+
+        cts.retain(|ct| kept_cts.contains(&ct.name));
+    }
+
+    // Associations and the navigation properties that use them
+    schema.associations.retain(|assoc| {
+        assoc.ends.iter().all(|end| {
+            end.end_type
+                .as_ref()
+                .is_some_and(|end_type| kept_ets.contains(unqualified_name(end_type)))
+        })
+    });
+    let kept_assocs: HashSet<String> = schema.associations.iter().map(|assoc| assoc.name.clone()).collect();
+
+    for et in schema.entity_types.iter_mut() {
+        et.navigations
+            .retain(|nav| kept_assocs.contains(unqualified_name(&nav.relationship)));
+    }
+
+    if let Some(container) = schema.entity_container.as_mut() {
+        container.association_sets.retain(|assoc_set| {
+            kept_assocs.contains(unqualified_name(&assoc_set.association))
+                && assoc_set
+                    .ends
+                    .iter()
+                    .all(|end| end.entity_set.as_ref().is_some_and(|es_name| kept_ent_sets.contains(es_name)))
+        });
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(test)]
+pub mod unit_tests;
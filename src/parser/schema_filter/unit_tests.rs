@@ -0,0 +1,87 @@
+use super::*;
+use crate::test_utils::*;
+
+fn fetch_filtered_schema(filter: &SchemaFilter) -> Result<Schema, String> {
+    let mut edmx = fetch_mock_service_edmx()?;
+    let mut schema = edmx.data_services.schemas.remove(0);
+    filter_schema(&mut schema, filter);
+
+    Ok(schema)
+}
+
+fn names<T>(items: &[T], name_of: fn(&T) -> &str) -> String {
+    items.iter().map(name_of).collect::<Vec<&str>>().join(",")
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_keep_allowed_entity_set_and_its_types() -> Result<(), String> {
+    let schema = fetch_filtered_schema(&SchemaFilter {
+        allow_entity_sets: vec!["BusinessPartnerSet".to_owned()],
+        ..Default::default()
+    })?;
+    let container = schema.entity_container.as_ref().ok_or("Entity container not found")?;
+
+    handle_test_comparison(&names(&container.entity_sets, |es| &es.name), &"BusinessPartnerSet".to_owned())?;
+    handle_test_comparison(&names(&schema.entity_types, |et| &et.name), &"BusinessPartner".to_owned())?;
+    handle_test_comparison(
+        &names(schema.complex_types.as_deref().unwrap_or_default(), |ct| &ct.name),
+        &"CT_Address".to_owned(),
+    )?;
+
+    // Only the unbound function import remains
+    handle_test_comparison(
+        &names(container.function_imports.as_deref().unwrap_or_default(), |fi| &fi.name),
+        &"RegenerateAllData".to_owned(),
+    )?;
+
+    // The association to Product is no longer needed
+    handle_test_bool(schema.associations.is_empty())?;
+    handle_test_bool(container.association_sets.is_empty())?;
+    handle_test_bool(schema.entity_types[0].navigations.is_empty())
+}
+
+#[test]
+pub fn should_drop_denied_entity_set_and_unused_types() -> Result<(), String> {
+    let schema = fetch_filtered_schema(&SchemaFilter {
+        deny_entity_sets: vec!["BusinessPartnerSet".to_owned()],
+        deny_function_imports: vec!["RegenerateAllData".to_owned()],
+        ..Default::default()
+    })?;
+    let container = schema.entity_container.as_ref().ok_or("Entity container not found")?;
+
+    handle_test_comparison(&names(&schema.entity_types, |et| &et.name), &"Product".to_owned())?;
+    handle_test_comparison(
+        &names(container.function_imports.as_deref().unwrap_or_default(), |fi| &fi.name),
+        &"FindProducts".to_owned(),
+    )?;
+
+    // CT_Address is only used by BusinessPartner
+    handle_test_bool(schema.complex_types.as_deref().unwrap_or_default().is_empty())
+}
+
+#[test]
+pub fn should_keep_whole_schema_with_empty_filter() -> Result<(), String> {
+    let schema = fetch_filtered_schema(&SchemaFilter::default())?;
+    let container = schema.entity_container.as_ref().ok_or("Entity container not found")?;
+
+    handle_test_comparison(&schema.entity_types.len(), &2)?;
+    handle_test_comparison(&schema.associations.len(), &1)?;
+    handle_test_comparison(&container.association_sets.len(), &1)?;
+    handle_test_comparison(&container.function_imports.as_deref().unwrap_or_default().len(), &2)
+}
+
+#[test]
+pub fn should_report_unknown_names() -> Result<(), String> {
+    let filter = SchemaFilter {
+        allow_entity_sets: vec!["ProductSet".to_owned(), "SalesOrderSet".to_owned()],
+        deny_function_imports: vec!["FindProducts".to_owned(), "ConfirmSalesOrder".to_owned()],
+        ..Default::default()
+    };
+    let schema = fetch_filtered_schema(&SchemaFilter::default())?;
+
+    handle_test_comparison(
+        &unknown_names(&schema, &filter).join(","),
+        &"SalesOrderSet,ConfirmSalesOrder".to_owned(),
+    )
+}
@@ -0,0 +1,30 @@
+use std::{env, fs};
+
+use super::io::write_buffer_if_changed;
+use crate::test_utils::*;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_only_rewrite_changed_files() -> Result<(), String> {
+    let out_path = env::temp_dir().join(format!("parse_sap_odata_{}_wibble.rs", std::process::id()));
+    let _ = fs::remove_file(&out_path);
+
+    handle_test_bool(write_buffer_if_changed(&out_path, b"pub struct Wibble;").map_err(|err| err.to_string())?)?;
+    let first_mtime = fs::metadata(&out_path)
+        .and_then(|m| m.modified())
+        .map_err(|err| err.to_string())?;
+
+    // Same content, so the file is left untouched
+    handle_test_bool(!write_buffer_if_changed(&out_path, b"pub struct Wibble;").map_err(|err| err.to_string())?)?;
+    let second_mtime = fs::metadata(&out_path)
+        .and_then(|m| m.modified())
+        .map_err(|err| err.to_string())?;
+    handle_test_bool(first_mtime == second_mtime)?;
+
+    // Same length, but different content
+    handle_test_bool(write_buffer_if_changed(&out_path, b"pub struct Wobble;").map_err(|err| err.to_string())?)?;
+    let content = fs::read_to_string(&out_path).map_err(|err| err.to_string())?;
+    let _ = fs::remove_file(&out_path);
+
+    handle_test_comparison(&content, &"pub struct Wobble;".to_string())
+}
@@ -1,11 +1,14 @@
 use std::fmt::Formatter;
 
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
 use crate::{
     parser::{
         generate::{
-            gen_bool_string, gen_custom_deserializer_info, gen_opt_string, gen_opt_u16_string, gen_option_of_type,
-            gen_owned_string, gen_struct_field_into, gen_vector_of_type,
-            syntax_fragments::serde_fragments::{gen_deserialize_with, gen_serde_rename},
+            gen_custom_deserializer_info, gen_ident, gen_nullable_deserializer_info, gen_opt_number, gen_opt_string,
+            gen_owned_string, gen_path,
+            syntax_fragments::serde_fragments::{gen_deserialize_with, gen_deserialize_with_default, gen_serde_rename},
             syntax_fragments::*,
         },
         AsRustSrc,
@@ -14,8 +17,6 @@ use crate::{
     utils::{odata_name_to_rust_safe_name, to_pascal_case, to_upper_camel_case},
 };
 
-static MY_NAME: &[u8] = "Property".as_bytes();
-
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// Property type flags
 ///
@@ -47,49 +48,176 @@ impl std::fmt::Display for PropertyType {
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-enum PropertyFieldNames {
-    ODataName,
-    EdmType,
-    Nullable,
-    MaxLength,
-    Precision,
-    Scale,
-    ConcurrencyMode,
-    FcKeepInContent,
-    FcTargetPath,
-    SAPAnnotations,
-    DeserializerFn,
-}
+impl Property {
+    fn maybe_optional(&self, rust_type: TokenStream) -> TokenStream {
+        if self.nullable {
+            quote!(Option<#rust_type>)
+        } else {
+            rust_type
+        }
+    }
 
-// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-impl PropertyFieldNames {
-    pub fn value(prop_name: PropertyFieldNames) -> &'static [u8] {
-        match prop_name {
-            PropertyFieldNames::ODataName => b"odata_name",
-            PropertyFieldNames::EdmType => b"edm_type",
-            PropertyFieldNames::Nullable => b"nullable",
-            PropertyFieldNames::MaxLength => b"max_length",
-            PropertyFieldNames::Precision => b"precision",
-            PropertyFieldNames::Scale => b"scale",
-            PropertyFieldNames::ConcurrencyMode => b"concurrency_mode",
-            PropertyFieldNames::FcKeepInContent => b"fc_keep_in_content",
-            PropertyFieldNames::FcTargetPath => b"fc_target_path",
-            PropertyFieldNames::SAPAnnotations => b"sap_annotations",
-            PropertyFieldNames::DeserializerFn => b"deserializer_fn",
+    /// Map an EDM type name to the Rust type used in the service document struct
+    fn edm_rust_type(&self, edm_type: &str) -> TokenStream {
+        match edm_type {
+            "Binary" => self.maybe_optional(quote!(Vec<u8>)),
+            "Boolean" => self.maybe_optional(quote!(bool)),
+            "Byte" => quote!(u8),
+            "DateTime" | "DateTimeOffset" => self.maybe_optional(quote!(chrono::NaiveDateTime)),
+            "Decimal" => self.maybe_optional(quote!(rust_decimal::Decimal)),
+            "Double" => quote!(f64),
+            "Guid" => quote!(uuid::Uuid),
+            "Int16" => self.maybe_optional(quote!(i16)),
+            "Int32" => self.maybe_optional(quote!(i32)),
+            "Int64" => self.maybe_optional(quote!(i64)),
+            // EDM allows for null which is intentionally excluded by Rust
+            "Null" => quote!(()),
+            "SByte" => self.maybe_optional(quote!(i8)),
+            "Single" => quote!(f32),
+            "Time" => self.maybe_optional(quote!(std::time::SystemTime)),
+
+            // Use String as the catch-all case
+            _ => self.maybe_optional(quote!(String)),
         }
     }
-}
 
-// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-impl Property {
-    fn maybe_optional(&self, rust_type: &[u8]) -> Vec<u8> {
-        if self.nullable {
-            gen_option_of_type(rust_type)
-        } else {
-            rust_type.to_vec()
+    /// Is the Rust type generated for this property wrapped in an `Option`?
+    pub fn is_option_type(&self) -> bool {
+        match self.get_property_type() {
+            PropertyType::Edm(edm_type, _) => {
+                self.nullable && !matches!(edm_type.as_str(), "Byte" | "Double" | "Guid" | "Null" | "Single")
+            },
+            _ => false,
+        }
+    }
+
+    /// Is the Rust type generated for this property (ignoring any `Option` wrapper) a `String`?
+    pub fn is_string_type(&self) -> bool {
+        self.get_property_type() == PropertyType::Edm("String".to_string(), "".to_string())
+    }
+
+    /// Does the Rust type generated for this property (ignoring any `Option` wrapper) implement `Copy`?
+    pub fn is_copy_type(&self) -> bool {
+        match self.get_property_type() {
+            PropertyType::Edm(edm_type, _) => matches!(
+                edm_type.as_str(),
+                "Boolean" | "Byte" | "Decimal" | "Double" | "Int16" | "Int32" | "Int64" | "SByte" | "Single"
+            ),
+            _ => false,
+        }
+    }
+
+    /// Generate an expression that formats the value of `value_ref` as an OData V2 URI literal.
+    /// `value_ref` must be an expression that evaluates to a reference to a non-optional value of this property's type
+    pub fn gen_odata_literal_expr(&self, value_ref: TokenStream) -> TokenStream {
+        let edm_type = match self.get_property_type() {
+            PropertyType::Edm(edm_type, _) => edm_type,
+            _ => "String".to_string(),
+        };
+
+        match edm_type.as_str() {
+            "Binary" => quote! {
+                format!("binary'{}'", (#value_ref).iter().map(|b| format!("{b:02X}")).collect::<String>())
+            },
+            "Boolean" | "Byte" | "Int16" | "Int32" | "SByte" => quote!((#value_ref).to_string()),
+            "DateTime" => quote!(format!("datetime'{}'", (#value_ref).format("%Y-%m-%dT%H:%M:%S"))),
+            "DateTimeOffset" => quote!(format!("datetimeoffset'{}Z'", (#value_ref).format("%Y-%m-%dT%H:%M:%S"))),
+            "Decimal" => quote!(format!("{}M", #value_ref)),
+            "Double" => quote!(format!("{}d", #value_ref)),
+            "Guid" => quote!(format!("guid'{}'", #value_ref)),
+            "Int64" => quote!(format!("{}L", #value_ref)),
+            "Single" => quote!(format!("{}f", #value_ref)),
+            "Time" => quote! {
+                format!(
+                    "time'PT{}S'",
+                    (#value_ref).duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs() % 86400
+                )
+            },
+            _ => quote!(parse_sap_odata::query::string_literal(#value_ref)),
+        }
+    }
+
+    /// Generate an expression that converts the value of `value_ref` into a `parse_sap_odata::sql::SqlValue`.
+    /// `value_ref` must be an expression that evaluates to a reference to a non-optional value of this property's type
+    pub fn gen_sql_value_expr(&self, value_ref: TokenStream) -> TokenStream {
+        let edm_type = match self.get_property_type() {
+            PropertyType::Edm(edm_type, _) => edm_type,
+            _ => "String".to_string(),
+        };
+        let sql_value = gen_path(PATH_TO_SQL_VALUE);
+
+        match edm_type.as_str() {
+            "Binary" => quote!(#sql_value::Blob((#value_ref).clone())),
+            "Boolean" => quote!(#sql_value::Boolean(*#value_ref)),
+            "Byte" | "Int16" | "Int32" | "Int64" | "SByte" => quote!(#sql_value::Integer(i64::from(*#value_ref))),
+            "DateTime" => quote!(#sql_value::Text((#value_ref).format("%Y-%m-%dT%H:%M:%S%.3f").to_string())),
+            "DateTimeOffset" => quote!(#sql_value::Text((#value_ref).format("%Y-%m-%dT%H:%M:%S%.3fZ").to_string())),
+            "Double" => quote!(#sql_value::Real(*#value_ref)),
+            "Null" => quote!(#sql_value::Null),
+            "Single" => quote!(#sql_value::Real(f64::from(*#value_ref))),
+            "Time" => quote! {
+                {
+                    let secs = (#value_ref).duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_secs() % 86400;
+                    #sql_value::Text(format!("{:02}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60))
+                }
+            },
+            "Decimal" | "Guid" => quote!(#sql_value::Text((#value_ref).to_string())),
+            _ => quote!(#sql_value::Text((#value_ref).clone())),
+        }
+    }
+
+    /// The Rust type of the service document struct field generated for this property
+    pub fn to_rust_type(&self) -> TokenStream {
+        match self.get_property_type() {
+            PropertyType::Edm(edm_type, _) => self.edm_rust_type(&edm_type),
+            PropertyType::Complex(cmplx_type) => gen_ident(&to_upper_camel_case(&cmplx_type)).into_token_stream(),
+            PropertyType::Unqualified => gen_path(&self.edm_type),
         }
     }
 
+    /// Generate the declaration of the service document struct field for this property, together with its crate
+    /// reference.
+    ///
+    /// When `handle_nulls` is true, a nullable property marked as `m:null="true"` is deserialized as `None` rather than
+    /// as an empty string or a parse error
+    pub fn to_rust_field(&self, handle_nulls: bool) -> (TokenStream, String) {
+        let mut attrs = TokenStream::new();
+
+        let crate_ref = match self.get_property_type() {
+            PropertyType::Edm(_, crate_ref) => {
+                // It is assumed that the OData field name always starts with a capital letter
+                //
+                // WARNING: Field names coming out of SAP do not always use strict PascalCase formatting.
+                // For example, you will often see field names containing the abbreviation "ID" when you would expect
+                // "Id" as in "BusinessPartnerID" instead of "BusinessPartnerId"
+                if !to_pascal_case(&self.odata_name).eq(&self.odata_name) {
+                    attrs.extend(gen_serde_rename(&self.odata_name))
+                }
+
+                // Output the serde attribute for a custom deserializer
+                if let Some(deserializer_fn) = handle_nulls.then(|| gen_nullable_deserializer_info(self)).flatten() {
+                    attrs.extend(gen_deserialize_with_default(&deserializer_fn))
+                } else {
+                    let deserializer_fn = gen_custom_deserializer_info(self);
+                    if !deserializer_fn.is_empty() {
+                        attrs.extend(gen_deserialize_with(&deserializer_fn))
+                    }
+                }
+
+                crate_ref
+            },
+
+            // TODO Need to decide what to do with an unqualified property type
+            // Simply writing it out in the hope that the source code compiles is probably not a good idea...
+            PropertyType::Complex(_) | PropertyType::Unqualified => "".to_string(),
+        };
+
+        let field_ident = gen_ident(&odata_name_to_rust_safe_name(&self.odata_name));
+        let rust_type = self.to_rust_type();
+
+        (quote!(#attrs pub #field_ident: #rust_type,), crate_ref)
+    }
+
     pub fn get_property_type(&self) -> PropertyType {
         let type_name_parts = self.edm_type.split('.').collect::<Vec<&str>>();
 
@@ -114,36 +242,38 @@ impl Property {
     }
 }
 
-// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-fn line_into(out: &mut Vec<u8>, prop_md: PropertyFieldNames, val: Vec<u8>) {
-    out.extend_from_slice(PropertyFieldNames::value(prop_md));
-    out.extend_from_slice(COLON);
-    out.extend_from_slice(&val);
-    out.extend_from_slice(COMMA);
-    out.extend_from_slice(LINE_FEED);
-}
-
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// Metadata Module Generation
 /// Generate the source code that declares an instance of this Property
-impl std::fmt::Display for Property {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut out_buffer: Vec<u8> = Vec::new();
-        out_buffer.extend_from_slice(MY_NAME);
-        out_buffer.extend_from_slice(OPEN_CURLY);
-        line_into(&mut out_buffer, PropertyFieldNames::ODataName, gen_owned_string(&self.odata_name));
-        line_into(&mut out_buffer, PropertyFieldNames::EdmType, gen_owned_string(&self.edm_type));
-        line_into(&mut out_buffer, PropertyFieldNames::Nullable, gen_bool_string(self.nullable));
-        line_into(&mut out_buffer, PropertyFieldNames::MaxLength, gen_opt_u16_string(self.max_length));
-        line_into(&mut out_buffer, PropertyFieldNames::Precision, gen_opt_u16_string(self.precision));
-        line_into(&mut out_buffer, PropertyFieldNames::Scale, gen_opt_u16_string(self.scale));
-        line_into(&mut out_buffer, PropertyFieldNames::ConcurrencyMode, gen_opt_string(&self.concurrency_mode));
-        line_into(&mut out_buffer, PropertyFieldNames::FcKeepInContent, gen_bool_string(self.fc_keep_in_content));
-        line_into(&mut out_buffer, PropertyFieldNames::FcTargetPath, gen_opt_string(&self.fc_target_path));
-        line_into(&mut out_buffer, PropertyFieldNames::SAPAnnotations, format!("{}", self.sap_annotations).into_bytes());
-        line_into(&mut out_buffer, PropertyFieldNames::DeserializerFn, gen_owned_string(&self.deserializer_fn));
-        out_buffer.extend_from_slice(CLOSE_CURLY);
-        write!(f, "{}", String::from_utf8(out_buffer).unwrap())
+impl ToTokens for Property {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let odata_name = gen_owned_string(&self.odata_name);
+        let edm_type = gen_owned_string(&self.edm_type);
+        let nullable = self.nullable;
+        let max_length = gen_opt_number(&self.max_length);
+        let precision = gen_opt_number(&self.precision);
+        let scale = gen_opt_number(&self.scale);
+        let concurrency_mode = gen_opt_string(&self.concurrency_mode);
+        let fc_keep_in_content = self.fc_keep_in_content;
+        let fc_target_path = gen_opt_string(&self.fc_target_path);
+        let sap_annotations = &self.sap_annotations;
+        let deserializer_fn = gen_owned_string(&self.deserializer_fn);
+
+        tokens.extend(quote! {
+            Property {
+                odata_name: #odata_name,
+                edm_type: #edm_type,
+                nullable: #nullable,
+                max_length: #max_length,
+                precision: #precision,
+                scale: #scale,
+                concurrency_mode: #concurrency_mode,
+                fc_keep_in_content: #fc_keep_in_content,
+                fc_target_path: #fc_target_path,
+                sap_annotations: #sap_annotations,
+                deserializer_fn: #deserializer_fn,
+            }
+        })
     }
 }
 
@@ -153,64 +283,7 @@ impl std::fmt::Display for Property {
 impl AsRustSrc for Property {
     type CrateRef = String;
 
-    fn to_rust(&self) -> (Vec<u8>, Self::CrateRef) {
-        let mut out_buffer: Vec<u8> = Vec::new();
-
-        let (resolved_prop_type, crate_ref) = match Self::get_property_type(&self) {
-            PropertyType::Edm(edm_type, crate_ref) => {
-                // It is assumed that the OData field name always starts with a capital letter
-                //
-                // WARNING: Field names coming out of SAP do not always use strict PascalCase formatting.
-                // For example, you will often see field names containing the abbreviation "ID" when you would expect
-                // "Id" as in "BusinessPartnerID" instead of "BusinessPartnerId"
-                if !to_pascal_case(&self.odata_name).eq(&self.odata_name) {
-                    out_buffer.append(&mut gen_serde_rename(&self.odata_name))
-                }
-
-                // Output the serde attribute for a custom deserializer
-                let deserializer_fn = gen_custom_deserializer_info(self);
-                if !deserializer_fn.is_empty() {
-                    out_buffer.append(&mut gen_deserialize_with(&deserializer_fn))
-                }
-
-                // Generate source code for Rust type
-                let src = match edm_type.as_str() {
-                    "Binary" => self.maybe_optional(&*gen_vector_of_type(U8)),
-                    "Boolean" => self.maybe_optional(BOOLEAN),
-                    "Byte" => U8.to_vec(),
-                    "DateTime" | "DateTimeOffset" => self.maybe_optional(NAIVE_DATE_TIME),
-                    "Decimal" => self.maybe_optional(RUST_DECIMAL),
-                    "Double" => F64.to_vec(),
-                    "Guid" => UUID.to_vec(),
-                    "Int16" => self.maybe_optional(I16),
-                    "Int32" => self.maybe_optional(I32),
-                    "Int64" => self.maybe_optional(I64),
-                    // EDM allows for null which is intentionally excluded by Rust
-                    "Null" => UNIT.to_vec(),
-                    "SByte" => self.maybe_optional(I8),
-                    "Single" => F32.to_vec(),
-                    "Time" => self.maybe_optional(STD_TIME_SYSTEMTIME),
-
-                    // Use String as the catch-all case
-                    _ => self.maybe_optional(STRING),
-                };
-
-                (src, crate_ref)
-            },
-
-            PropertyType::Complex(cmplx_type) => (to_upper_camel_case(&cmplx_type).into_bytes(), "".to_string()),
-
-            // TODO Need to decide what to do with an unqualified property type
-            // Simply writing it out in the hope that the source code compiles is probably not a good idea...
-            PropertyType::Unqualified => (self.edm_type.clone().into_bytes(), "".to_string()),
-        };
-
-        gen_struct_field_into(
-            &mut out_buffer,
-            &odata_name_to_rust_safe_name(&self.odata_name),
-            &resolved_prop_type,
-        );
-
-        (out_buffer, crate_ref)
+    fn to_rust(&self) -> (TokenStream, Self::CrateRef) {
+        self.to_rust_field(false)
     }
 }
@@ -1,30 +1,14 @@
-use std::fmt::Formatter;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
 
 use crate::{
-    parser::generate::{
-        gen_owned_string,
-        syntax_fragments::{CLOSE_CURLY, COLON, LINE_FEED, OPEN_CURLY},
-    },
-    property::property_ref::PropertyRef,
-    utils::odata_name_to_rust_safe_name,
+    parser::generate::gen_owned_string, property::property_ref::PropertyRef, utils::odata_name_to_rust_safe_name,
 };
 
-static MY_NAME: &[u8] = "PropertyRef".as_bytes();
-
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-impl std::fmt::Display for PropertyRef {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let out_buffer: Vec<u8> = [
-            MY_NAME,
-            OPEN_CURLY,
-            LINE_FEED,
-            "name".as_bytes(),
-            COLON,
-            &*gen_owned_string(&odata_name_to_rust_safe_name(&self.name)),
-            CLOSE_CURLY,
-        ]
-        .concat();
-
-        write!(f, "{}", String::from_utf8(out_buffer).unwrap())
+impl ToTokens for PropertyRef {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let name = gen_owned_string(&odata_name_to_rust_safe_name(&self.name));
+        tokens.extend(quote!(PropertyRef { name: #name }))
     }
 }
@@ -8,6 +8,8 @@ use crate::{
     test_utils::*,
 };
 
+use proc_macro2::TokenStream;
+use quote::quote;
 use rust_decimal::{serde::str, Decimal};
 use serde::Deserialize;
 use std::str::FromStr;
@@ -122,6 +124,14 @@ fn should_not_convert_safe_property_name() -> Result<(), String> {
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+// A struct field cannot be pretty printed on its own, so wrap it in a struct and skip the struct's opening line
+fn to_field_src(field: TokenStream) -> Vec<String> {
+    tokens_to_rust_src(quote!(struct Wrapper { #field }))
+        .into_iter()
+        .skip(1)
+        .collect()
+}
+
 fn gen_property_of_type(prop_name: &str, prop_type: &str, is_nullable: bool) -> Property {
     let dummy_annotations = SAPAnnotationsProperty {
         label: Some("Dummy label".to_string()),
@@ -188,50 +198,52 @@ fn gen_property_of_type(prop_name: &str, prop_type: &str, is_nullable: bool) ->
 fn should_convert_edm_string() -> Result<(), String> {
     let prop = gen_property_of_type("BusinessPartnerID", "Edm.String", false);
     let (src, _) = prop.to_rust();
-    let src_lines = to_rust_src(src);
+    let src_lines = to_field_src(src);
     handle_test_comparison(
         &src_lines[0].to_string(),
         &r#"#[serde(rename = "BusinessPartnerID")]"#.to_string(),
     )?;
-    handle_test_comparison(&src_lines[1].to_string(), &"pub business_partner_id:String,".to_string())
+    handle_test_comparison(&src_lines[1].to_string(), &"pub business_partner_id: String,".to_string())
 }
 
 #[test]
 fn should_convert_edm_binary() -> Result<(), String> {
     let prop = gen_property_of_type("SomeBinaryStuff", "Edm.Binary", false);
     let (src, _) = prop.to_rust();
-    let src_lines = to_rust_src(src);
-    handle_test_comparison(&src_lines[0].to_string(), &"pub some_binary_stuff:Vec<u8>,".to_string())
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"pub some_binary_stuff: Vec<u8>,".to_string())
 }
 
 #[test]
 fn should_convert_edm_boolean() -> Result<(), String> {
     let prop = gen_property_of_type("IsOverMisunderestimatable", "Edm.Boolean", false);
     let (src, _) = prop.to_rust();
-    let src_lines = to_rust_src(src);
-    handle_test_comparison(&src_lines[0].to_string(), &"pub is_over_misunderestimatable:bool,".to_string())
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"pub is_over_misunderestimatable: bool,".to_string())
 }
 
 #[test]
 fn should_convert_edm_byte() -> Result<(), String> {
     let prop = gen_property_of_type("ASingleByte", "Edm.Byte", true);
     let (src, _) = prop.to_rust();
-    let src_lines = to_rust_src(src);
-    handle_test_comparison(&src_lines[0].to_string(), &"pub a_single_byte:u8,".to_string())
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"pub a_single_byte: u8,".to_string())
 }
 
 #[test]
 fn should_convert_edm_datetime() -> Result<(), String> {
     let prop = gen_property_of_type("OnceUponAWednesday", "Edm.DateTime", true);
     let (src, cr) = prop.to_rust();
-    let src_lines = to_rust_src(src);
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"#[serde(".to_string())?;
     handle_test_comparison(
-        &src_lines[0].to_string(),
-        &r#"#[serde(deserialize_with = "parse_sap_atom_feed::deserializers::edm_datetime::to_naive_date_time_opt")]"#.to_string(),
+        &src_lines[1].to_string(),
+        &r#"deserialize_with = "parse_sap_atom_feed::deserializers::edm_datetime::to_naive_date_time_opt""#.to_string(),
     )?;
+    handle_test_comparison(&src_lines[2].to_string(), &")]".to_string())?;
     handle_test_comparison(
-        &src_lines[1].to_string(),
-        &"pub once_upon_a_wednesday:Option<chrono::NaiveDateTime>,".to_string(),
+        &src_lines[3].to_string(),
+        &"pub once_upon_a_wednesday: Option<chrono::NaiveDateTime>,".to_string(),
     )?;
     handle_test_comparison(&cr, &CRATE_CHRONO.to_string())
 }
@@ -240,14 +252,16 @@ fn should_convert_edm_datetime() -> Result<(), String> {
 fn should_convert_edm_datetime_offset() -> Result<(), String> {
     let prop = gen_property_of_type("OnceUponAWednesday", "Edm.DateTimeOffset", true);
     let (src, cr) = prop.to_rust();
-    let src_lines = to_rust_src(src);
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"#[serde(".to_string())?;
     handle_test_comparison(
-        &src_lines[0].to_string(),
-        &r#"#[serde(deserialize_with = "parse_sap_atom_feed::deserializers::edm_datetime::to_naive_date_time_opt")]"#.to_string(),
+        &src_lines[1].to_string(),
+        &r#"deserialize_with = "parse_sap_atom_feed::deserializers::edm_datetime::to_naive_date_time_opt""#.to_string(),
     )?;
+    handle_test_comparison(&src_lines[2].to_string(), &")]".to_string())?;
     handle_test_comparison(
-        &src_lines[1].to_string(),
-        &"pub once_upon_a_wednesday:Option<chrono::NaiveDateTime>,".to_string(),
+        &src_lines[3].to_string(),
+        &"pub once_upon_a_wednesday: Option<chrono::NaiveDateTime>,".to_string(),
     )?;
     handle_test_comparison(&cr, &CRATE_CHRONO.to_string())
 }
@@ -256,14 +270,16 @@ fn should_convert_edm_datetime_offset() -> Result<(), String> {
 fn should_convert_edm_decimal() -> Result<(), String> {
     let prop = gen_property_of_type("SomeDecimalNumber", "Edm.Decimal", false);
     let (src, cr) = prop.to_rust();
-    let src_lines = to_rust_src(src);
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"#[serde(".to_string())?;
     handle_test_comparison(
-        &src_lines[0].to_string(),
-        &r#"#[serde(deserialize_with = "parse_sap_atom_feed::deserializers::edm_decimal::to_rust_decimal_0dp")]"#.to_string(),
+        &src_lines[1].to_string(),
+        &r#"deserialize_with = "parse_sap_atom_feed::deserializers::edm_decimal::to_rust_decimal_0dp""#.to_string(),
     )?;
+    handle_test_comparison(&src_lines[2].to_string(), &")]".to_string())?;
     handle_test_comparison(
-        &src_lines[1].to_string(),
-        &"pub some_decimal_number:rust_decimal::Decimal,".to_string(),
+        &src_lines[3].to_string(),
+        &"pub some_decimal_number: rust_decimal::Decimal,".to_string(),
     )?;
     handle_test_comparison(&cr, &CRATE_RUST_DECIMAL.to_string())
 }
@@ -272,74 +288,95 @@ fn should_convert_edm_decimal() -> Result<(), String> {
 fn should_convert_edm_double() -> Result<(), String> {
     let prop = gen_property_of_type("DoubleTrouble", "Edm.Double", false);
     let (src, _) = prop.to_rust();
-    let src_lines = to_rust_src(src);
-    handle_test_comparison(&src_lines[0].to_string(), &"pub double_trouble:f64,".to_string())
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"pub double_trouble: f64,".to_string())
 }
 
 #[test]
 fn should_convert_edm_guid() -> Result<(), String> {
     let prop = gen_property_of_type("GooeyGuid", "Edm.Guid", false);
     let (src, _) = prop.to_rust();
-    let src_lines = to_rust_src(src);
-    handle_test_comparison(&src_lines[0].to_string(), &"pub gooey_guid:uuid::Uuid,".to_string())
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"pub gooey_guid: uuid::Uuid,".to_string())
 }
 
 #[test]
 fn should_convert_edm_int16() -> Result<(), String> {
     let prop = gen_property_of_type("MaybeAnInt16", "Edm.Int16", true);
     let (src, _) = prop.to_rust();
-    let src_lines = to_rust_src(src);
-    handle_test_comparison(&src_lines[0].to_string(), &"pub maybe_an_int_16:Option<i16>,".to_string())
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"pub maybe_an_int_16: Option<i16>,".to_string())
 }
 
 #[test]
 fn should_convert_edm_int32() -> Result<(), String> {
     let prop = gen_property_of_type("MaybeAnInt32", "Edm.Int32", true);
     let (src, _) = prop.to_rust();
-    let src_lines = to_rust_src(src);
-    handle_test_comparison(&src_lines[0].to_string(), &"pub maybe_an_int_32:Option<i32>,".to_string())
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"pub maybe_an_int_32: Option<i32>,".to_string())
 }
 
 #[test]
 fn should_convert_edm_int64() -> Result<(), String> {
     let prop = gen_property_of_type("MaybeAnInt64", "Edm.Int64", true);
     let (src, _) = prop.to_rust();
-    let src_lines = to_rust_src(src);
-    handle_test_comparison(&src_lines[0].to_string(), &"pub maybe_an_int_64:Option<i64>,".to_string())
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"pub maybe_an_int_64: Option<i64>,".to_string())
+}
+
+#[test]
+fn should_convert_nullable_edm_int64_handling_nulls() -> Result<(), String> {
+    let prop = gen_property_of_type("MaybeAnInt64", "Edm.Int64", true);
+    let (src, _) = prop.to_rust_field(true);
+    let src = to_field_src(src).join(" ");
+    handle_test_bool(src.contains(r#"default, deserialize_with = "parse_sap_odata::deserializers::to_nullable""#))?;
+    handle_test_bool(src.contains("pub maybe_an_int_64: Option<i64>,"))
+}
+
+#[test]
+fn should_convert_nullable_edm_decimal_handling_nulls() -> Result<(), String> {
+    let mut prop = gen_property_of_type("MaybeADecimal", "Edm.Decimal", true);
+    prop.scale = Some(3);
+    let (src, _) = prop.to_rust_field(true);
+    let src = to_field_src(src).join(" ");
+    handle_test_bool(src.contains(
+        r#"default, deserialize_with = "parse_sap_odata::deserializers::edm_decimal::to_nullable_rust_decimal_3dp""#,
+    ))?;
+    handle_test_bool(src.contains("pub maybe_a_decimal: Option<rust_decimal::Decimal>,"))
 }
 
 #[test]
 fn should_convert_edm_null() -> Result<(), String> {
     let prop = gen_property_of_type("NothingToSeeHere", "Edm.Null", false);
     let (src, _) = prop.to_rust();
-    let src_lines = to_rust_src(src);
-    handle_test_comparison(&src_lines[0].to_string(), &"pub nothing_to_see_here:(),".to_string())
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"pub nothing_to_see_here: (),".to_string())
 }
 
 #[test]
 fn should_convert_edm_sbyte() -> Result<(), String> {
     let prop = gen_property_of_type("SingleByte", "Edm.SByte", false);
     let (src, _) = prop.to_rust();
-    let src_lines = to_rust_src(src);
-    handle_test_comparison(&src_lines[0].to_string(), &"pub single_byte:i8,".to_string())
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"pub single_byte: i8,".to_string())
 }
 
 #[test]
 fn should_convert_edm_float_single() -> Result<(), String> {
     let prop = gen_property_of_type("SinglePrecisionFloat", "Edm.Single", false);
     let (src, _) = prop.to_rust();
-    let src_lines = to_rust_src(src);
-    handle_test_comparison(&src_lines[0].to_string(), &"pub single_precision_float:f32,".to_string())
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"pub single_precision_float: f32,".to_string())
 }
 
 #[test]
 fn should_convert_edm_time() -> Result<(), String> {
     let prop = gen_property_of_type("WhatsTheTimeEccles", "Edm.Time", false);
     let (src, _) = prop.to_rust();
-    let src_lines = to_rust_src(src);
+    let src_lines = to_field_src(src);
     handle_test_comparison(
         &src_lines[0].to_string(),
-        &"pub whats_the_time_eccles:std::time::SystemTime,".to_string(),
+        &"pub whats_the_time_eccles: std::time::SystemTime,".to_string(),
     )
 }
 
@@ -348,8 +385,8 @@ fn should_convert_nonsense_type() -> Result<(), String> {
     // Unknown EDM type
     let prop = gen_property_of_type("WhatIsIt", "Edm.NonsenseTypeValue", false);
     let (src, _) = prop.to_rust();
-    let src_lines = to_rust_src(src);
-    handle_test_comparison(&src_lines[0].to_string(), &"pub what_is_it:String,".to_string())
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"pub what_is_it: String,".to_string())
 }
 
 #[test]
@@ -357,6 +394,6 @@ fn should_convert_unknown_type() -> Result<(), String> {
     // Unknown type
     let prop = gen_property_of_type("ThatsWeird", "NotSeenThisBefore", false);
     let (src, _) = prop.to_rust();
-    let src_lines = to_rust_src(src);
-    handle_test_comparison(&src_lines[0].to_string(), &"pub thats_weird:NotSeenThisBefore,".to_string())
+    let src_lines = to_field_src(src);
+    handle_test_comparison(&src_lines[0].to_string(), &"pub thats_weird: NotSeenThisBefore,".to_string())
 }
@@ -0,0 +1,232 @@
+use crate::sap_annotations::filter_restriction::SAPFilterRestrictionProperty;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Implemented by the generated `<EntityType>FilterField` enums.
+/// Each variant is a filterable property together with the filter restrictions declared by its SAP annotations
+pub trait FilterField: Copy + PartialEq + 'static {
+    fn odata_name(&self) -> &'static str;
+    /// The value of `sap:required-in-filter`
+    fn is_required_in_filter(&self) -> bool;
+    /// The value of `sap:filter-restriction`
+    fn filter_restriction(&self) -> Option<SAPFilterRestrictionProperty>;
+    /// The fields that must appear in every filter
+    fn required_fields() -> &'static [Self];
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// A condition on a single property.
+/// Values are OData literals such as `2024` or `'1000'`, and must not be percent-encoded
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FilterCondition {
+    Eq(String),
+    Ne(String),
+    Gt(String),
+    Ge(String),
+    Lt(String),
+    Le(String),
+    Between(String, String),
+}
+
+impl FilterCondition {
+    fn is_eq(&self) -> bool {
+        matches!(self, FilterCondition::Eq(_))
+    }
+
+    // Any single condition other than "not equal" selects one interval of values
+    fn is_interval(&self) -> bool {
+        !matches!(self, FilterCondition::Ne(_))
+    }
+
+    fn to_expression(&self, odata_name: &str) -> String {
+        match self {
+            FilterCondition::Eq(val) => format!("{odata_name} eq {val}"),
+            FilterCondition::Ne(val) => format!("{odata_name} ne {val}"),
+            FilterCondition::Gt(val) => format!("{odata_name} gt {val}"),
+            FilterCondition::Ge(val) => format!("{odata_name} ge {val}"),
+            FilterCondition::Lt(val) => format!("{odata_name} lt {val}"),
+            FilterCondition::Le(val) => format!("{odata_name} le {val}"),
+            FilterCondition::Between(low, high) => format!("({odata_name} ge {low} and {odata_name} le {high})"),
+        }
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// The reasons why a filter would be rejected by SAP Gateway
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum FilterError {
+    /// A property annotated with `sap:required-in-filter="true"` does not appear in the filter
+    MissingRequiredFilter { property: String },
+    /// The conditions on a property do not satisfy its `sap:filter-restriction`
+    RestrictionViolated {
+        property: String,
+        restriction: SAPFilterRestrictionProperty,
+    },
+}
+
+impl std::fmt::Display for FilterError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterError::MissingRequiredFilter { property } => {
+                write!(f, "Property {property} is required in the filter")
+            },
+            FilterError::RestrictionViolated { property, restriction } => {
+                let allowed = match restriction {
+                    SAPFilterRestrictionProperty::SingleValue => "a single \"eq\" condition",
+                    SAPFilterRestrictionProperty::MultiValue => "only \"eq\" conditions",
+                    SAPFilterRestrictionProperty::Interval => "a single interval",
+                };
+                write!(f, "Property {property} can only be filtered using {allowed}")
+            },
+        }
+    }
+}
+
+impl std::error::Error for FilterError {}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Builds a `$filter` expression that respects the filter annotations of an entity type's properties:
+/// * Every property annotated with `sap:required-in-filter="true"` must have at least one condition
+/// * A `single-value` property allows exactly one `eq` condition
+/// * A `multi-value` property allows any number of `eq` conditions, which are combined using `or`
+/// * An `interval` property allows exactly one condition other than `ne`, which is typically a `between` condition
+///
+/// Conditions on different properties are combined using `and`.
+///
+/// ```rust,ignore
+/// let filter = FilterBuilder::new()
+///     .eq(SalesOrderQueryResultFilterField::CompanyCode, &quoted_string("1000"))
+///     .between(SalesOrderQueryResultFilterField::PostingDate, "datetime'2024-01-01T00:00:00'", "datetime'2024-12-31T00:00:00'")
+///     .build()?;
+/// ```
+#[derive(Clone, Debug)]
+pub struct FilterBuilder<F: FilterField> {
+    conditions: Vec<(F, FilterCondition)>,
+}
+
+impl<F: FilterField> Default for FilterBuilder<F> {
+    fn default() -> Self {
+        FilterBuilder { conditions: vec![] }
+    }
+}
+
+impl<F: FilterField> FilterBuilder<F> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn condition(mut self, field: F, condition: FilterCondition) -> Self {
+        self.conditions.push((field, condition));
+        self
+    }
+
+    pub fn eq(self, field: F, value: &str) -> Self {
+        self.condition(field, FilterCondition::Eq(value.to_owned()))
+    }
+
+    pub fn ne(self, field: F, value: &str) -> Self {
+        self.condition(field, FilterCondition::Ne(value.to_owned()))
+    }
+
+    pub fn gt(self, field: F, value: &str) -> Self {
+        self.condition(field, FilterCondition::Gt(value.to_owned()))
+    }
+
+    pub fn ge(self, field: F, value: &str) -> Self {
+        self.condition(field, FilterCondition::Ge(value.to_owned()))
+    }
+
+    pub fn lt(self, field: F, value: &str) -> Self {
+        self.condition(field, FilterCondition::Lt(value.to_owned()))
+    }
+
+    pub fn le(self, field: F, value: &str) -> Self {
+        self.condition(field, FilterCondition::Le(value.to_owned()))
+    }
+
+    pub fn between(self, field: F, low: &str, high: &str) -> Self {
+        self.condition(field, FilterCondition::Between(low.to_owned(), high.to_owned()))
+    }
+
+    // The fields that have conditions, in the order they were first used
+    fn fields(&self) -> Vec<F> {
+        self.conditions.iter().fold(Vec::new(), |mut acc, (field, _)| {
+            if !acc.contains(field) {
+                acc.push(*field);
+            }
+            acc
+        })
+    }
+
+    fn conditions_for(&self, field: F) -> Vec<&FilterCondition> {
+        self.conditions
+            .iter()
+            .filter(|(f, _)| *f == field)
+            .map(|(_, condition)| condition)
+            .collect()
+    }
+
+    /// Check that the conditions satisfy the filter annotations
+    pub fn validate(&self) -> Result<(), FilterError> {
+        let fields = self.fields();
+
+        for required in F::required_fields() {
+            if !fields.contains(required) {
+                return Err(FilterError::MissingRequiredFilter {
+                    property: required.odata_name().to_owned(),
+                });
+            }
+        }
+
+        for field in fields {
+            let Some(restriction) = field.filter_restriction() else {
+                continue;
+            };
+            let conditions = self.conditions_for(field);
+
+            let is_valid = match restriction {
+                SAPFilterRestrictionProperty::SingleValue => conditions.len() == 1 && conditions[0].is_eq(),
+                SAPFilterRestrictionProperty::MultiValue => conditions.iter().all(|c| c.is_eq()),
+                SAPFilterRestrictionProperty::Interval => conditions.len() == 1 && conditions[0].is_interval(),
+            };
+
+            if !is_valid {
+                return Err(FilterError::RestrictionViolated {
+                    property: field.odata_name().to_owned(),
+                    restriction,
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// The `$filter` expression. This value is not percent-encoded
+    pub fn build(&self) -> Result<String, FilterError> {
+        self.validate()?;
+
+        Ok(self
+            .fields()
+            .into_iter()
+            .map(|field| {
+                let (excluded, included): (Vec<&FilterCondition>, Vec<&FilterCondition>) = self
+                    .conditions_for(field)
+                    .into_iter()
+                    .partition(|c| matches!(c, FilterCondition::Ne(_)));
+
+                // Several inclusive conditions on the same property are alternatives, whereas every exclusion applies
+                let alternatives: Vec<String> = included.iter().map(|c| c.to_expression(field.odata_name())).collect();
+                let expressions: Vec<String> = match alternatives.len() {
+                    0 => vec![],
+                    1 => alternatives,
+                    _ => vec![format!("({})", alternatives.join(" or "))],
+                }
+                .into_iter()
+                .chain(excluded.iter().map(|c| c.to_expression(field.odata_name())))
+                .collect();
+
+                expressions.join(" and ")
+            })
+            .collect::<Vec<String>>()
+            .join(" and "))
+    }
+}
@@ -0,0 +1,34 @@
+pub mod filter;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Percent-encode all characters in a URL path segment or query option value except the unreserved characters defined
+/// in RFC 3986
+pub fn percent_encode(s: &str) -> String {
+    s.bytes().fold(String::with_capacity(s.len()), |mut acc, b| {
+        if b.is_ascii_alphanumeric() || matches!(b, b'-' | b'.' | b'_' | b'~') {
+            acc.push(b as char);
+        } else {
+            acc.push_str(&format!("%{b:02X}"));
+        }
+        acc
+    })
+}
+
+/// Format a string as an OData V2 string literal
+///
+/// The value is enclosed in single quotes, any single quotes within the value are doubled, and the result is
+/// percent-encoded so that it can be used directly in a URL
+pub fn string_literal(s: &str) -> String {
+    format!("'{}'", percent_encode(&s.replace('\'', "''")))
+}
+
+/// Format a string as an OData V2 string literal without percent-encoding it, E.G. for use in a `$filter` expression
+/// that will be percent-encoded as a whole
+pub fn quoted_string(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(feature = "parser")]
+#[cfg(test)]
+pub mod unit_tests;
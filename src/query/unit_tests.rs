@@ -0,0 +1,109 @@
+use super::{filter::*, *};
+use crate::sap_annotations::filter_restriction::SAPFilterRestrictionProperty;
+use crate::test_utils::*;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_percent_encode_reserved_characters() -> Result<(), String> {
+    handle_test_comparison(&percent_encode("AB-1.0_~"), &"AB-1.0_~".to_string())?;
+    handle_test_comparison(&percent_encode("a b/c&d"), &"a%20b%2Fc%26d".to_string())?;
+    handle_test_comparison(&percent_encode("ä"), &"%C3%A4".to_string())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_format_string_literal() -> Result<(), String> {
+    handle_test_comparison(&string_literal("1000"), &"'1000'".to_string())?;
+    handle_test_comparison(&string_literal("O'Neil"), &"'O%27%27Neil'".to_string())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_format_quoted_string() -> Result<(), String> {
+    handle_test_comparison(&quoted_string("O'Neil"), &"'O''Neil'".to_string())
+}
+
+// Hand-written equivalent of a generated filter field enum
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum SalesFilterField {
+    CompanyCode,
+    Customer,
+    PostingDate,
+    Currency,
+}
+
+impl FilterField for SalesFilterField {
+    fn odata_name(&self) -> &'static str {
+        match *self {
+            Self::CompanyCode => "CompanyCode",
+            Self::Customer => "Customer",
+            Self::PostingDate => "PostingDate",
+            Self::Currency => "Currency",
+        }
+    }
+    fn is_required_in_filter(&self) -> bool {
+        matches!(*self, Self::CompanyCode)
+    }
+    fn filter_restriction(&self) -> Option<SAPFilterRestrictionProperty> {
+        match *self {
+            Self::CompanyCode => Some(SAPFilterRestrictionProperty::SingleValue),
+            Self::Customer => Some(SAPFilterRestrictionProperty::MultiValue),
+            Self::PostingDate => Some(SAPFilterRestrictionProperty::Interval),
+            Self::Currency => None,
+        }
+    }
+    fn required_fields() -> &'static [Self] {
+        &[Self::CompanyCode]
+    }
+}
+
+#[test]
+fn should_build_filter_respecting_restrictions() -> Result<(), String> {
+    let filter = FilterBuilder::new()
+        .eq(SalesFilterField::CompanyCode, &quoted_string("1000"))
+        .eq(SalesFilterField::Customer, &quoted_string("C1"))
+        .eq(SalesFilterField::Customer, &quoted_string("C2"))
+        .between(SalesFilterField::PostingDate, "datetime'2024-01-01T00:00:00'", "datetime'2024-12-31T00:00:00'")
+        .ne(SalesFilterField::Currency, &quoted_string("USD"))
+        .build()
+        .map_err(|err| err.to_string())?;
+
+    handle_test_comparison(
+        &filter,
+        &"CompanyCode eq '1000' and (Customer eq 'C1' or Customer eq 'C2') and (PostingDate ge datetime'2024-01-01T00:00:00' and PostingDate le datetime'2024-12-31T00:00:00') and Currency ne 'USD'".to_string(),
+    )
+}
+
+#[test]
+fn should_reject_filter_missing_required_property() -> Result<(), String> {
+    let result = FilterBuilder::new().eq(SalesFilterField::Currency, "'EUR'").build();
+
+    handle_test_comparison(
+        &result.unwrap_err().to_string(),
+        &"Property CompanyCode is required in the filter".to_string(),
+    )
+}
+
+#[test]
+fn should_reject_filter_violating_restriction() -> Result<(), String> {
+    let interval_on_single_value = FilterBuilder::new()
+        .between(SalesFilterField::CompanyCode, "'1000'", "'2000'")
+        .build();
+    let two_intervals = FilterBuilder::new()
+        .eq(SalesFilterField::CompanyCode, "'1000'")
+        .ge(SalesFilterField::PostingDate, "datetime'2024-01-01T00:00:00'")
+        .le(SalesFilterField::PostingDate, "datetime'2024-12-31T00:00:00'")
+        .build();
+
+    handle_test_comparison(
+        &interval_on_single_value.unwrap_err(),
+        &FilterError::RestrictionViolated {
+            property: "CompanyCode".to_owned(),
+            restriction: SAPFilterRestrictionProperty::SingleValue,
+        },
+    )?;
+    handle_test_comparison(
+        &two_intervals.unwrap_err().to_string(),
+        &"Property PostingDate can only be filtered using a single interval".to_string(),
+    )
+}
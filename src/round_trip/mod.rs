@@ -0,0 +1,93 @@
+use std::fmt::Debug;
+
+use quick_xml::{
+    events::{BytesEnd, BytesStart, Event},
+    Reader, Writer,
+};
+use serde::{de::DeserializeOwned, Serialize};
+
+static ROOT_ELEMENT: &str = "properties";
+static ATOM_ROOT_ELEMENT: &str = "m:properties";
+static PREFIX_DATA_SERVICES: &str = "d:";
+static ATTR_M_NULL: (&str, &str) = ("m:null", "true");
+static XMLNS_M: (&str, &str) = ("xmlns:m", "http://schemas.microsoft.com/ado/2007/08/dataservices/metadata");
+static XMLNS_D: (&str, &str) = ("xmlns:d", "http://schemas.microsoft.com/ado/2007/08/dataservices");
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Serialize a service document struct into an `<m:properties>` block, formatted as it would appear in the content of
+/// an Atom entry.
+///
+/// Every property element (including the properties of a complex type) is placed in the `d:` namespace.
+/// Since `quick_xml` serializes `None` as an empty element, empty property elements are marked as `m:null="true"`
+pub fn to_atom_properties<T: Serialize>(value: &T) -> Result<String, String> {
+    let plain_xml = quick_xml::se::to_string_with_root(ROOT_ELEMENT, value).map_err(|err| err.to_string())?;
+
+    let mut reader = Reader::from_str(&plain_xml);
+    let mut writer = Writer::new(Vec::new());
+    let mut depth: usize = 0;
+
+    let prefixed = |elem: &BytesStart| {
+        let name = String::from_utf8_lossy(elem.local_name().as_ref()).into_owned();
+        BytesStart::new(format!("{PREFIX_DATA_SERVICES}{name}"))
+    };
+
+    loop {
+        let event = match reader.read_event().map_err(|err| err.to_string())? {
+            // The root element becomes <m:properties> and declares the namespaces
+            Event::Start(_) if depth == 0 => {
+                depth += 1;
+                Event::Start(BytesStart::new(ATOM_ROOT_ELEMENT).with_attributes([XMLNS_M, XMLNS_D]))
+            },
+            Event::Empty(_) if depth == 0 => {
+                Event::Empty(BytesStart::new(ATOM_ROOT_ELEMENT).with_attributes([XMLNS_M, XMLNS_D]))
+            },
+            Event::Start(elem) => {
+                depth += 1;
+                Event::Start(prefixed(&elem))
+            },
+            Event::Empty(elem) => Event::Empty(prefixed(&elem).with_attributes([ATTR_M_NULL])),
+            Event::End(elem) => {
+                depth -= 1;
+                let name = String::from_utf8_lossy(elem.local_name().as_ref()).into_owned();
+
+                if depth == 0 {
+                    Event::End(BytesEnd::new(ATOM_ROOT_ELEMENT))
+                } else {
+                    Event::End(BytesEnd::new(format!("{PREFIX_DATA_SERVICES}{name}")))
+                }
+            },
+            Event::Eof => break,
+            other => other,
+        };
+
+        writer.write_event(event).map_err(|err| err.to_string())?;
+    }
+
+    String::from_utf8(writer.into_inner()).map_err(|err| err.to_string())
+}
+
+/// Check that a service document struct survives being serialized into an Atom `<m:properties>` block and then
+/// deserialized again.
+///
+/// Since the generated structs do not implement `PartialEq`, the values are compared using their `Debug` output
+pub fn check_round_trip<T: Serialize + DeserializeOwned + Debug>(value: &T) -> Result<(), String> {
+    let xml = to_atom_properties(value)?;
+    let round_tripped: T =
+        quick_xml::de::from_str(&xml).map_err(|err| format!("Unable to deserialize {xml}\n{err}"))?;
+
+    let expected = format!("{value:?}");
+    let actual = format!("{round_tripped:?}");
+
+    if expected.eq(&actual) {
+        Ok(())
+    } else {
+        Err(format!(
+            "Round trip altered the value\nExpected: {expected}\n  Actual: {actual}\n     XML: {xml}"
+        ))
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(feature = "parser")]
+#[cfg(test)]
+pub mod unit_tests;
@@ -0,0 +1,75 @@
+use serde::Deserialize;
+
+use super::*;
+use crate::test_utils::*;
+
+// Hand-written equivalents of the structs generated in a service document
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct CtAddress {
+    #[serde(default, deserialize_with = "crate::deserializers::to_nullable")]
+    city: Option<String>,
+    #[serde(default, deserialize_with = "crate::deserializers::to_nullable")]
+    country: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct Product {
+    address: CtAddress,
+    #[serde(deserialize_with = "parse_sap_atom_feed::deserializers::edm_datetime::to_naive_date_time_opt")]
+    changed_at: Option<chrono::NaiveDateTime>,
+    is_available: bool,
+    #[serde(deserialize_with = "parse_sap_atom_feed::deserializers::edm_decimal::to_rust_decimal_3dp")]
+    price: rust_decimal::Decimal,
+    #[serde(rename = "ProductID")]
+    product_id: String,
+}
+
+fn product(changed_at: Option<chrono::NaiveDateTime>) -> Product {
+    Product {
+        address: CtAddress {
+            city: Some("Walldorf".to_owned()),
+            country: None,
+        },
+        changed_at,
+        is_available: true,
+        price: rust_decimal::Decimal::new(-1_249_500, 3),
+        product_id: "HT-1000 & <1>".to_owned(),
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_serialize_atom_properties() -> Result<(), String> {
+    let xml = to_atom_properties(&product(None))?;
+
+    handle_test_bool(xml.starts_with(&format!("<m:properties xmlns:m=\"{}\" xmlns:d=\"{}\">", XMLNS_M.1, XMLNS_D.1)))?;
+    handle_test_bool(xml.contains("<d:Address><d:City>Walldorf</d:City><d:Country m:null=\"true\"/></d:Address>"))?;
+    handle_test_bool(xml.contains("<d:ChangedAt m:null=\"true\"/>"))?;
+    handle_test_bool(xml.contains("<d:Price>-1249.500</d:Price>"))?;
+    handle_test_bool(xml.contains("<d:ProductID>HT-1000 &amp; &lt;1&gt;</d:ProductID>"))?;
+    handle_test_bool(xml.ends_with("</m:properties>"))
+}
+
+#[test]
+pub fn should_round_trip_through_atom_properties() -> Result<(), String> {
+    let changed_at = chrono::NaiveDate::from_ymd_opt(2024, 2, 29)
+        .and_then(|date| date.and_hms_opt(23, 59, 1))
+        .ok_or("Invalid test date")?;
+
+    check_round_trip(&product(None))?;
+    check_round_trip(&product(Some(changed_at)))
+}
+
+#[test]
+pub fn should_report_values_altered_by_round_trip() -> Result<(), String> {
+    let mut lossy = product(None);
+    // An empty string cannot be distinguished from null
+    lossy.address.country = Some(String::new());
+
+    match check_round_trip(&lossy) {
+        Ok(()) => Err("Expected the round trip to alter the country".to_owned()),
+        Err(msg) => handle_test_bool(msg.starts_with("Round trip altered the value")),
+    }
+}
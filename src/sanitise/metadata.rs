@@ -0,0 +1,21 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
+use super::SanitiseOptions;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+impl ToTokens for SanitiseOptions {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let escape_attribute_quotes = self.escape_attribute_quotes;
+        let escape_bare_ampersands = self.escape_bare_ampersands;
+        let remove_invalid_chars = self.remove_invalid_chars;
+
+        tokens.extend(quote! {
+            parse_sap_odata::sanitise::SanitiseOptions {
+                escape_attribute_quotes: #escape_attribute_quotes,
+                escape_bare_ampersands: #escape_bare_ampersands,
+                remove_invalid_chars: #remove_invalid_chars,
+            }
+        })
+    }
+}
@@ -0,0 +1,233 @@
+#[cfg(feature = "parser")]
+pub mod metadata;
+
+use std::{borrow::Cow, fmt};
+
+use quick_xml::DeError;
+use serde::de::DeserializeOwned;
+
+// Sections copied without modification, identified by their opening and closing delimiters
+static VERBATIM_SECTIONS: [(&str, &str); 4] = [("<!--", "-->"), ("<![CDATA[", "]]>"), ("<?", "?>"), ("<!", ">")];
+
+// The longest entity or character reference that is recognised as such, including the `&` and `;`
+const MAX_REFERENCE_LEN: usize = 32;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// The repairs made by [`sanitise_xml`] to XML that `quick_xml` would otherwise reject.
+///
+/// Every repair is switched on by default
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SanitiseOptions {
+    /// Escape quote characters that appear within an attribute value delimited by the same character.  For example,
+    /// SAP returns `<entry m:etag="W/"datetime'2024-05-01T10%3A00%3A00'"">`, which becomes
+    /// `<entry m:etag="W/&quot;datetime'2024-05-01T10%3A00%3A00'&quot;">`.
+    ///
+    /// A quote is only treated as the end of the value if it is followed by whitespace, `>`, `/`, `?` or the end of the
+    /// document
+    pub escape_attribute_quotes: bool,
+
+    /// Escape `&` characters that do not start an entity or character reference, as in `<d:Name>Q&A</d:Name>`
+    pub escape_bare_ampersands: bool,
+
+    /// Remove characters that are not allowed anywhere in an XML document, such as ASCII control characters other than
+    /// tab, line feed and carriage return
+    pub remove_invalid_chars: bool,
+}
+
+impl Default for SanitiseOptions {
+    fn default() -> Self {
+        SanitiseOptions {
+            escape_attribute_quotes: true,
+            escape_bare_ampersands: true,
+            remove_invalid_chars: true,
+        }
+    }
+}
+
+/// A single repair made by [`sanitise_xml`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SanitiseFix {
+    EscapedAttributeQuote(char),
+    EscapedBareAmpersand,
+    RemovedInvalidChar(char),
+}
+
+/// A repair made by [`sanitise_xml`], together with the byte offset in the original XML at which it was made
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SanitiseChange {
+    pub offset: usize,
+    pub fix: SanitiseFix,
+}
+
+impl fmt::Display for SanitiseChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.fix {
+            SanitiseFix::EscapedAttributeQuote(quote) => {
+                write!(f, "Escaped {quote} within an attribute value at byte {}", self.offset)
+            },
+            SanitiseFix::EscapedBareAmpersand => write!(f, "Escaped bare & at byte {}", self.offset),
+            SanitiseFix::RemovedInvalidChar(c) => {
+                write!(f, "Removed invalid character U+{:04X} at byte {}", c as u32, self.offset)
+            },
+        }
+    }
+}
+
+/// The sanitised XML, together with a list of the repairs made.  If no repairs were needed, the XML is borrowed from
+/// the original
+#[derive(Debug)]
+pub struct Sanitised<'a> {
+    pub xml: Cow<'a, str>,
+    pub changes: Vec<SanitiseChange>,
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Repair the known quirks of the XML returned by SAP OData services before it is passed to `quick_xml`
+pub fn sanitise_xml<'a>(xml: &'a str, options: &SanitiseOptions) -> Sanitised<'a> {
+    let mut sanitiser = Sanitiser {
+        xml,
+        options,
+        out: String::with_capacity(xml.len()),
+        changes: Vec::new(),
+    };
+
+    sanitiser.run();
+
+    Sanitised {
+        xml: if sanitiser.changes.is_empty() {
+            Cow::Borrowed(xml)
+        } else {
+            Cow::Owned(sanitiser.out)
+        },
+        changes: sanitiser.changes,
+    }
+}
+
+/// Sanitise `xml` then deserialize it, returning the deserialized value together with the repairs made
+pub fn from_str_sanitised<T: DeserializeOwned>(
+    xml: &str,
+    options: &SanitiseOptions,
+) -> Result<(T, Vec<SanitiseChange>), DeError> {
+    let sanitised = sanitise_xml(xml, options);
+    let value = quick_xml::de::from_str(&sanitised.xml)?;
+
+    Ok((value, sanitised.changes))
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+fn is_invalid_xml_char(c: char) -> bool {
+    (c < '\u{20}' && !matches!(c, '\t' | '\n' | '\r')) || matches!(c, '\u{FFFE}' | '\u{FFFF}')
+}
+
+fn is_attribute_value_end(next: Option<char>) -> bool {
+    next.is_none_or(|c| c.is_whitespace() || matches!(c, '>' | '/' | '?'))
+}
+
+// Does `s` start with an entity reference such as `&amp;` or a character reference such as `&#x27;`?
+fn starts_with_reference(s: &str) -> bool {
+    let Some(end) = s
+        .char_indices()
+        .take(MAX_REFERENCE_LEN)
+        .find(|(_, c)| *c == ';')
+        .map(|(idx, _)| idx)
+    else {
+        return false;
+    };
+    let name = &s[1..end];
+
+    match name.strip_prefix('#') {
+        Some(num) => match num.strip_prefix('x') {
+            Some(hex) => !hex.is_empty() && hex.chars().all(|c| c.is_ascii_hexdigit()),
+            None => !num.is_empty() && num.chars().all(|c| c.is_ascii_digit()),
+        },
+        None => {
+            name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+                && name.chars().all(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':'))
+        },
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Position {
+    Text,
+    Tag,
+    AttributeValue(char),
+}
+
+struct Sanitiser<'a, 'o> {
+    xml: &'a str,
+    options: &'o SanitiseOptions,
+    out: String,
+    changes: Vec<SanitiseChange>,
+}
+
+impl Sanitiser<'_, '_> {
+    fn record(&mut self, offset: usize, fix: SanitiseFix) {
+        self.changes.push(SanitiseChange { offset, fix });
+    }
+
+    fn run(&mut self) {
+        let xml = self.xml;
+        let mut position = Position::Text;
+        let mut idx = 0;
+
+        while let Some(c) = xml[idx..].chars().next() {
+            let rest = &xml[idx..];
+            let next_idx = idx + c.len_utf8();
+
+            if self.options.remove_invalid_chars && is_invalid_xml_char(c) {
+                self.record(idx, SanitiseFix::RemovedInvalidChar(c));
+                idx = next_idx;
+                continue;
+            }
+
+            match (position, c) {
+                (Position::Text, '<') => {
+                    if let Some(section_len) = VERBATIM_SECTIONS.iter().find_map(|(open, close)| {
+                        rest.starts_with(open)
+                            .then(|| rest[open.len()..].find(close).map(|end| open.len() + end + close.len()))
+                            .flatten()
+                    }) {
+                        self.out.push_str(&rest[..section_len]);
+                        idx += section_len;
+                        continue;
+                    }
+
+                    position = Position::Tag;
+                    self.out.push(c);
+                },
+                (Position::Tag, '>') => {
+                    position = Position::Text;
+                    self.out.push(c);
+                },
+                (Position::Tag, '"' | '\'') => {
+                    position = Position::AttributeValue(c);
+                    self.out.push(c);
+                },
+                (Position::AttributeValue(quote), _) if c == quote => {
+                    if is_attribute_value_end(xml[next_idx..].chars().next()) || !self.options.escape_attribute_quotes {
+                        position = Position::Tag;
+                        self.out.push(c);
+                    } else {
+                        self.record(idx, SanitiseFix::EscapedAttributeQuote(c));
+                        self.out.push_str(if c == '"' { "&quot;" } else { "&apos;" });
+                    }
+                },
+                (Position::Text | Position::AttributeValue(_), '&')
+                    if self.options.escape_bare_ampersands && !starts_with_reference(rest) =>
+                {
+                    self.record(idx, SanitiseFix::EscapedBareAmpersand);
+                    self.out.push_str("&amp;");
+                },
+                _ => self.out.push(c),
+            }
+
+            idx = next_idx;
+        }
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(feature = "parser")]
+#[cfg(test)]
+pub mod unit_tests;
@@ -0,0 +1,86 @@
+use serde::Deserialize;
+
+use super::*;
+use crate::test_utils::*;
+
+static XML_ENTRY_WITH_ETAG: &str = r#"<entry m:etag="W/"datetime'2024-05-01T10%3A00%3A00.0000000'"" xml:base="https://sap/opu/odata/sap/GWSAMPLE_BASIC/"/>"#;
+
+#[derive(Debug, Deserialize)]
+struct EntryEtag {
+    #[serde(rename = "@etag")]
+    etag: String,
+}
+
+fn fixes(sanitised: &Sanitised) -> Vec<SanitiseFix> {
+    sanitised.changes.iter().map(|change| change.fix).collect()
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_escape_quotes_in_etag_attribute() -> Result<(), String> {
+    let sanitised = sanitise_xml(XML_ENTRY_WITH_ETAG, &SanitiseOptions::default());
+
+    handle_test_comparison(
+        &sanitised.xml.to_string(),
+        &r#"<entry m:etag="W/&quot;datetime'2024-05-01T10%3A00%3A00.0000000'&quot;" xml:base="https://sap/opu/odata/sap/GWSAMPLE_BASIC/"/>"#.to_owned(),
+    )?;
+    handle_test_bool(fixes(&sanitised) == [SanitiseFix::EscapedAttributeQuote('"'); 2])?;
+    handle_test_comparison(
+        &sanitised.changes[0].to_string(),
+        &"Escaped \" within an attribute value at byte 17".to_owned(),
+    )
+}
+
+#[test]
+pub fn should_escape_bare_ampersands_only() -> Result<(), String> {
+    let xml = r#"<d:Name title="R&D">Q&A &amp; &#39;FAQ&#x27; &unknown</d:Name>"#;
+    let sanitised = sanitise_xml(xml, &SanitiseOptions::default());
+
+    handle_test_comparison(
+        &sanitised.xml.to_string(),
+        &r#"<d:Name title="R&amp;D">Q&amp;A &amp; &#39;FAQ&#x27; &amp;unknown</d:Name>"#.to_owned(),
+    )?;
+    handle_test_comparison(&sanitised.changes.len(), &3)
+}
+
+#[test]
+pub fn should_remove_invalid_chars() -> Result<(), String> {
+    let sanitised = sanitise_xml("<d:Name>Note\u{1}book\tBasic</d:Name>", &SanitiseOptions::default());
+
+    handle_test_comparison(&sanitised.xml.to_string(), &"<d:Name>Notebook\tBasic</d:Name>".to_owned())?;
+    handle_test_bool(fixes(&sanitised) == [SanitiseFix::RemovedInvalidChar('\u{1}')])
+}
+
+#[test]
+pub fn should_borrow_valid_xml() -> Result<(), String> {
+    let xml = r#"<?xml version="1.0"?><!-- R&D "notes" --><d:Name a="x" b=''><![CDATA[Q&A]]>&lt;Basic&gt;</d:Name>"#;
+    let sanitised = sanitise_xml(xml, &SanitiseOptions::default());
+
+    handle_test_bool(matches!(sanitised.xml, Cow::Borrowed(_)))?;
+    handle_test_bool(sanitised.changes.is_empty())
+}
+
+#[test]
+pub fn should_only_apply_selected_fixes() -> Result<(), String> {
+    let options = SanitiseOptions {
+        escape_attribute_quotes: false,
+        ..Default::default()
+    };
+    let sanitised = sanitise_xml(XML_ENTRY_WITH_ETAG, &options);
+
+    handle_test_bool(matches!(sanitised.xml, Cow::Borrowed(_)))
+}
+
+#[test]
+pub fn should_deserialize_sanitised_xml() -> Result<(), String> {
+    handle_test_bool(matches!(
+        quick_xml::de::from_str::<EntryEtag>(XML_ENTRY_WITH_ETAG),
+        Err(DeError::InvalidXml(_))
+    ))?;
+
+    let (entry, changes) = from_str_sanitised::<EntryEtag>(XML_ENTRY_WITH_ETAG, &SanitiseOptions::default())
+        .map_err(|err| err.to_string())?;
+
+    handle_test_comparison(&entry.etag, &r#"W/"datetime'2024-05-01T10%3A00%3A00.0000000'""#.to_owned())?;
+    handle_test_comparison(&changes.len(), &2)
+}
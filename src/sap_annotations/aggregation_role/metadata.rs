@@ -1,34 +1,25 @@
-use super::SAPAggregationRoleProperty;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
 
-use crate::{
-    parser::generate::{
-        gen_some_value,
-        syntax_fragments::{COLON2, DOUBLE_QUOTE, NONE},
-    },
-    sap_annotations::{AnnotationType, OptionalAnnotationType},
-};
+use super::SAPAggregationRoleProperty;
 
-static MY_NAME: &[u8] = "SAPAggregationRoleProperty".as_bytes();
+use crate::{parser::generate::gen_ident, sap_annotations::AnnotationType};
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 impl AnnotationType for SAPAggregationRoleProperty {
-    fn member_name(&self) -> &'static [u8] {
+    fn member_name(&self) -> &'static str {
         match self {
-            SAPAggregationRoleProperty::Dimension => b"Dimension",
-            SAPAggregationRoleProperty::Measure => b"Measure",
-            SAPAggregationRoleProperty::TotalPropertiesList => b"TotalPropertiesList",
+            SAPAggregationRoleProperty::Dimension => "Dimension",
+            SAPAggregationRoleProperty::Measure => "Measure",
+            SAPAggregationRoleProperty::TotalPropertiesList => "TotalPropertiesList",
         }
     }
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-impl OptionalAnnotationType for Option<SAPAggregationRoleProperty> {
-    fn opt_anno_type<T: AnnotationType>(&self, opt_self: &Option<T>) -> Vec<u8> {
-        if let Some(anno_type) = opt_self {
-            let fq_name = [DOUBLE_QUOTE, MY_NAME, COLON2, anno_type.member_name(), DOUBLE_QUOTE].concat();
-            gen_some_value(&fq_name)
-        } else {
-            NONE.to_vec()
-        }
+impl ToTokens for SAPAggregationRoleProperty {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let member = gen_ident(self.member_name());
+        tokens.extend(quote!(SAPAggregationRoleProperty::#member))
     }
 }
@@ -1,34 +1,25 @@
-use super::SAPFilterRestrictionProperty;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
 
-use crate::{
-    parser::generate::{
-        gen_some_value,
-        syntax_fragments::{COLON2, DOUBLE_QUOTE, NONE},
-    },
-    sap_annotations::{AnnotationType, OptionalAnnotationType},
-};
+use super::SAPFilterRestrictionProperty;
 
-static MY_NAME: &[u8] = "SAPFilterRestrictionProperty".as_bytes();
+use crate::{parser::generate::gen_ident, sap_annotations::AnnotationType};
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 impl AnnotationType for SAPFilterRestrictionProperty {
-    fn member_name(&self) -> &'static [u8] {
+    fn member_name(&self) -> &'static str {
         match self {
-            SAPFilterRestrictionProperty::SingleValue => b"SingleValue",
-            SAPFilterRestrictionProperty::MultiValue => b"MultiValue",
-            SAPFilterRestrictionProperty::Interval => b"Interval",
+            SAPFilterRestrictionProperty::SingleValue => "SingleValue",
+            SAPFilterRestrictionProperty::MultiValue => "MultiValue",
+            SAPFilterRestrictionProperty::Interval => "Interval",
         }
     }
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-impl OptionalAnnotationType for Option<SAPFilterRestrictionProperty> {
-    fn opt_anno_type<T: AnnotationType>(&self, opt_self: &Option<T>) -> Vec<u8> {
-        if let Some(anno_type) = opt_self {
-            let fq_name = [DOUBLE_QUOTE, MY_NAME, COLON2, anno_type.member_name(), DOUBLE_QUOTE].concat();
-            gen_some_value(&fq_name)
-        } else {
-            NONE.to_vec()
-        }
+impl ToTokens for SAPFilterRestrictionProperty {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let member = gen_ident(self.member_name());
+        tokens.extend(quote!(SAPFilterRestrictionProperty::#member))
     }
 }
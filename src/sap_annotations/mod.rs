@@ -16,11 +16,7 @@ pub mod schema;
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 pub trait AnnotationType {
-    fn member_name(&self) -> &'static [u8];
-}
-
-pub trait OptionalAnnotationType {
-    fn opt_anno_type<T: AnnotationType>(&self, opt_self: &Option<T>) -> Vec<u8>;
+    fn member_name(&self) -> &'static str;
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
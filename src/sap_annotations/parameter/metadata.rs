@@ -1,33 +1,24 @@
-use super::SAPParameterProperty;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
 
-use crate::{
-    parser::generate::{
-        gen_some_value,
-        syntax_fragments::{COLON2, DOUBLE_QUOTE, NONE},
-    },
-    sap_annotations::{AnnotationType, OptionalAnnotationType},
-};
+use super::SAPParameterProperty;
 
-static MY_NAME: &[u8] = "SAPParameterProperty".as_bytes();
+use crate::{parser::generate::gen_ident, sap_annotations::AnnotationType};
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 impl AnnotationType for SAPParameterProperty {
-    fn member_name(&self) -> &'static [u8] {
+    fn member_name(&self) -> &'static str {
         match self {
-            SAPParameterProperty::Mandatory => b"Mandatory",
-            SAPParameterProperty::Optional => b"Optional",
+            SAPParameterProperty::Mandatory => "Mandatory",
+            SAPParameterProperty::Optional => "Optional",
         }
     }
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-impl OptionalAnnotationType for Option<SAPParameterProperty> {
-    fn opt_anno_type<T: AnnotationType>(&self, opt_self: &Option<T>) -> Vec<u8> {
-        if let Some(anno_type) = opt_self {
-            let fq_name = [DOUBLE_QUOTE, MY_NAME, COLON2, anno_type.member_name(), DOUBLE_QUOTE].concat();
-            gen_some_value(&fq_name)
-        } else {
-            NONE.to_vec()
-        }
+impl ToTokens for SAPParameterProperty {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let member = gen_ident(self.member_name());
+        tokens.extend(quote!(SAPParameterProperty::#member))
     }
 }
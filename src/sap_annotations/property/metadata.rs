@@ -1,114 +1,15 @@
-use std::fmt::Formatter;
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
 
 use super::SAPAnnotationsProperty;
 
-use crate::{
-    parser::generate::{gen_bool_string, gen_opt_string, syntax_fragments::*},
-    sap_annotations::OptionalAnnotationType,
-    sap_semantics::OptionalSemanticType,
-};
-
-static MY_NAME: &[u8] = "SAPAnnotationsProperty".as_bytes();
-
-// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-enum SAPAnnotationsPropertyFieldNames {
-    Label,
-    Heading,
-    QuickInfo,
-    IsUnicode,
-    Semantics,
-    IsCreatable,
-    IsUpdatable,
-    IsSortable,
-    IsFilterable,
-    IsAddressable,
-    IsRequiredInFilter,
-    FilterRestriction,
-    FilterFor,
-    Text,
-    TextFor,
-    Unit,
-    Precision,
-    IsVisible,
-    FieldControl,
-    ValidationRegexp,
-    DisplayFormat,
-    ValueList,
-    LowerBoundary,
-    UpperBoundary,
-    AggregationRole,
-    SuperOrdinate,
-    AttributeFor,
-    HierarchyNodeFor,
-    HierarchyNodeExternalKeyFor,
-    HierarchyLevelFor,
-    HierarchyParentNodeFor,
-    HierarchyParentNavigationFor,
-    HierarchyDrillStateFor,
-    HierarchyNodeDescendantCountFor,
-    HierarchyPreorderRankFor,
-    HierarchySiblingRankFor,
-    Parameter,
-    IsAnnotation,
-    UpdatablePath,
-    PreserveFlagFor,
-    HasVariableScale,
-}
-
-// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-impl SAPAnnotationsPropertyFieldNames {
-    pub fn value(prop_name: SAPAnnotationsPropertyFieldNames) -> &'static [u8] {
-        match prop_name {
-            SAPAnnotationsPropertyFieldNames::Label => b"label",
-            SAPAnnotationsPropertyFieldNames::Heading => b"heading",
-            SAPAnnotationsPropertyFieldNames::QuickInfo => b"quick_info",
-            SAPAnnotationsPropertyFieldNames::IsUnicode => b"is_unicode",
-            SAPAnnotationsPropertyFieldNames::Semantics => b"semantics",
-            SAPAnnotationsPropertyFieldNames::IsCreatable => b"is_creatable",
-            SAPAnnotationsPropertyFieldNames::IsUpdatable => b"is_updatable",
-            SAPAnnotationsPropertyFieldNames::IsSortable => b"is_sortable",
-            SAPAnnotationsPropertyFieldNames::IsFilterable => b"is_filterable",
-            SAPAnnotationsPropertyFieldNames::IsAddressable => b"is_addressable",
-            SAPAnnotationsPropertyFieldNames::IsRequiredInFilter => b"is_required_in_filter",
-            SAPAnnotationsPropertyFieldNames::FilterRestriction => b"filter_restriction",
-            SAPAnnotationsPropertyFieldNames::FilterFor => b"filter_for",
-            SAPAnnotationsPropertyFieldNames::Text => b"text",
-            SAPAnnotationsPropertyFieldNames::TextFor => b"text_for",
-            SAPAnnotationsPropertyFieldNames::Unit => b"unit",
-            SAPAnnotationsPropertyFieldNames::Precision => b"precision",
-            SAPAnnotationsPropertyFieldNames::IsVisible => b"is_visible",
-            SAPAnnotationsPropertyFieldNames::FieldControl => b"field_control",
-            SAPAnnotationsPropertyFieldNames::ValidationRegexp => b"validation_regexp",
-            SAPAnnotationsPropertyFieldNames::DisplayFormat => b"display_format",
-            SAPAnnotationsPropertyFieldNames::ValueList => b"value_list",
-            SAPAnnotationsPropertyFieldNames::LowerBoundary => b"lower_boundary",
-            SAPAnnotationsPropertyFieldNames::UpperBoundary => b"upper_boundary",
-            SAPAnnotationsPropertyFieldNames::AggregationRole => b"aggregation_role",
-            SAPAnnotationsPropertyFieldNames::SuperOrdinate => b"super_ordinate",
-            SAPAnnotationsPropertyFieldNames::AttributeFor => b"attribute_for",
-            SAPAnnotationsPropertyFieldNames::HierarchyNodeFor => b"hierarchy_node_for",
-            SAPAnnotationsPropertyFieldNames::HierarchyNodeExternalKeyFor => b"hierarchy_node_external_key_for",
-            SAPAnnotationsPropertyFieldNames::HierarchyLevelFor => b"hierarchy_level_for",
-            SAPAnnotationsPropertyFieldNames::HierarchyParentNodeFor => b"hierarchy_parent_node_for",
-            SAPAnnotationsPropertyFieldNames::HierarchyParentNavigationFor => b"hierarchy_parent_navigation_for",
-            SAPAnnotationsPropertyFieldNames::HierarchyDrillStateFor => b"hierarchy_drill_state_for",
-            SAPAnnotationsPropertyFieldNames::HierarchyNodeDescendantCountFor => b"hierarchy_node_descendant_count_for",
-            SAPAnnotationsPropertyFieldNames::HierarchyPreorderRankFor => b"hierarchy_preorder_rank_for",
-            SAPAnnotationsPropertyFieldNames::HierarchySiblingRankFor => b"hierarchy_sibling_rank_for",
-            SAPAnnotationsPropertyFieldNames::Parameter => b"parameter",
-            SAPAnnotationsPropertyFieldNames::IsAnnotation => b"is_annotation",
-            SAPAnnotationsPropertyFieldNames::UpdatablePath => b"updatable_path",
-            SAPAnnotationsPropertyFieldNames::PreserveFlagFor => b"preserve_flag_for",
-            SAPAnnotationsPropertyFieldNames::HasVariableScale => b"has_variable_scale",
-        }
-    }
-}
+use crate::parser::generate::{gen_opt_string, gen_opt_value, syntax_fragments::*};
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 impl SAPAnnotationsProperty {
     // Check for any used subtypes
-    pub fn used_subtypes(&self) -> Vec<&[u8]> {
-        let mut subtypes: Vec<&[u8]> = vec![];
+    pub fn used_subtypes(&self) -> Vec<&'static str> {
+        let mut subtypes: Vec<&'static str> = vec![];
 
         if self.semantics.is_some() {
             subtypes.push(PATH_TO_SAP_SEMANTICS_PROPERTY)
@@ -134,61 +35,94 @@ impl SAPAnnotationsProperty {
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-fn line_into(out: &mut Vec<u8>, prop_md: SAPAnnotationsPropertyFieldNames, val: Vec<u8>) {
-    out.extend_from_slice(SAPAnnotationsPropertyFieldNames::value(prop_md));
-    out.extend_from_slice(COLON);
-    out.extend_from_slice(&val);
-    out.extend_from_slice(COMMA);
-    out.extend_from_slice(LINE_FEED);
-}
+impl ToTokens for SAPAnnotationsProperty {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let label = gen_opt_string(&self.label);
+        let heading = gen_opt_string(&self.heading);
+        let quick_info = gen_opt_string(&self.quick_info);
+        let is_unicode = self.is_unicode;
+        let semantics = gen_opt_value(&self.semantics);
+        let is_creatable = self.is_creatable;
+        let is_updatable = self.is_updatable;
+        let is_sortable = self.is_sortable;
+        let is_filterable = self.is_filterable;
+        let is_addressable = self.is_addressable;
+        let is_required_in_filter = self.is_required_in_filter;
+        let filter_restriction = gen_opt_value(&self.filter_restriction);
+        let filter_for = gen_opt_string(&self.filter_for);
+        let text = gen_opt_string(&self.text);
+        let text_for = gen_opt_string(&self.text_for);
+        let unit = gen_opt_string(&self.unit);
+        let precision = gen_opt_string(&self.precision);
+        let is_visible = self.is_visible;
+        let field_control = gen_opt_value(&self.field_control);
+        let validation_regexp = gen_opt_string(&self.validation_regexp);
+        let display_format = gen_opt_value(&self.display_format);
+        let value_list = gen_opt_string(&self.value_list);
+        let lower_boundary = gen_opt_string(&self.lower_boundary);
+        let upper_boundary = gen_opt_string(&self.upper_boundary);
+        let aggregation_role = gen_opt_value(&self.aggregation_role);
+        let super_ordinate = gen_opt_string(&self.super_ordinate);
+        let attribute_for = gen_opt_string(&self.attribute_for);
+        let hierarchy_node_for = gen_opt_string(&self.hierarchy_node_for);
+        let hierarchy_node_external_key_for = gen_opt_string(&self.hierarchy_node_external_key_for);
+        let hierarchy_level_for = gen_opt_string(&self.hierarchy_level_for);
+        let hierarchy_parent_node_for = gen_opt_string(&self.hierarchy_parent_node_for);
+        let hierarchy_parent_navigation_for = gen_opt_string(&self.hierarchy_parent_navigation_for);
+        let hierarchy_drill_state_for = gen_opt_string(&self.hierarchy_drill_state_for);
+        let hierarchy_node_descendant_count_for = gen_opt_string(&self.hierarchy_node_descendant_count_for);
+        let hierarchy_preorder_rank_for = gen_opt_string(&self.hierarchy_preorder_rank_for);
+        let hierarchy_sibling_rank_for = gen_opt_string(&self.hierarchy_sibling_rank_for);
+        let parameter = gen_opt_value(&self.parameter);
+        let is_annotation = self.is_annotation;
+        let updatable_path = gen_opt_string(&self.updatable_path);
+        let preserve_flag_for = gen_opt_string(&self.preserve_flag_for);
+        let has_variable_scale = self.has_variable_scale;
 
-impl std::fmt::Display for SAPAnnotationsProperty {
-    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let mut out_buffer: Vec<u8> = Vec::new();
-        out_buffer.extend_from_slice(MY_NAME);
-        out_buffer.extend_from_slice(OPEN_CURLY);
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::Label, gen_opt_string(&self.label));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::Heading, gen_opt_string(&self.heading));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::QuickInfo, gen_opt_string(&self.quick_info));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::IsUnicode, gen_bool_string(self.is_unicode));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::Semantics, self.semantics.opt_sem_type(&self.semantics));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::IsCreatable, gen_bool_string(self.is_creatable));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::IsUpdatable, gen_bool_string(self.is_updatable));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::IsSortable, gen_bool_string(self.is_sortable));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::IsFilterable, gen_bool_string(self.is_filterable));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::IsAddressable, gen_bool_string(self.is_addressable));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::IsRequiredInFilter, gen_bool_string(self.is_required_in_filter));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::FilterRestriction, self.filter_restriction.opt_anno_type(&self.filter_restriction));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::FilterFor, gen_opt_string(&self.filter_for));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::Text, gen_opt_string(&self.text));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::TextFor, gen_opt_string(&self.text_for));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::Unit, gen_opt_string(&self.unit));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::Precision, gen_opt_string(&self.precision));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::IsVisible, gen_bool_string(self.is_visible));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::FieldControl, self.field_control.opt_anno_type(&self.field_control));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::ValidationRegexp, gen_opt_string(&self.validation_regexp));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::DisplayFormat, self.display_format.opt_anno_type(&self.display_format));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::ValueList, gen_opt_string(&self.value_list));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::LowerBoundary, gen_opt_string(&self.lower_boundary));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::UpperBoundary, gen_opt_string(&self.upper_boundary));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::AggregationRole, self.aggregation_role.opt_anno_type(&self.aggregation_role));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::SuperOrdinate, gen_opt_string(&self.super_ordinate));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::AttributeFor, gen_opt_string(&self.attribute_for));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::HierarchyNodeFor, gen_opt_string(&self.hierarchy_node_for));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::HierarchyNodeExternalKeyFor, gen_opt_string(&self.hierarchy_node_external_key_for));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::HierarchyLevelFor, gen_opt_string(&self.hierarchy_level_for));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::HierarchyParentNodeFor, gen_opt_string(&self.hierarchy_parent_node_for));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::HierarchyParentNavigationFor, gen_opt_string(&self.hierarchy_parent_navigation_for));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::HierarchyDrillStateFor, gen_opt_string(&self.hierarchy_drill_state_for));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::HierarchyNodeDescendantCountFor, gen_opt_string(&self.hierarchy_node_descendant_count_for));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::HierarchyPreorderRankFor, gen_opt_string(&self.hierarchy_preorder_rank_for));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::HierarchySiblingRankFor, gen_opt_string(&self.hierarchy_sibling_rank_for));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::Parameter, self.parameter.opt_anno_type(&self.parameter));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::IsAnnotation, gen_bool_string(self.is_annotation));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::UpdatablePath, gen_opt_string(&self.updatable_path));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::PreserveFlagFor, gen_opt_string(&self.preserve_flag_for));
-        line_into(&mut out_buffer, SAPAnnotationsPropertyFieldNames::HasVariableScale, gen_bool_string(self.has_variable_scale));
-        out_buffer.extend_from_slice(CLOSE_CURLY);
-        write!(f, "{}", String::from_utf8(out_buffer).unwrap())
+        tokens.extend(quote! {
+            SAPAnnotationsProperty {
+                label: #label,
+                heading: #heading,
+                quick_info: #quick_info,
+                is_unicode: #is_unicode,
+                semantics: #semantics,
+                is_creatable: #is_creatable,
+                is_updatable: #is_updatable,
+                is_sortable: #is_sortable,
+                is_filterable: #is_filterable,
+                is_addressable: #is_addressable,
+                is_required_in_filter: #is_required_in_filter,
+                filter_restriction: #filter_restriction,
+                filter_for: #filter_for,
+                text: #text,
+                text_for: #text_for,
+                unit: #unit,
+                precision: #precision,
+                is_visible: #is_visible,
+                field_control: #field_control,
+                validation_regexp: #validation_regexp,
+                display_format: #display_format,
+                value_list: #value_list,
+                lower_boundary: #lower_boundary,
+                upper_boundary: #upper_boundary,
+                aggregation_role: #aggregation_role,
+                super_ordinate: #super_ordinate,
+                attribute_for: #attribute_for,
+                hierarchy_node_for: #hierarchy_node_for,
+                hierarchy_node_external_key_for: #hierarchy_node_external_key_for,
+                hierarchy_level_for: #hierarchy_level_for,
+                hierarchy_parent_node_for: #hierarchy_parent_node_for,
+                hierarchy_parent_navigation_for: #hierarchy_parent_navigation_for,
+                hierarchy_drill_state_for: #hierarchy_drill_state_for,
+                hierarchy_node_descendant_count_for: #hierarchy_node_descendant_count_for,
+                hierarchy_preorder_rank_for: #hierarchy_preorder_rank_for,
+                hierarchy_sibling_rank_for: #hierarchy_sibling_rank_for,
+                parameter: #parameter,
+                is_annotation: #is_annotation,
+                updatable_path: #updatable_path,
+                preserve_flag_for: #preserve_flag_for,
+                has_variable_scale: #has_variable_scale,
+            }
+        })
     }
 }
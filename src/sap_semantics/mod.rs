@@ -4,9 +4,5 @@ pub mod property;
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 pub trait SemanticType {
-    fn member_name(&self) -> &'static [u8];
-}
-
-pub trait OptionalSemanticType {
-    fn opt_sem_type<T: SemanticType>(&self, opt_self: &Option<T>) -> Vec<u8>;
+    fn member_name(&self) -> &'static str;
 }
@@ -1,85 +1,83 @@
+use proc_macro2::TokenStream;
+use quote::{quote, ToTokens};
+
 use crate::{
-    parser::generate::syntax_fragments::{CLOSE_PAREN, COLON2, NONE, OPEN_PAREN, SOME},
-    sap_semantics::{property::SAPSemanticsProperty, OptionalSemanticType, SemanticType},
+    parser::generate::gen_ident,
+    sap_semantics::{property::SAPSemanticsProperty, SemanticType},
 };
 
-static MY_NAME: &[u8] = "SAPSemanticsProperty".as_bytes();
-
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 impl SemanticType for SAPSemanticsProperty {
-    fn member_name(&self) -> &'static [u8] {
+    fn member_name(&self) -> &'static str {
         match self {
-            SAPSemanticsProperty::TelephoneNumber => b"TelephoneNumber",
-            SAPSemanticsProperty::WorkCellphoneNumber => b"WorkCellphoneNumber",
-            SAPSemanticsProperty::FaxNumber => b"FaxNumber",
-            SAPSemanticsProperty::EmailAddress => b"EmailAddress",
-            SAPSemanticsProperty::PreferredEmailAddress => b"PreferredEmailAddress",
-            SAPSemanticsProperty::URL => b"URL",
-            SAPSemanticsProperty::Fullname => b"Fullname",
-            SAPSemanticsProperty::FirstOrGivenName => b"FirstOrGivenName",
-            SAPSemanticsProperty::MiddleName => b"MiddleName",
-            SAPSemanticsProperty::LastName => b"LastName",
-            SAPSemanticsProperty::Nickname => b"Nickname",
-            SAPSemanticsProperty::Title => b"Title",
-            SAPSemanticsProperty::NameSuffix => b"NameSuffix",
-            SAPSemanticsProperty::VCardNotes => b"VCardNotes",
-            SAPSemanticsProperty::PhotoURL => b"PhotoURL",
-            SAPSemanticsProperty::City => b"City",
-            SAPSemanticsProperty::Street => b"Street",
-            SAPSemanticsProperty::Country => b"Country",
-            SAPSemanticsProperty::Region => b"Region",
-            SAPSemanticsProperty::PostalCode => b"PostalCode",
-            SAPSemanticsProperty::PostOfficeBox => b"PostOfficeBox",
-            SAPSemanticsProperty::OrganizationName => b"OrganizationName",
-            SAPSemanticsProperty::OrganizationalUnit => b"OrganizationalUnit",
-            SAPSemanticsProperty::OrganizationalRole => b"OrganizationalRole",
-            SAPSemanticsProperty::JobTitle => b"JobTitle",
-            SAPSemanticsProperty::DateOfBirth => b"DateOfBirth",
-            SAPSemanticsProperty::CalendarComponentSummary => b"CalendarComponentSummary",
-            SAPSemanticsProperty::CalendarComponentDescription => b"CalendarComponentDescription",
-            SAPSemanticsProperty::CalendarComponentCategories => b"CalendarComponentCategories",
-            SAPSemanticsProperty::CalendarComponentStartDateTime => b"CalendarComponentStartDateTime",
-            SAPSemanticsProperty::CalendarComponentEndDateTime => b"CalendarComponentEndDateTime",
-            SAPSemanticsProperty::CalendarComponentDuration => b"CalendarComponentDuration",
-            SAPSemanticsProperty::ToDoDueDateTime => b"ToDoDueDateTime",
-            SAPSemanticsProperty::ToDoCompletedDateTime => b"ToDoCompletedDateTime",
-            SAPSemanticsProperty::CalendarComponentPriority => b"CalendarComponentPriority",
-            SAPSemanticsProperty::CalendarComponentAccessClassification => b"CalendarComponentAccessClassification",
-            SAPSemanticsProperty::CalendarComponentStatus => b"CalendarComponentStatus",
-            SAPSemanticsProperty::ToDoPercentComplete => b"ToDoPercentComplete",
-            SAPSemanticsProperty::CalendarComponentContact => b"CalendarComponentContact",
-            SAPSemanticsProperty::CalendarComponentVenue => b"CalendarComponentVenue",
-            SAPSemanticsProperty::TransparentEvent => b"TransparentEvent",
-            SAPSemanticsProperty::CalendarComponentFreeBusyTime => b"CalendarComponentFreeBusyTime",
-            SAPSemanticsProperty::CalendarComponentOccupiesWholeDay => b"CalendarComponentOccupiesWholeDay",
-            SAPSemanticsProperty::CalendarComponentYear => b"CalendarComponentYear",
-            SAPSemanticsProperty::CalendarComponentYearMonth => b"CalendarComponentYearMonth",
-            SAPSemanticsProperty::CalendarComponentYearMonthDay => b"CalendarComponentYearMonthDay",
-            SAPSemanticsProperty::EmailFrom => b"EmailFrom",
-            SAPSemanticsProperty::EmailSender => b"EmailSender",
-            SAPSemanticsProperty::EmailToList => b"EmailToList",
-            SAPSemanticsProperty::EmailCCList => b"EmailCCList",
-            SAPSemanticsProperty::EmailBCCList => b"EmailBCCList",
-            SAPSemanticsProperty::EmailSubject => b"EmailSubject",
-            SAPSemanticsProperty::EmailBody => b"EmailBody",
-            SAPSemanticsProperty::EmailKeywordList => b"EmailKeywordList",
-            SAPSemanticsProperty::EmailDateTimeReceived => b"EmailDateTimeReceived",
-            SAPSemanticsProperty::GeolocationLongitude => b"GeolocationLongitude",
-            SAPSemanticsProperty::GeolocationLatitude => b"GeolocationLatitude",
-            SAPSemanticsProperty::CurrencyCode => b"CurrencyCode",
-            SAPSemanticsProperty::UnitOfMeasure => b"UnitOfMeasure",
-            SAPSemanticsProperty::Count => b"Count",
+            SAPSemanticsProperty::TelephoneNumber => "TelephoneNumber",
+            SAPSemanticsProperty::WorkCellphoneNumber => "WorkCellphoneNumber",
+            SAPSemanticsProperty::FaxNumber => "FaxNumber",
+            SAPSemanticsProperty::EmailAddress => "EmailAddress",
+            SAPSemanticsProperty::PreferredEmailAddress => "PreferredEmailAddress",
+            SAPSemanticsProperty::URL => "URL",
+            SAPSemanticsProperty::Fullname => "Fullname",
+            SAPSemanticsProperty::FirstOrGivenName => "FirstOrGivenName",
+            SAPSemanticsProperty::MiddleName => "MiddleName",
+            SAPSemanticsProperty::LastName => "LastName",
+            SAPSemanticsProperty::Nickname => "Nickname",
+            SAPSemanticsProperty::Title => "Title",
+            SAPSemanticsProperty::NameSuffix => "NameSuffix",
+            SAPSemanticsProperty::VCardNotes => "VCardNotes",
+            SAPSemanticsProperty::PhotoURL => "PhotoURL",
+            SAPSemanticsProperty::City => "City",
+            SAPSemanticsProperty::Street => "Street",
+            SAPSemanticsProperty::Country => "Country",
+            SAPSemanticsProperty::Region => "Region",
+            SAPSemanticsProperty::PostalCode => "PostalCode",
+            SAPSemanticsProperty::PostOfficeBox => "PostOfficeBox",
+            SAPSemanticsProperty::OrganizationName => "OrganizationName",
+            SAPSemanticsProperty::OrganizationalUnit => "OrganizationalUnit",
+            SAPSemanticsProperty::OrganizationalRole => "OrganizationalRole",
+            SAPSemanticsProperty::JobTitle => "JobTitle",
+            SAPSemanticsProperty::DateOfBirth => "DateOfBirth",
+            SAPSemanticsProperty::CalendarComponentSummary => "CalendarComponentSummary",
+            SAPSemanticsProperty::CalendarComponentDescription => "CalendarComponentDescription",
+            SAPSemanticsProperty::CalendarComponentCategories => "CalendarComponentCategories",
+            SAPSemanticsProperty::CalendarComponentStartDateTime => "CalendarComponentStartDateTime",
+            SAPSemanticsProperty::CalendarComponentEndDateTime => "CalendarComponentEndDateTime",
+            SAPSemanticsProperty::CalendarComponentDuration => "CalendarComponentDuration",
+            SAPSemanticsProperty::ToDoDueDateTime => "ToDoDueDateTime",
+            SAPSemanticsProperty::ToDoCompletedDateTime => "ToDoCompletedDateTime",
+            SAPSemanticsProperty::CalendarComponentPriority => "CalendarComponentPriority",
+            SAPSemanticsProperty::CalendarComponentAccessClassification => "CalendarComponentAccessClassification",
+            SAPSemanticsProperty::CalendarComponentStatus => "CalendarComponentStatus",
+            SAPSemanticsProperty::ToDoPercentComplete => "ToDoPercentComplete",
+            SAPSemanticsProperty::CalendarComponentContact => "CalendarComponentContact",
+            SAPSemanticsProperty::CalendarComponentVenue => "CalendarComponentVenue",
+            SAPSemanticsProperty::TransparentEvent => "TransparentEvent",
+            SAPSemanticsProperty::CalendarComponentFreeBusyTime => "CalendarComponentFreeBusyTime",
+            SAPSemanticsProperty::CalendarComponentOccupiesWholeDay => "CalendarComponentOccupiesWholeDay",
+            SAPSemanticsProperty::CalendarComponentYear => "CalendarComponentYear",
+            SAPSemanticsProperty::CalendarComponentYearMonth => "CalendarComponentYearMonth",
+            SAPSemanticsProperty::CalendarComponentYearMonthDay => "CalendarComponentYearMonthDay",
+            SAPSemanticsProperty::EmailFrom => "EmailFrom",
+            SAPSemanticsProperty::EmailSender => "EmailSender",
+            SAPSemanticsProperty::EmailToList => "EmailToList",
+            SAPSemanticsProperty::EmailCCList => "EmailCCList",
+            SAPSemanticsProperty::EmailBCCList => "EmailBCCList",
+            SAPSemanticsProperty::EmailSubject => "EmailSubject",
+            SAPSemanticsProperty::EmailBody => "EmailBody",
+            SAPSemanticsProperty::EmailKeywordList => "EmailKeywordList",
+            SAPSemanticsProperty::EmailDateTimeReceived => "EmailDateTimeReceived",
+            SAPSemanticsProperty::GeolocationLongitude => "GeolocationLongitude",
+            SAPSemanticsProperty::GeolocationLatitude => "GeolocationLatitude",
+            SAPSemanticsProperty::CurrencyCode => "CurrencyCode",
+            SAPSemanticsProperty::UnitOfMeasure => "UnitOfMeasure",
+            SAPSemanticsProperty::Count => "Count",
         }
     }
 }
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
-impl OptionalSemanticType for Option<SAPSemanticsProperty> {
-    fn opt_sem_type<T: SemanticType>(&self, opt_self: &Option<T>) -> Vec<u8> {
-        if let Some(anno_type) = opt_self {
-            [SOME, OPEN_PAREN, MY_NAME, COLON2, anno_type.member_name(), CLOSE_PAREN].concat()
-        } else {
-            NONE.to_vec()
-        }
+impl ToTokens for SAPSemanticsProperty {
+    fn to_tokens(&self, tokens: &mut TokenStream) {
+        let member = gen_ident(self.member_name());
+        tokens.extend(quote!(SAPSemanticsProperty::#member))
     }
 }
@@ -0,0 +1,80 @@
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// The SQL dialects for which DDL can be generated
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SqlDialect {
+    #[default]
+    Postgres,
+    Sqlite,
+}
+
+impl SqlDialect {
+    /// The placeholder for the bind parameter at position `idx` (counting from 1)
+    pub fn placeholder(&self, idx: usize) -> String {
+        match self {
+            SqlDialect::Postgres => format!("${idx}"),
+            SqlDialect::Sqlite => format!("?{idx}"),
+        }
+    }
+}
+
+/// Quote an SQL identifier
+pub fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// A value that can be bound to a parameter of an SQL statement, independently of any particular database driver.
+///
+/// Values without a direct SQL equivalent are converted to text:
+/// * `Edm.Decimal` values keep their exact decimal representation
+/// * `Edm.DateTime` and `Edm.DateTimeOffset` values use the ISO 8601 format `YYYY-MM-DDThh:mm:ss.fff`
+/// * `Edm.Time` values use the format `hh:mm:ss`
+/// * `Edm.Guid` values use the hyphenated format
+#[derive(Clone, Debug, PartialEq)]
+pub enum SqlValue {
+    Null,
+    Boolean(bool),
+    Integer(i64),
+    Real(f64),
+    Text(String),
+    Blob(Vec<u8>),
+}
+
+/// The columns to which a service document struct is mapped.
+///
+/// Implemented by the generated entity type and complex type structs.
+/// The properties of a complex type are flattened into the columns of the table that contains it, so the column names
+/// of a complex type are prefixed by the name of the property that holds it
+pub trait SqlColumns {
+    /// The column names, each preceded by `prefix`
+    fn sql_column_names(prefix: &str) -> Vec<String>;
+
+    /// The column values in the same order as the column names
+    fn to_sql_values(&self) -> Vec<SqlValue>;
+}
+
+/// A service document struct that is mapped to a row in an SQL table.
+///
+/// Implemented by the generated entity type structs
+pub trait SqlRow: SqlColumns {
+    const SQL_TABLE_NAME: &'static str;
+
+    /// An `INSERT` statement with one bind parameter per column, in the same order as [`SqlColumns::to_sql_values`]
+    fn sql_insert_statement(dialect: SqlDialect) -> String {
+        let column_names = Self::sql_column_names("");
+        let columns: Vec<String> = column_names.iter().map(|name| quote_identifier(name)).collect();
+        let placeholders: Vec<String> = (1..=column_names.len()).map(|idx| dialect.placeholder(idx)).collect();
+
+        format!(
+            "INSERT INTO {} ({}) VALUES ({})",
+            quote_identifier(Self::SQL_TABLE_NAME),
+            columns.join(", "),
+            placeholders.join(", ")
+        )
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(feature = "parser")]
+#[cfg(test)]
+pub mod unit_tests;
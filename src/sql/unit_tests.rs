@@ -0,0 +1,66 @@
+use super::*;
+use crate::test_utils::*;
+
+// Hand-written equivalents of the implementations generated in a service document
+struct CtAddress {
+    city: Option<String>,
+}
+
+impl SqlColumns for CtAddress {
+    fn sql_column_names(prefix: &str) -> Vec<String> {
+        vec![format!("{prefix}city")]
+    }
+
+    fn to_sql_values(&self) -> Vec<SqlValue> {
+        vec![match &self.city {
+            Some(value) => SqlValue::Text(value.clone()),
+            None => SqlValue::Null,
+        }]
+    }
+}
+
+struct Order {
+    order_id: i32,
+    address: CtAddress,
+}
+
+impl SqlColumns for Order {
+    fn sql_column_names(prefix: &str) -> Vec<String> {
+        let mut names = vec![format!("{prefix}order_id")];
+        names.extend(CtAddress::sql_column_names(&format!("{prefix}address_")));
+        names
+    }
+
+    fn to_sql_values(&self) -> Vec<SqlValue> {
+        let mut values = vec![SqlValue::Integer(i64::from(self.order_id))];
+        values.extend(self.address.to_sql_values());
+        values
+    }
+}
+
+impl SqlRow for Order {
+    const SQL_TABLE_NAME: &'static str = "order";
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_build_insert_statement() -> Result<(), String> {
+    handle_test_comparison(
+        &Order::sql_insert_statement(SqlDialect::Postgres),
+        &r#"INSERT INTO "order" ("order_id", "address_city") VALUES ($1, $2)"#.to_owned(),
+    )?;
+    handle_test_comparison(
+        &Order::sql_insert_statement(SqlDialect::Sqlite),
+        &r#"INSERT INTO "order" ("order_id", "address_city") VALUES (?1, ?2)"#.to_owned(),
+    )
+}
+
+#[test]
+pub fn should_flatten_complex_type_values() -> Result<(), String> {
+    let order = Order {
+        order_id: 42,
+        address: CtAddress { city: None },
+    };
+
+    handle_test_bool(order.to_sql_values().eq(&vec![SqlValue::Integer(42), SqlValue::Null]))
+}
@@ -1,4 +1,13 @@
-use std::fmt::{Debug, Display};
+use proc_macro2::TokenStream;
+use std::{
+    fmt::{Debug, Display},
+    fs::read_to_string,
+    str::FromStr,
+};
+
+use crate::edmx::Edmx;
+
+static PATH_TO_MOCK_SERVICE: &str = "./test_data/mock_service.xml";
 
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 pub fn handle_test_bool(received_bool: bool) -> Result<(), String> {
@@ -39,3 +48,19 @@ pub fn to_rust_src(raw_src: Vec<u8>) -> Vec<String> {
         .map(|c| String::from_utf8(c.to_vec()).unwrap())
         .collect()
 }
+
+pub fn tokens_to_rust_src(tokens: TokenStream) -> Vec<String> {
+    let file = syn::parse2::<syn::File>(tokens).unwrap();
+
+    prettyplease::unparse(&file)
+        .lines()
+        .map(|line| line.trim().to_string())
+        .collect()
+}
+
+// Parse the mock service metadata shared by the generator tests
+pub fn fetch_mock_service_edmx() -> Result<Edmx, String> {
+    let xml = read_to_string(PATH_TO_MOCK_SERVICE).map_err(|err| err.to_string())?;
+
+    Edmx::from_str(&xml).map_err(|err| err.to_string())
+}
@@ -0,0 +1,62 @@
+use crate::property::Property;
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Pairs a property with the property holding its human-readable description
+///
+/// A pairing is declared either by an `sap:text` annotation on the value property, or by an `sap:text-for` annotation
+/// on the text property.
+/// Both property names are the OData names as they appear in the metadata document
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextPairing {
+    pub value_property: String,
+    pub text_property: String,
+}
+
+impl TextPairing {
+    /// Find all the text pairings whose value and text properties both exist in the same list of properties
+    ///
+    /// Pairings are returned in the alphabetical order of the value property names
+    pub fn find_in(props: &[Property]) -> Vec<TextPairing> {
+        let has_prop = |name: &str| props.iter().any(|prop| prop.odata_name.eq(name));
+
+        let mut pairings: Vec<TextPairing> = props
+            .iter()
+            .flat_map(|prop| {
+                let from_text = prop.sap_annotations.text.as_ref().map(|text| TextPairing {
+                    value_property: prop.odata_name.clone(),
+                    text_property: text.clone(),
+                });
+                let from_text_for = prop.sap_annotations.text_for.as_ref().map(|value| TextPairing {
+                    value_property: value.clone(),
+                    text_property: prop.odata_name.clone(),
+                });
+
+                [from_text, from_text_for]
+            })
+            .flatten()
+            .filter(|tp| has_prop(&tp.value_property) && has_prop(&tp.text_property))
+            .collect();
+
+        pairings.sort_by(|a, b| a.value_property.cmp(&b.value_property));
+
+        // The same pairing may be declared from both ends, and a value property can only have one text property
+        pairings.dedup_by(|a, b| a.value_property.eq(&b.value_property));
+        pairings
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Render a value together with its description as `"value (text)"`
+///
+/// If the description is missing or empty, only the value is rendered
+pub fn value_with_text<V: std::fmt::Display>(value: V, text: Option<&str>) -> String {
+    match text {
+        Some(txt) if !txt.is_empty() => format!("{value} ({txt})"),
+        _ => value.to_string(),
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(feature = "parser")]
+#[cfg(test)]
+pub mod unit_tests;
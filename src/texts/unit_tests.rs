@@ -0,0 +1,90 @@
+use std::{
+    fs::File,
+    io::{BufReader, Read},
+    path::Path,
+    str::FromStr,
+};
+
+use super::{value_with_text, TextPairing};
+use crate::{edmx::data_services::schema::entity_type::EntityType, test_utils::*};
+
+static PATH_TO_ENTITY_TYPE_PRODUCT: &str = "./test_data/entity_type_product.xml";
+
+/// The Product test data contains no text annotations, so add some:
+/// * `SupplierID` declares `sap:text="SupplierName"`
+/// * `Name` declares `sap:text-for="ProductID"`
+pub fn fetch_entity_type_product_with_texts() -> Result<EntityType, String> {
+    let mut xml_buffer: Vec<u8> = Vec::new();
+    let test_data = File::open(Path::new(PATH_TO_ENTITY_TYPE_PRODUCT)).unwrap();
+    let _file_size = BufReader::new(test_data).read_to_end(&mut xml_buffer);
+
+    let mut product = match String::from_utf8(xml_buffer) {
+        Ok(xml) => EntityType::from_str(&xml).map_err(|err| err.to_string()),
+        Err(err) => Err(format!("XML test data was not in UTF8 format: {err}")),
+    }?;
+
+    for prop in product.properties.iter_mut() {
+        match prop.odata_name.as_str() {
+            "SupplierID" => prop.sap_annotations.text = Some("SupplierName".to_string()),
+            "Name" => prop.sap_annotations.text_for = Some("ProductID".to_string()),
+            _ => {},
+        }
+    }
+
+    Ok(product)
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_find_text_pairings() -> Result<(), String> {
+    let product = fetch_entity_type_product_with_texts()?;
+    let pairings = TextPairing::find_in(&product.properties);
+
+    handle_test_comparison(&pairings.len(), &2)?;
+
+    // Pairings are sorted by value property name
+    handle_test_comparison(&pairings[0].value_property, &"ProductID".to_string())?;
+    handle_test_comparison(&pairings[0].text_property, &"Name".to_string())?;
+    handle_test_comparison(&pairings[1].value_property, &"SupplierID".to_string())?;
+    handle_test_comparison(&pairings[1].text_property, &"SupplierName".to_string())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_merge_pairing_declared_from_both_ends() -> Result<(), String> {
+    let mut product = fetch_entity_type_product_with_texts()?;
+
+    for prop in product.properties.iter_mut() {
+        if prop.odata_name.eq("SupplierName") {
+            prop.sap_annotations.text_for = Some("SupplierID".to_string());
+        }
+    }
+
+    let pairings = TextPairing::find_in(&product.properties);
+
+    handle_test_comparison(&pairings.len(), &2)?;
+    handle_test_comparison(&pairings[1].value_property, &"SupplierID".to_string())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_ignore_text_property_not_in_same_type() -> Result<(), String> {
+    let mut product = fetch_entity_type_product_with_texts()?;
+    product.properties.retain(|prop| !prop.odata_name.eq("SupplierName"));
+
+    let pairings = TextPairing::find_in(&product.properties);
+
+    handle_test_comparison(&pairings.len(), &1)?;
+    handle_test_comparison(&pairings[0].value_property, &"ProductID".to_string())
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+fn should_display_value_with_text() -> Result<(), String> {
+    handle_test_comparison(
+        &value_with_text("HT-1000", Some("Notebook Basic 15")),
+        &"HT-1000 (Notebook Basic 15)".to_string(),
+    )?;
+    handle_test_comparison(&value_with_text(42, Some("")), &"42".to_string())?;
+    handle_test_comparison(&value_with_text("HT-1000", None), &"HT-1000".to_string())
+}
@@ -49,3 +49,8 @@ pub fn to_snake_case(odata_name: &str) -> String {
 pub fn odata_name_to_rust_safe_name(odata_name: &str) -> String {
     CheckKeyword::into_safe(to_snake_case(odata_name))
 }
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[cfg(feature = "parser")]
+#[cfg(test)]
+pub mod unit_tests;
@@ -2,22 +2,78 @@ use std::{
     env,
     fs::OpenOptions,
     io::Write,
-    path::Path,
+    path::{Path, PathBuf},
     process::{Command, Stdio},
 };
 
 use anyhow::{anyhow, Context};
+use proc_macro2::TokenStream;
 use which::which;
 
+use crate::parser::options::{Formatter, RustfmtOptions};
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Format the generated tokens as a Rust source file using the requested formatter
+///
+/// If the tokens do not form a valid Rust source file, or `rustfmt` reports an error, the source code is still written
+/// to `OUT_DIR`, but with `failed_` prefixed to the filename
+pub fn format_tokens(tokens: TokenStream, file_name: &str, formatter: &Formatter) -> Result<Vec<u8>, anyhow::Error> {
+    let syntax_tree = match syn::parse2::<syn::File>(tokens.clone()) {
+        Ok(syntax_tree) => syntax_tree,
+        Err(err) => {
+            write_failed_source(tokens.to_string().as_bytes(), file_name)?;
+            return Err(anyhow!("Syntax error in generated source code: {}", err));
+        },
+    };
+
+    match formatter {
+        Formatter::Unformatted => Ok(tokens.to_string().into_bytes()),
+        Formatter::Prettyplease => Ok(prettyplease::unparse(&syntax_tree).into_bytes()),
+        Formatter::Rustfmt(rustfmt_opts) => {
+            let pretty_src = prettyplease::unparse(&syntax_tree).into_bytes();
+
+            match find_rustfmt(rustfmt_opts) {
+                Ok(rustfmt_path) => run_rustfmt(&rustfmt_path, rustfmt_opts, &pretty_src, file_name),
+                Err(err) => {
+                    println!("cargo:warning={}  Formatting '{}' with prettyplease instead", err, file_name);
+                    Ok(pretty_src)
+                },
+            }
+        },
+    }
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+/// Locate the `rustfmt` binary, either at the path given in the options or in the `PATH`
+pub fn find_rustfmt(rustfmt_opts: &RustfmtOptions) -> Result<PathBuf, anyhow::Error> {
+    match &rustfmt_opts.path {
+        Some(path) if path.is_file() => Ok(path.clone()),
+        Some(path) => Err(anyhow!("Cannot find `rustfmt` at {}.", path.display())),
+        None => which("rustfmt").with_context(|| "Cannot find `rustfmt` in the path.  Is it installed?"),
+    }
+}
+
 // - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
 /// Pass the generated source code through `rustfmt`
 ///
 /// If `rustfmt` finds any errors, the source code is still written to `OUT_DIR`, but with `failed_` prefixed to the
 /// filename
-pub fn run_rustfmt(buffer: &[u8], file_name: &str) -> Result<Vec<u8>, anyhow::Error> {
-    let rustfmt_path = which("rustfmt").with_context(|| "Cannot find `rustfmt` in the path.  Is it installed?")?;
+pub fn run_rustfmt(
+    rustfmt_path: &Path,
+    rustfmt_opts: &RustfmtOptions,
+    buffer: &[u8],
+    file_name: &str,
+) -> Result<Vec<u8>, anyhow::Error> {
+    let mut fmt_cmd = Command::new(rustfmt_path);
+
+    if let Some(edition) = &rustfmt_opts.edition {
+        fmt_cmd.arg("--edition").arg(edition);
+    }
+    if let Some(config_path) = &rustfmt_opts.config_path {
+        fmt_cmd.arg("--config-path").arg(config_path);
+    }
 
-    let mut fmt_proc = Command::new(rustfmt_path)
+    let mut fmt_proc = fmt_cmd
         .stdin(Stdio::piped())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -34,21 +90,27 @@ pub fn run_rustfmt(buffer: &[u8], file_name: &str) -> Result<Vec<u8>, anyhow::Er
     if rustfmt_output.status.success() {
         Ok(rustfmt_output.stdout)
     } else {
-        // For diagnostic purposes during development, write the failed source code to $OUT_DIR as file
-        // "failed_<metadata_file_name>.rs"
-        let out_dir = env::var_os("OUT_DIR").unwrap();
-        let failed_file_name = format!("failed_{}", file_name);
-        let gen_failed_path = Path::new(&out_dir).join(failed_file_name);
-
-        let _dont_care = OpenOptions::new()
-            .create(true)
-            .write(true)
-            .truncate(true)
-            .open(&gen_failed_path)?
-            .write_all(&buffer);
+        write_failed_source(buffer, file_name)?;
 
         let rustfmt_err_out = std::str::from_utf8(&rustfmt_output.stderr)?;
 
         Err(anyhow!("Syntax error in generated source code:\n{}", rustfmt_err_out))
     }
 }
+
+// For diagnostic purposes during development, write the failed source code to $OUT_DIR as file
+// "failed_<metadata_file_name>.rs".  The files of a module tree are written as "failed_<dir>_<file_name>.rs"
+fn write_failed_source(buffer: &[u8], file_name: &str) -> Result<(), anyhow::Error> {
+    let out_dir = env::var_os("OUT_DIR").unwrap();
+    let failed_file_name = format!("failed_{}", file_name.replace(['/', '\\'], "_"));
+    let gen_failed_path = Path::new(&out_dir).join(failed_file_name);
+
+    let _dont_care = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .truncate(true)
+        .open(&gen_failed_path)?
+        .write_all(buffer);
+
+    Ok(())
+}
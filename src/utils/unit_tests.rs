@@ -0,0 +1,71 @@
+use std::path::PathBuf;
+
+use super::rust_tools::*;
+use crate::{
+    parser::options::{Formatter, RustfmtOptions},
+    test_utils::*,
+};
+
+use quote::quote;
+
+fn gen_tokens() -> proc_macro2::TokenStream {
+    quote!(
+        pub struct Wibble {
+            pub wobble: Option<String>,
+        }
+    )
+}
+
+// - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - - -
+#[test]
+pub fn should_skip_formatting() -> Result<(), String> {
+    let src = format_tokens(gen_tokens(), "wibble.rs", &Formatter::Unformatted).map_err(|err| err.to_string())?;
+
+    handle_test_comparison(&String::from_utf8(src).unwrap(), &gen_tokens().to_string())
+}
+
+#[test]
+pub fn should_format_with_prettyplease() -> Result<(), String> {
+    let src = format_tokens(gen_tokens(), "wibble.rs", &Formatter::Prettyplease).map_err(|err| err.to_string())?;
+
+    handle_test_comparison(
+        &String::from_utf8(src).unwrap(),
+        &"pub struct Wibble {\n    pub wobble: Option<String>,\n}\n".to_string(),
+    )
+}
+
+#[test]
+pub fn should_fall_back_to_prettyplease_without_rustfmt() -> Result<(), String> {
+    let rustfmt_opts = RustfmtOptions {
+        path: Some(PathBuf::from("./no/such/rustfmt")),
+        ..Default::default()
+    };
+    let pretty_src =
+        format_tokens(gen_tokens(), "wibble.rs", &Formatter::Prettyplease).map_err(|err| err.to_string())?;
+    let src = format_tokens(gen_tokens(), "wibble.rs", &Formatter::Rustfmt(rustfmt_opts.clone()))
+        .map_err(|err| err.to_string())?;
+
+    handle_test_bool(find_rustfmt(&rustfmt_opts).is_err())?;
+    handle_test_comparison(&String::from_utf8(src).unwrap(), &String::from_utf8(pretty_src).unwrap())
+}
+
+#[test]
+pub fn should_format_with_rustfmt() -> Result<(), String> {
+    let rustfmt_opts = RustfmtOptions {
+        edition: Some("2021".to_string()),
+        ..Default::default()
+    };
+
+    // This test can only be run where rustfmt is installed
+    if find_rustfmt(&rustfmt_opts).is_err() {
+        return Ok(());
+    }
+
+    let src =
+        format_tokens(gen_tokens(), "wibble.rs", &Formatter::Rustfmt(rustfmt_opts)).map_err(|err| err.to_string())?;
+
+    handle_test_comparison(
+        &String::from_utf8(src).unwrap(),
+        &"pub struct Wibble {\n    pub wobble: Option<String>,\n}\n".to_string(),
+    )
+}
@@ -0,0 +1,65 @@
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct Product {
+    #[serde(
+        deserialize_with = "parse_sap_atom_feed::deserializers::edm_datetime::to_naive_date_time_opt"
+    )]
+    pub changed_at: Option<chrono::NaiveDateTime>,
+    pub currency_code: Option<String>,
+    pub name: String,
+    #[serde(
+        deserialize_with = "parse_sap_atom_feed::deserializers::edm_decimal::to_rust_decimal_3dp_opt"
+    )]
+    pub price: Option<rust_decimal::Decimal>,
+    #[serde(rename = "ProductID")]
+    pub product_id: String,
+    #[serde(rename = "SupplierID")]
+    pub supplier_id: String,
+}
+impl std::str::FromStr for Product {
+    type Err = quick_xml::DeError;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        quick_xml::de::from_str(s)
+    }
+}
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct ProductBorrowed<'a> {
+    #[serde(
+        deserialize_with = "parse_sap_atom_feed::deserializers::edm_datetime::to_naive_date_time_opt"
+    )]
+    pub changed_at: Option<chrono::NaiveDateTime>,
+    #[serde(
+        default,
+        borrow,
+        deserialize_with = "parse_sap_odata::deserializers::to_nullable_cow_str"
+    )]
+    pub currency_code: Option<std::borrow::Cow<'a, str>>,
+    #[serde(borrow)]
+    pub name: std::borrow::Cow<'a, str>,
+    #[serde(
+        deserialize_with = "parse_sap_atom_feed::deserializers::edm_decimal::to_rust_decimal_3dp_opt"
+    )]
+    pub price: Option<rust_decimal::Decimal>,
+    #[serde(rename = "ProductID")]
+    #[serde(borrow)]
+    pub product_id: std::borrow::Cow<'a, str>,
+    #[serde(rename = "SupplierID")]
+    #[serde(borrow)]
+    pub supplier_id: std::borrow::Cow<'a, str>,
+}
+impl<'a> ProductBorrowed<'a> {
+    pub fn from_xml(xml: &'a str) -> Result<Self, quick_xml::DeError> {
+        quick_xml::de::from_str(xml)
+    }
+    pub fn into_owned(self) -> Product {
+        Product {
+            changed_at: self.changed_at,
+            currency_code: self.currency_code.map(std::borrow::Cow::into_owned),
+            name: self.name.into_owned(),
+            price: self.price,
+            product_id: self.product_id.into_owned(),
+            supplier_id: self.supplier_id.into_owned(),
+        }
+    }
+}